@@ -1,4 +1,4 @@
-use crate::ffi::collections::{ConstSpan, MutSpan, NonNullConst, Optional, Result};
+use crate::ffi::collections::{ConstSpan, NonNullConst, Optional, Result, SpanMut, SpanRef};
 use crate::ffi::errors::Error;
 use crate::ffi::library::api::LibraryBinding;
 use crate::ffi::library::library_loader::LibraryLoaderInterface;
@@ -17,11 +17,18 @@ use crate::fn_caster::FnCaster;
 use crate::library::LibraryAPI;
 use crate::module::ModuleAPI;
 use crate::ownership::Owned;
+use crate::sys::sync_handler::lock_interface_timeout;
 use crate::sys::{SysAPI, SysAPIMin};
 use crate::version::{Version, VersionAPI};
+use crate::wake_queue;
 use std::cell::UnsafeCell;
 use std::ffi::c_void;
+use std::future::Future;
 use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
 
 /// Borrowed `emf-core-base` interface.
 #[derive(Debug)]
@@ -41,6 +48,43 @@ pub struct CBase<'interface> {
 unsafe impl Send for CBase<'_> {}
 unsafe impl Sync for CBase<'_> {}
 
+/// A locked `emf-core-base` interface.
+///
+/// The interface is unlocked when the guard is dropped.
+/// The guard dereferences to a copy of the locked interface. Handles obtained
+/// through it are bound to the borrow of the [CBase] and can therefore not outlive it.
+///
+/// # Example
+///
+/// ```no_run
+/// # use emf_core_base_rs::CBase;
+/// # let base_interface: &CBase = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// use emf_core_base_rs::module::{ModuleAPI, DEFAULT_HANDLE};
+///
+/// let mut guard = base_interface.lock_guard();
+/// let module = ModuleAPI::add_module(&mut *guard, &DEFAULT_HANDLE, "path to a module");
+/// assert_eq!(module.is_ok(), true);
+/// ```
+///
+/// ```compile_fail
+/// # use emf_core_base_rs::CBase;
+/// # let base_interface: CBase = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// use emf_core_base_rs::module::{ModuleAPI, DEFAULT_HANDLE};
+///
+/// let module = {
+///     let mut guard = base_interface.lock_guard();
+///     ModuleAPI::add_module(&mut *guard, &DEFAULT_HANDLE, "path to a module")
+/// };
+///
+/// drop(base_interface);
+/// assert_eq!(module.is_ok(), true);
+/// ```
+#[derive(Debug)]
+pub struct CBaseGuard<'a> {
+    _base: CBaseRef<'a>,
+    _interface: CBaseRef<'a>,
+}
+
 impl CBaseRef<'_> {
     /// Constructs itself using the native interface.
     ///
@@ -66,6 +110,146 @@ impl<'interface> CBase<'interface> {
             _interface: UnsafeCell::new(interface),
         }
     }
+
+    /// Locks the interface.
+    ///
+    /// The calling thread will wait until it can acquire the lock.
+    ///
+    /// # Return
+    ///
+    /// A guard which unlocks the interface when dropped.
+    #[inline]
+    #[must_use]
+    pub fn lock_guard(&self) -> CBaseGuard<'_> {
+        unsafe {
+            let base = self.copy_ref();
+            SysBinding::lock(&base);
+            CBaseGuard::new(base)
+        }
+    }
+
+    /// Tries to lock the interface.
+    ///
+    /// The function fails if another thread already holds the lock.
+    ///
+    /// # Return
+    ///
+    /// A guard which unlocks the interface when dropped on success
+    /// and [Option::None] otherwise.
+    #[inline]
+    #[must_use]
+    pub fn try_lock_guard(&self) -> Option<CBaseGuard<'_>> {
+        unsafe {
+            let base = self.copy_ref();
+            match SysBinding::try_lock(&base) {
+                Bool::False => Option::None,
+                Bool::True => Some(CBaseGuard::new(base)),
+            }
+        }
+    }
+
+    /// Locks the interface asynchronously.
+    ///
+    /// The returned future resolves once the lock has been acquired. Instead of
+    /// blocking, the future is woken up whenever the interface is unlocked by a
+    /// [CBase], [CBaseGuard] or [LockToken](crate::global::LockToken) and
+    /// retries acquiring the lock. Other unlocks, e.g. by the host, other modules
+    /// or directly through the [SysBinding], are noticed by periodically retrying.
+    ///
+    /// # Return
+    ///
+    /// A future resolving to a guard which unlocks the interface when dropped.
+    #[inline]
+    pub fn lock_async(&self) -> CBaseLockFuture<'_> {
+        CBaseLockFuture {
+            _base: unsafe { self.copy_ref() },
+            _key: None,
+        }
+    }
+
+    /// Copies the stored interface, bound to the borrow of `self`.
+    ///
+    /// # Safety
+    ///
+    /// The copy bypasses the locking mechanism.
+    #[inline]
+    unsafe fn copy_ref(&self) -> CBaseRef<'_> {
+        CBaseRef::new((*self._interface.get())._interface)
+    }
+
+    #[inline]
+    unsafe fn unlock(&self) {
+        SysBinding::unlock(&*self._interface.get());
+        wake_queue::notify_unlock(&*self._interface.get());
+    }
+}
+
+impl<'a> CBaseGuard<'a> {
+    /// Constructs a guard for an already locked interface.
+    ///
+    /// # Safety
+    ///
+    /// The interface must be locked by the caller.
+    #[inline]
+    unsafe fn new(base: CBaseRef<'a>) -> Self {
+        Self {
+            _interface: CBaseRef::new(base._interface),
+            _base: base,
+        }
+    }
+}
+
+impl<'a> Deref for CBaseGuard<'a> {
+    type Target = CBaseRef<'a>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self._interface
+    }
+}
+
+impl DerefMut for CBaseGuard<'_> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self._interface
+    }
+}
+
+impl Drop for CBaseGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        // Unlock the locked interface, even if the dereferenced copy has been replaced.
+        unsafe { SysBinding::unlock(&self._base) }
+        wake_queue::notify_unlock(&self._base);
+    }
+}
+
+/// Future returned by [CBase::lock_async].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct CBaseLockFuture<'a> {
+    _base: CBaseRef<'a>,
+    _key: Option<usize>,
+}
+
+impl<'a> Future for CBaseLockFuture<'a> {
+    type Output = CBaseGuard<'a>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let base = unsafe { CBaseRef::new(self._base._interface) };
+        let try_lock = || unsafe { SysBinding::try_lock(&base) == Bool::True };
+        match wake_queue::poll_lock(&base, &mut self._key, cx, try_lock) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(_) => Poll::Ready(unsafe { CBaseGuard::new(base) }),
+        }
+    }
+}
+
+impl Drop for CBaseLockFuture<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        wake_queue::cancel(&self._base, self._key.take())
+    }
 }
 
 /// A trait for providing information about the loaded interface.
@@ -123,6 +307,20 @@ pub trait CBaseAccess<'interface> {
     /// the lock could not be acquired.
     fn try_lock<U>(&self, f: impl FnOnce(&mut Self::Interface) -> U) -> Option<U>;
 
+    /// Enters the critical section with the provided function.
+    ///
+    /// The calling thread will wait at most `timeout` to acquire the lock.
+    ///
+    /// # Return
+    ///
+    /// Return value from the provided function or [Option::None] if
+    /// the lock could not be acquired in time.
+    fn lock_timeout<U>(
+        &self,
+        timeout: Duration,
+        f: impl FnOnce(&mut Self::Interface) -> U,
+    ) -> Option<U>;
+
     /// Enters the critical section with the provided function without locking.
     ///
     /// # Return
@@ -150,44 +348,62 @@ impl SysBinding for CBaseRef<'_> {
 
     #[inline]
     unsafe fn has_function(&self, id: FnId) -> Bool {
-        SysBinding::has_function(&self._interface, id)
+        traced!("sys.has_function", [?id], {
+            SysBinding::has_function(&self._interface, id)
+        })
     }
 
     #[inline]
     unsafe fn get_function(&self, id: FnId) -> Optional<fn()> {
-        SysBinding::get_function(&self._interface, id)
+        traced!("sys.get_function", [?id], {
+            SysBinding::get_function(&self._interface, id)
+        })
     }
 
     #[inline]
     unsafe fn lock(&self) {
-        SysBinding::lock(&self._interface)
+        let start = crate::trace::now();
+        SysBinding::lock(&self._interface);
+        crate::trace::lock_acquired(start);
     }
 
     #[inline]
     unsafe fn try_lock(&self) -> Bool {
-        SysBinding::try_lock(&self._interface)
+        let start = crate::trace::now();
+        let locked = SysBinding::try_lock(&self._interface);
+        if locked == Bool::True {
+            crate::trace::lock_acquired(start);
+        }
+        locked
     }
 
     #[inline]
     unsafe fn unlock(&self) {
-        SysBinding::unlock(&self._interface)
+        SysBinding::unlock(&self._interface);
+        crate::trace::lock_released();
     }
 
     #[inline]
     unsafe fn get_sync_handler(&self) -> SyncHandlerInterface {
-        SysBinding::get_sync_handler(&self._interface)
+        traced!("sys.get_sync_handler", {
+            SysBinding::get_sync_handler(&self._interface)
+        })
     }
 
     #[inline]
     unsafe fn set_sync_handler(&mut self, handler: Optional<SyncHandlerInterface>) {
-        SysBinding::set_sync_handler(&mut self._interface, handler)
+        traced!("sys.set_sync_handler", {
+            SysBinding::set_sync_handler(&mut self._interface, handler)
+        })
     }
 }
 
 impl VersionBinding for CBaseRef<'_> {
     #[inline]
     unsafe fn new_short(&self, major: i32, minor: i32, patch: i32) -> Version {
-        VersionBinding::new_short(&self._interface, major, minor, patch)
+        traced!("version.new_short", [major, minor, patch], {
+            VersionBinding::new_short(&self._interface, major, minor, patch)
+        })
     }
 
     #[inline]
@@ -199,14 +415,16 @@ impl VersionBinding for CBaseRef<'_> {
         release_type: version::ReleaseType,
         release_number: i8,
     ) -> Version {
-        VersionBinding::new_long(
-            &self._interface,
-            major,
-            minor,
-            patch,
-            release_type,
-            release_number,
-        )
+        traced!("version.new_long", [major, minor, patch, %release_type, release_number], {
+            VersionBinding::new_long(
+                &self._interface,
+                major,
+                minor,
+                patch,
+                release_type,
+                release_number,
+            )
+        })
     }
 
     #[inline]
@@ -219,87 +437,168 @@ impl VersionBinding for CBaseRef<'_> {
         release_number: i8,
         build: i64,
     ) -> Version {
-        VersionBinding::new_full(
-            &self._interface,
-            major,
-            minor,
-            patch,
-            release_type,
-            release_number,
-            build,
-        )
+        traced!("version.new_full", [major, minor, patch, %release_type, release_number, build], {
+            VersionBinding::new_full(
+                &self._interface,
+                major,
+                minor,
+                patch,
+                release_type,
+                release_number,
+                build,
+            )
+        })
     }
 
     #[inline]
-    unsafe fn from_string(&self, buffer: ConstSpan<u8>) -> Result<Version, Error> {
-        VersionBinding::from_string(&self._interface, buffer)
+    unsafe fn from_string(&self, buffer: SpanRef<'_, u8>) -> Result<Version, Error> {
+        traced!("version.from_string", [buffer = %crate::trace::string(&buffer)], {
+            VersionBinding::from_string(&self._interface, buffer)
+        })
     }
 
     #[inline]
     unsafe fn string_length_short(&self, version: NonNullConst<Version>) -> usize {
-        VersionBinding::string_length_short(&self._interface, version)
+        traced!("version.string_length_short", [version = %crate::trace::pointee(version)], {
+            VersionBinding::string_length_short(&self._interface, version)
+        })
     }
 
     #[inline]
     unsafe fn string_length_long(&self, version: NonNullConst<Version>) -> usize {
-        VersionBinding::string_length_long(&self._interface, version)
+        traced!("version.string_length_long", [version = %crate::trace::pointee(version)], {
+            VersionBinding::string_length_long(&self._interface, version)
+        })
     }
 
     #[inline]
     unsafe fn string_length_full(&self, version: NonNullConst<Version>) -> usize {
-        VersionBinding::string_length_full(&self._interface, version)
+        traced!("version.string_length_full", [version = %crate::trace::pointee(version)], {
+            VersionBinding::string_length_full(&self._interface, version)
+        })
     }
 
     #[inline]
     unsafe fn as_string_short(
         &self,
         version: NonNullConst<Version>,
-        buffer: MutSpan<u8>,
+        buffer: SpanMut<'_, u8>,
     ) -> Result<usize, Error> {
-        VersionBinding::as_string_short(&self._interface, version, buffer)
+        traced!(
+            "version.as_string_short",
+            [
+                version = %crate::trace::pointee(version),
+                buffer_len = buffer.len()
+            ],
+            {
+                VersionBinding::as_string_short(&self._interface, version, buffer)
+            }
+        )
     }
 
     #[inline]
     unsafe fn as_string_long(
         &self,
         version: NonNullConst<Version>,
-        buffer: MutSpan<u8>,
+        buffer: SpanMut<'_, u8>,
     ) -> Result<usize, Error> {
-        VersionBinding::as_string_long(&self._interface, version, buffer)
+        traced!(
+            "version.as_string_long",
+            [
+                version = %crate::trace::pointee(version),
+                buffer_len = buffer.len()
+            ],
+            {
+                VersionBinding::as_string_long(&self._interface, version, buffer)
+            }
+        )
     }
 
     #[inline]
     unsafe fn as_string_full(
         &self,
         version: NonNullConst<Version>,
-        buffer: MutSpan<u8>,
+        buffer: SpanMut<'_, u8>,
     ) -> Result<usize, Error> {
-        VersionBinding::as_string_full(&self._interface, version, buffer)
+        traced!(
+            "version.as_string_full",
+            [
+                version = %crate::trace::pointee(version),
+                buffer_len = buffer.len()
+            ],
+            {
+                VersionBinding::as_string_full(&self._interface, version, buffer)
+            }
+        )
     }
 
     #[inline]
-    unsafe fn string_is_valid(&self, version_string: ConstSpan<u8>) -> Bool {
-        VersionBinding::string_is_valid(&self._interface, version_string)
+    unsafe fn string_is_valid(&self, version_string: SpanRef<'_, u8>) -> Bool {
+        traced!(
+            "version.string_is_valid",
+            [
+                version_string = %crate::trace::string(&version_string)
+            ],
+            {
+                VersionBinding::string_is_valid(&self._interface, version_string)
+            }
+        )
     }
 
     #[inline]
     unsafe fn compare(&self, lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> i32 {
-        VersionBinding::compare(&self._interface, lhs, rhs)
+        traced!(
+            "version.compare",
+            [
+                lhs = %crate::trace::pointee(lhs),
+                rhs = %crate::trace::pointee(rhs)
+            ],
+            {
+                VersionBinding::compare(&self._interface, lhs, rhs)
+            }
+        )
     }
 
     #[inline]
     unsafe fn compare_weak(&self, lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> i32 {
-        VersionBinding::compare_weak(&self._interface, lhs, rhs)
+        traced!(
+            "version.compare_weak",
+            [
+                lhs = %crate::trace::pointee(lhs),
+                rhs = %crate::trace::pointee(rhs)
+            ],
+            {
+                VersionBinding::compare_weak(&self._interface, lhs, rhs)
+            }
+        )
     }
 
     #[inline]
     unsafe fn compare_strong(&self, lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> i32 {
-        VersionBinding::compare_strong(&self._interface, lhs, rhs)
+        traced!(
+            "version.compare_strong",
+            [
+                lhs = %crate::trace::pointee(lhs),
+                rhs = %crate::trace::pointee(rhs)
+            ],
+            {
+                VersionBinding::compare_strong(&self._interface, lhs, rhs)
+            }
+        )
     }
 
     #[inline]
     unsafe fn is_compatible(&self, lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> Bool {
-        VersionBinding::is_compatible(&self._interface, lhs, rhs)
+        traced!(
+            "version.is_compatible",
+            [
+                lhs = %crate::trace::pointee(lhs),
+                rhs = %crate::trace::pointee(rhs)
+            ],
+            {
+                VersionBinding::is_compatible(&self._interface, lhs, rhs)
+            }
+        )
     }
 }
 
@@ -310,12 +609,16 @@ impl LibraryBinding for CBaseRef<'_> {
         loader: LibraryLoaderInterface,
         lib_type: NonNullConst<library::LibraryType>,
     ) -> Result<library::LoaderHandle, Error> {
-        LibraryBinding::register_loader(&mut self._interface, loader, lib_type)
+        traced!("library.register_loader", [lib_type = %crate::trace::pointee(lib_type)], {
+            LibraryBinding::register_loader(&mut self._interface, loader, lib_type)
+        })
     }
 
     #[inline]
     unsafe fn unregister_loader(&mut self, handle: library::LoaderHandle) -> Result<i8, Error> {
-        LibraryBinding::unregister_loader(&mut self._interface, handle)
+        traced!("library.unregister_loader", [handle = %handle], {
+            LibraryBinding::unregister_loader(&mut self._interface, handle)
+        })
     }
 
     #[inline]
@@ -323,7 +626,9 @@ impl LibraryBinding for CBaseRef<'_> {
         &self,
         handle: library::LoaderHandle,
     ) -> Result<LibraryLoaderInterface, Error> {
-        LibraryBinding::get_loader_interface(&self._interface, handle)
+        traced!("library.get_loader_interface", [handle = %handle], {
+            LibraryBinding::get_loader_interface(&self._interface, handle)
+        })
     }
 
     #[inline]
@@ -331,7 +636,15 @@ impl LibraryBinding for CBaseRef<'_> {
         &self,
         lib_type: NonNullConst<library::LibraryType>,
     ) -> Result<library::LoaderHandle, Error> {
-        LibraryBinding::get_loader_handle_from_type(&self._interface, lib_type)
+        traced!(
+            "library.get_loader_handle_from_type",
+            [
+                lib_type = %crate::trace::pointee(lib_type)
+            ],
+            {
+                LibraryBinding::get_loader_handle_from_type(&self._interface, lib_type)
+            }
+        )
     }
 
     #[inline]
@@ -339,35 +652,47 @@ impl LibraryBinding for CBaseRef<'_> {
         &self,
         handle: library::LibraryHandle,
     ) -> Result<library::LoaderHandle, Error> {
-        LibraryBinding::get_loader_handle_from_library(&self._interface, handle)
+        traced!("library.get_loader_handle_from_library", [handle = %handle], {
+            LibraryBinding::get_loader_handle_from_library(&self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn get_num_loaders(&self) -> usize {
-        LibraryBinding::get_num_loaders(&self._interface)
+        traced!("library.get_num_loaders", {
+            LibraryBinding::get_num_loaders(&self._interface)
+        })
     }
 
     #[inline]
     unsafe fn library_exists(&self, handle: library::LibraryHandle) -> Bool {
-        LibraryBinding::library_exists(&self._interface, handle)
+        traced!("library.library_exists", [handle = %handle], {
+            LibraryBinding::library_exists(&self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn type_exists(&self, lib_type: NonNullConst<library::LibraryType>) -> Bool {
-        LibraryBinding::type_exists(&self._interface, lib_type)
+        traced!("library.type_exists", [lib_type = %crate::trace::pointee(lib_type)], {
+            LibraryBinding::type_exists(&self._interface, lib_type)
+        })
     }
 
     #[inline]
     unsafe fn get_library_types(
         &self,
-        buffer: MutSpan<library::LibraryType>,
+        buffer: SpanMut<'_, library::LibraryType>,
     ) -> Result<usize, Error> {
-        LibraryBinding::get_library_types(&self._interface, buffer)
+        traced!("library.get_library_types", [buffer_len = buffer.len()], {
+            LibraryBinding::get_library_types(&self._interface, buffer)
+        })
     }
 
     #[inline]
     unsafe fn create_library_handle(&mut self) -> library::LibraryHandle {
-        LibraryBinding::create_library_handle(&mut self._interface)
+        traced!("library.create_library_handle", {
+            LibraryBinding::create_library_handle(&mut self._interface)
+        })
     }
 
     #[inline]
@@ -375,7 +700,9 @@ impl LibraryBinding for CBaseRef<'_> {
         &mut self,
         handle: library::LibraryHandle,
     ) -> Result<i8, Error> {
-        LibraryBinding::remove_library_handle(&mut self._interface, handle)
+        traced!("library.remove_library_handle", [handle = %handle], {
+            LibraryBinding::remove_library_handle(&mut self._interface, handle)
+        })
     }
 
     #[inline]
@@ -385,7 +712,17 @@ impl LibraryBinding for CBaseRef<'_> {
         loader: library::LoaderHandle,
         internal: library::InternalHandle,
     ) -> Result<i8, Error> {
-        LibraryBinding::link_library(&mut self._interface, handle, loader, internal)
+        traced!(
+            "library.link_library",
+            [
+                handle = %handle,
+                loader = %loader,
+                internal = %internal
+            ],
+            {
+                LibraryBinding::link_library(&mut self._interface, handle, loader, internal)
+            }
+        )
     }
 
     #[inline]
@@ -393,7 +730,9 @@ impl LibraryBinding for CBaseRef<'_> {
         &self,
         handle: library::LibraryHandle,
     ) -> Result<library::InternalHandle, Error> {
-        LibraryBinding::get_internal_library_handle(&self._interface, handle)
+        traced!("library.get_internal_library_handle", [handle = %handle], {
+            LibraryBinding::get_internal_library_handle(&self._interface, handle)
+        })
     }
 
     #[inline]
@@ -402,12 +741,16 @@ impl LibraryBinding for CBaseRef<'_> {
         loader: library::LoaderHandle,
         path: OSPathString,
     ) -> Result<library::LibraryHandle, Error> {
-        LibraryBinding::load(&mut self._interface, loader, path)
+        traced!("library.load", [loader = %loader, path = %crate::trace::path(&path)], {
+            LibraryBinding::load(&mut self._interface, loader, path)
+        })
     }
 
     #[inline]
     unsafe fn unload(&mut self, handle: library::LibraryHandle) -> Result<i8, Error> {
-        LibraryBinding::unload(&mut self._interface, handle)
+        traced!("library.unload", [handle = %handle], {
+            LibraryBinding::unload(&mut self._interface, handle)
+        })
     }
 
     #[inline]
@@ -416,7 +759,16 @@ impl LibraryBinding for CBaseRef<'_> {
         handle: library::LibraryHandle,
         symbol: SymbolName,
     ) -> Result<library::Symbol<NonNullConst<c_void>>, Error> {
-        LibraryBinding::get_data_symbol(&self._interface, handle, symbol)
+        traced!(
+            "library.get_data_symbol",
+            [
+                handle = %handle,
+                symbol = %crate::trace::string(&symbol)
+            ],
+            {
+                LibraryBinding::get_data_symbol(&self._interface, handle, symbol)
+            }
+        )
     }
 
     #[inline]
@@ -425,7 +777,16 @@ impl LibraryBinding for CBaseRef<'_> {
         handle: library::LibraryHandle,
         symbol: SymbolName,
     ) -> Result<library::Symbol<fn()>, Error> {
-        LibraryBinding::get_function_symbol(&self._interface, handle, symbol)
+        traced!(
+            "library.get_function_symbol",
+            [
+                handle = %handle,
+                symbol = %crate::trace::string(&symbol)
+            ],
+            {
+                LibraryBinding::get_function_symbol(&self._interface, handle, symbol)
+            }
+        )
     }
 }
 
@@ -436,12 +797,16 @@ impl ModuleBinding for CBaseRef<'_> {
         loader: ModuleLoaderInterface,
         mod_type: NonNullConst<module::ModuleType>,
     ) -> Result<module::LoaderHandle, Error> {
-        ModuleBinding::register_loader(&mut self._interface, loader, mod_type)
+        traced!("module.register_loader", [mod_type = %crate::trace::pointee(mod_type)], {
+            ModuleBinding::register_loader(&mut self._interface, loader, mod_type)
+        })
     }
 
     #[inline]
     unsafe fn unregister_loader(&mut self, loader: module::LoaderHandle) -> Result<i8, Error> {
-        ModuleBinding::unregister_loader(&mut self._interface, loader)
+        traced!("module.unregister_loader", [loader = %loader], {
+            ModuleBinding::unregister_loader(&mut self._interface, loader)
+        })
     }
 
     #[inline]
@@ -449,7 +814,9 @@ impl ModuleBinding for CBaseRef<'_> {
         &self,
         loader: module::LoaderHandle,
     ) -> Result<ModuleLoaderInterface, Error> {
-        ModuleBinding::get_loader_interface(&self._interface, loader)
+        traced!("module.get_loader_interface", [loader = %loader], {
+            ModuleBinding::get_loader_interface(&self._interface, loader)
+        })
     }
 
     #[inline]
@@ -457,7 +824,15 @@ impl ModuleBinding for CBaseRef<'_> {
         &self,
         mod_type: NonNullConst<module::ModuleType>,
     ) -> Result<module::LoaderHandle, Error> {
-        ModuleBinding::get_loader_handle_from_type(&self._interface, mod_type)
+        traced!(
+            "module.get_loader_handle_from_type",
+            [
+                mod_type = %crate::trace::pointee(mod_type)
+            ],
+            {
+                ModuleBinding::get_loader_handle_from_type(&self._interface, mod_type)
+            }
+        )
     }
 
     #[inline]
@@ -465,76 +840,119 @@ impl ModuleBinding for CBaseRef<'_> {
         &self,
         handle: module::ModuleHandle,
     ) -> Result<module::LoaderHandle, Error> {
-        ModuleBinding::get_loader_handle_from_module(&self._interface, handle)
+        traced!("module.get_loader_handle_from_module", [handle = %handle], {
+            ModuleBinding::get_loader_handle_from_module(&self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn get_num_modules(&self) -> usize {
-        ModuleBinding::get_num_modules(&self._interface)
+        traced!("module.get_num_modules", {
+            ModuleBinding::get_num_modules(&self._interface)
+        })
     }
 
     #[inline]
     unsafe fn get_num_loaders(&self) -> usize {
-        ModuleBinding::get_num_loaders(&self._interface)
+        traced!("module.get_num_loaders", {
+            ModuleBinding::get_num_loaders(&self._interface)
+        })
     }
 
     #[inline]
     unsafe fn get_num_exported_interfaces(&self) -> usize {
-        ModuleBinding::get_num_exported_interfaces(&self._interface)
+        traced!("module.get_num_exported_interfaces", {
+            ModuleBinding::get_num_exported_interfaces(&self._interface)
+        })
     }
 
     #[inline]
     unsafe fn module_exists(&self, handle: module::ModuleHandle) -> Bool {
-        ModuleBinding::module_exists(&self._interface, handle)
+        traced!("module.module_exists", [handle = %handle], {
+            ModuleBinding::module_exists(&self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn type_exists(&self, mod_type: NonNullConst<module::ModuleType>) -> Bool {
-        ModuleBinding::type_exists(&self._interface, mod_type)
+        traced!("module.type_exists", [mod_type = %crate::trace::pointee(mod_type)], {
+            ModuleBinding::type_exists(&self._interface, mod_type)
+        })
     }
 
     #[inline]
     unsafe fn exported_interface_exists(
         &self,
-        interface: NonNullConst<module::InterfaceDescriptor>,
+        interface: NonNullConst<module::InterfaceDescriptor<'_>>,
     ) -> Bool {
-        ModuleBinding::exported_interface_exists(&self._interface, interface)
+        traced!(
+            "module.exported_interface_exists",
+            [
+                interface = %crate::trace::pointee(interface)
+            ],
+            {
+                ModuleBinding::exported_interface_exists(&self._interface, interface)
+            }
+        )
     }
 
     #[inline]
-    unsafe fn get_modules(&self, buffer: MutSpan<module::ModuleInfo>) -> Result<usize, Error> {
-        ModuleBinding::get_modules(&self._interface, buffer)
+    unsafe fn get_modules(&self, buffer: SpanMut<'_, module::ModuleInfo>) -> Result<usize, Error> {
+        traced!("module.get_modules", [buffer_len = buffer.len()], {
+            ModuleBinding::get_modules(&self._interface, buffer)
+        })
     }
 
     #[inline]
-    unsafe fn get_module_types(&self, buffer: MutSpan<module::ModuleType>) -> Result<usize, Error> {
-        ModuleBinding::get_module_types(&self._interface, buffer)
+    unsafe fn get_module_types(
+        &self,
+        buffer: SpanMut<'_, module::ModuleType>,
+    ) -> Result<usize, Error> {
+        traced!("module.get_module_types", [buffer_len = buffer.len()], {
+            ModuleBinding::get_module_types(&self._interface, buffer)
+        })
     }
 
     #[inline]
     unsafe fn get_exported_interfaces(
         &self,
-        buffer: MutSpan<module::InterfaceDescriptor>,
+        buffer: SpanMut<'_, module::InterfaceDescriptor<'static>>,
     ) -> Result<usize, Error> {
-        ModuleBinding::get_exported_interfaces(&self._interface, buffer)
+        traced!(
+            "module.get_exported_interfaces",
+            [buffer_len = buffer.len()],
+            { ModuleBinding::get_exported_interfaces(&self._interface, buffer) }
+        )
     }
 
     #[inline]
     unsafe fn get_exported_interface_handle(
         &self,
-        interface: NonNullConst<module::InterfaceDescriptor>,
+        interface: NonNullConst<module::InterfaceDescriptor<'_>>,
     ) -> Result<module::ModuleHandle, Error> {
-        ModuleBinding::get_exported_interface_handle(&self._interface, interface)
+        traced!(
+            "module.get_exported_interface_handle",
+            [
+                interface = %crate::trace::pointee(interface)
+            ],
+            {
+                ModuleBinding::get_exported_interface_handle(&self._interface, interface)
+            }
+        )
     }
 
     #[inline]
     unsafe fn create_module_handle(&mut self) -> module::ModuleHandle {
-        ModuleBinding::create_module_handle(&mut self._interface)
+        traced!("module.create_module_handle", {
+            ModuleBinding::create_module_handle(&mut self._interface)
+        })
     }
 
     #[inline]
     unsafe fn remove_module_handle(&mut self, handle: module::ModuleHandle) -> Result<i8, Error> {
-        ModuleBinding::remove_module_handle(&mut self._interface, handle)
+        traced!("module.remove_module_handle", [handle = %handle], {
+            ModuleBinding::remove_module_handle(&mut self._interface, handle)
+        })
     }
 
     #[inline]
@@ -544,7 +962,9 @@ impl ModuleBinding for CBaseRef<'_> {
         loader: module::LoaderHandle,
         internal: module::InternalHandle,
     ) -> Result<i8, Error> {
-        ModuleBinding::link_module(&mut self._interface, handle, loader, internal)
+        traced!("module.link_module", [handle = %handle, loader = %loader, internal = %internal], {
+            ModuleBinding::link_module(&mut self._interface, handle, loader, internal)
+        })
     }
 
     #[inline]
@@ -552,7 +972,9 @@ impl ModuleBinding for CBaseRef<'_> {
         &self,
         handle: module::ModuleHandle,
     ) -> Result<module::InternalHandle, Error> {
-        ModuleBinding::get_internal_module_handle(&self._interface, handle)
+        traced!("module.get_internal_module_handle", [handle = %handle], {
+            ModuleBinding::get_internal_module_handle(&self._interface, handle)
+        })
     }
 
     #[inline]
@@ -561,83 +983,128 @@ impl ModuleBinding for CBaseRef<'_> {
         loader: module::LoaderHandle,
         path: OSPathString,
     ) -> Result<module::ModuleHandle, Error> {
-        ModuleBinding::add_module(&mut self._interface, loader, path)
+        traced!("module.add_module", [loader = %loader, path = %crate::trace::path(&path)], {
+            ModuleBinding::add_module(&mut self._interface, loader, path)
+        })
     }
 
     #[inline]
     unsafe fn remove_module(&mut self, handle: module::ModuleHandle) -> Result<i8, Error> {
-        ModuleBinding::remove_module(&mut self._interface, handle)
+        traced!("module.remove_module", [handle = %handle], {
+            ModuleBinding::remove_module(&mut self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn load(&mut self, handle: module::ModuleHandle) -> Result<i8, Error> {
-        ModuleBinding::load(&mut self._interface, handle)
+        traced!("module.load", [handle = %handle], {
+            ModuleBinding::load(&mut self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn unload(&mut self, handle: module::ModuleHandle) -> Result<i8, Error> {
-        ModuleBinding::unload(&mut self._interface, handle)
+        traced!("module.unload", [handle = %handle], {
+            ModuleBinding::unload(&mut self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn initialize(&mut self, handle: module::ModuleHandle) -> Result<i8, Error> {
-        ModuleBinding::initialize(&mut self._interface, handle)
+        traced!("module.initialize", [handle = %handle], {
+            ModuleBinding::initialize(&mut self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn terminate(&mut self, handle: module::ModuleHandle) -> Result<i8, Error> {
-        ModuleBinding::terminate(&mut self._interface, handle)
+        traced!("module.terminate", [handle = %handle], {
+            ModuleBinding::terminate(&mut self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn add_dependency(
         &mut self,
         handle: module::ModuleHandle,
-        interface: NonNullConst<module::InterfaceDescriptor>,
+        interface: NonNullConst<module::InterfaceDescriptor<'_>>,
     ) -> Result<i8, Error> {
-        ModuleBinding::add_dependency(&mut self._interface, handle, interface)
+        traced!(
+            "module.add_dependency",
+            [
+                handle = %handle,
+                interface = %crate::trace::pointee(interface)
+            ],
+            {
+                ModuleBinding::add_dependency(&mut self._interface, handle, interface)
+            }
+        )
     }
 
     #[inline]
     unsafe fn remove_dependency(
         &mut self,
         handle: module::ModuleHandle,
-        interface: NonNullConst<module::InterfaceDescriptor>,
+        interface: NonNullConst<module::InterfaceDescriptor<'_>>,
     ) -> Result<i8, Error> {
-        ModuleBinding::remove_dependency(&mut self._interface, handle, interface)
+        traced!(
+            "module.remove_dependency",
+            [
+                handle = %handle,
+                interface = %crate::trace::pointee(interface)
+            ],
+            {
+                ModuleBinding::remove_dependency(&mut self._interface, handle, interface)
+            }
+        )
     }
 
     #[inline]
     unsafe fn export_interface(
         &mut self,
         handle: module::ModuleHandle,
-        interface: NonNullConst<module::InterfaceDescriptor>,
+        interface: NonNullConst<module::InterfaceDescriptor<'_>>,
     ) -> Result<i8, Error> {
-        ModuleBinding::export_interface(&mut self._interface, handle, interface)
+        traced!(
+            "module.export_interface",
+            [
+                handle = %handle,
+                interface = %crate::trace::pointee(interface)
+            ],
+            {
+                ModuleBinding::export_interface(&mut self._interface, handle, interface)
+            }
+        )
     }
 
     #[inline]
     unsafe fn get_load_dependencies(
         &self,
         handle: module::ModuleHandle,
-    ) -> Result<ConstSpan<module::InterfaceDescriptor>, Error> {
-        ModuleBinding::get_load_dependencies(&self._interface, handle)
+    ) -> Result<ConstSpan<module::InterfaceDescriptor<'static>>, Error> {
+        traced!("module.get_load_dependencies", [handle = %handle], {
+            ModuleBinding::get_load_dependencies(&self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn get_runtime_dependencies(
         &self,
         handle: module::ModuleHandle,
-    ) -> Result<ConstSpan<module::InterfaceDescriptor>, Error> {
-        ModuleBinding::get_runtime_dependencies(&self._interface, handle)
+    ) -> Result<ConstSpan<module::InterfaceDescriptor<'static>>, Error> {
+        traced!("module.get_runtime_dependencies", [handle = %handle], {
+            ModuleBinding::get_runtime_dependencies(&self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn get_exportable_interfaces(
         &self,
         handle: module::ModuleHandle,
-    ) -> Result<ConstSpan<module::InterfaceDescriptor>, Error> {
-        ModuleBinding::get_exportable_interfaces(&self._interface, handle)
+    ) -> Result<ConstSpan<module::InterfaceDescriptor<'static>>, Error> {
+        traced!("module.get_exportable_interfaces", [handle = %handle], {
+            ModuleBinding::get_exportable_interfaces(&self._interface, handle)
+        })
     }
 
     #[inline]
@@ -645,12 +1112,16 @@ impl ModuleBinding for CBaseRef<'_> {
         &self,
         handle: module::ModuleHandle,
     ) -> Result<module::ModuleStatus, Error> {
-        ModuleBinding::fetch_status(&self._interface, handle)
+        traced!("module.fetch_status", [handle = %handle], {
+            ModuleBinding::fetch_status(&self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn get_module_path(&self, handle: module::ModuleHandle) -> Result<OSPathString, Error> {
-        ModuleBinding::get_module_path(&self._interface, handle)
+        traced!("module.get_module_path", [handle = %handle], {
+            ModuleBinding::get_module_path(&self._interface, handle)
+        })
     }
 
     #[inline]
@@ -658,16 +1129,27 @@ impl ModuleBinding for CBaseRef<'_> {
         &self,
         handle: module::ModuleHandle,
     ) -> Result<NonNullConst<module::ModuleInfo>, Error> {
-        ModuleBinding::get_module_info(&self._interface, handle)
+        traced!("module.get_module_info", [handle = %handle], {
+            ModuleBinding::get_module_info(&self._interface, handle)
+        })
     }
 
     #[inline]
     unsafe fn get_interface(
         &self,
         handle: module::ModuleHandle,
-        interface: NonNullConst<module::InterfaceDescriptor>,
+        interface: NonNullConst<module::InterfaceDescriptor<'_>>,
     ) -> Result<module::Interface, Error> {
-        ModuleBinding::get_interface(&self._interface, handle, interface)
+        traced!(
+            "module.get_interface",
+            [
+                handle = %handle,
+                interface = %crate::trace::pointee(interface)
+            ],
+            {
+                ModuleBinding::get_interface(&self._interface, handle, interface)
+            }
+        )
     }
 }
 
@@ -693,7 +1175,7 @@ impl<'interface> CBaseAccess<'interface> for CBase<'interface> {
         unsafe {
             SysBinding::lock(&*self._interface.get());
             let result = self.assume_locked(f);
-            SysBinding::unlock(&*self._interface.get());
+            self.unlock();
             result
         }
     }
@@ -705,12 +1187,29 @@ impl<'interface> CBaseAccess<'interface> for CBase<'interface> {
                 Option::None
             } else {
                 let result = self.assume_locked(f);
-                SysBinding::unlock(&*self._interface.get());
+                self.unlock();
                 Some(result)
             }
         }
     }
 
+    #[inline]
+    fn lock_timeout<U>(
+        &self,
+        timeout: Duration,
+        f: impl FnOnce(&mut Self::Interface) -> U,
+    ) -> Option<U> {
+        unsafe {
+            if lock_interface_timeout(&*self._interface.get(), timeout) {
+                let result = self.assume_locked(f);
+                self.unlock();
+                Some(result)
+            } else {
+                Option::None
+            }
+        }
+    }
+
     #[inline]
     unsafe fn assume_locked<U>(&self, f: impl FnOnce(&mut Self::Interface) -> U) -> U {
         f(&mut *self._interface.get())
@@ -800,7 +1299,7 @@ impl VersionBinding for CBase<'_> {
     }
 
     #[inline]
-    unsafe fn from_string(&self, buffer: ConstSpan<u8>) -> Result<Version, Error> {
+    unsafe fn from_string(&self, buffer: SpanRef<'_, u8>) -> Result<Version, Error> {
         VersionBinding::from_string(&*self._interface.get(), buffer)
     }
 
@@ -823,7 +1322,7 @@ impl VersionBinding for CBase<'_> {
     unsafe fn as_string_short(
         &self,
         version: NonNullConst<Version>,
-        buffer: MutSpan<u8>,
+        buffer: SpanMut<'_, u8>,
     ) -> Result<usize, Error> {
         VersionBinding::as_string_short(&*self._interface.get(), version, buffer)
     }
@@ -832,7 +1331,7 @@ impl VersionBinding for CBase<'_> {
     unsafe fn as_string_long(
         &self,
         version: NonNullConst<Version>,
-        buffer: MutSpan<u8>,
+        buffer: SpanMut<'_, u8>,
     ) -> Result<usize, Error> {
         VersionBinding::as_string_long(&*self._interface.get(), version, buffer)
     }
@@ -841,13 +1340,13 @@ impl VersionBinding for CBase<'_> {
     unsafe fn as_string_full(
         &self,
         version: NonNullConst<Version>,
-        buffer: MutSpan<u8>,
+        buffer: SpanMut<'_, u8>,
     ) -> Result<usize, Error> {
         VersionBinding::as_string_full(&*self._interface.get(), version, buffer)
     }
 
     #[inline]
-    unsafe fn string_is_valid(&self, version_string: ConstSpan<u8>) -> Bool {
+    unsafe fn string_is_valid(&self, version_string: SpanRef<'_, u8>) -> Bool {
         VersionBinding::string_is_valid(&*self._interface.get(), version_string)
     }
 
@@ -871,3 +1370,49 @@ impl VersionBinding for CBase<'_> {
         VersionBinding::is_compatible(&*self._interface.get(), lhs, rhs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{CBase, CBaseRef};
+    use crate::ffi::intercept::{InterceptHooks, InterceptedInterface};
+    use crate::ffi::record::{Recording, ReplayInterface};
+    use crate::ffi::FnId;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct LockCount(AtomicUsize);
+
+    impl InterceptHooks for LockCount {
+        fn before(&self, id: FnId) {
+            match id {
+                FnId::SysLock => self.0.fetch_add(1, Ordering::Relaxed),
+                FnId::SysUnlock => self.0.fetch_sub(1, Ordering::Relaxed),
+                _ => 0,
+            };
+        }
+    }
+
+    #[test]
+    fn swapped_guards() {
+        let replay = ReplayInterface::new(Recording::default());
+        let (a, b) = unsafe {
+            (
+                InterceptedInterface::new(replay.interface(), LockCount::default()),
+                InterceptedInterface::new(replay.interface(), LockCount::default()),
+            )
+        };
+        let base_a = unsafe { CBase::new(CBaseRef::new(a.interface())) };
+        let base_b = unsafe { CBase::new(CBaseRef::new(b.interface())) };
+
+        let mut guard_a = base_a.lock_guard();
+        let mut guard_b = base_b.lock_guard();
+        std::mem::swap(&mut *guard_a, &mut *guard_b);
+
+        // The guards unlock the interface they locked, not the swapped copies.
+        drop(guard_a);
+        assert_eq!(a.hooks().0.load(Ordering::Relaxed), 0);
+        assert_eq!(b.hooks().0.load(Ordering::Relaxed), 1);
+        drop(guard_b);
+        assert_eq!(b.hooks().0.load(Ordering::Relaxed), 0);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::ffi::errors::error::ErrorRef;
-use crate::ffi::errors::Error as ErrorFFI;
+use crate::ffi::errors::{Error as ErrorFFI, StaticError};
 use crate::ownership::{BorrowImmutable, Owned};
 use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
@@ -94,6 +94,12 @@ impl Error<Owned> {
         }
     }
 
+    /// Constructs a new instance from a static error without allocating.
+    #[inline]
+    pub const fn from_static(error: &'static StaticError<str>) -> Self {
+        Self::new(ErrorFFI::from_static(error))
+    }
+
     /// Gets the internal error.
     #[inline]
     pub fn into_inner(mut self) -> ErrorFFI {
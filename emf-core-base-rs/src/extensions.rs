@@ -1,4 +1,134 @@
 //! Extensions to the interface.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use emf_core_base_rs::CBaseRef;
+//! # let base_interface: &CBaseRef<'static> = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+//! use emf_core_base_rs::extensions::{Extension, ExtensionInterface};
+//! use emf_core_base_rs::ffi::version::{ReleaseType, Version};
+//!
+//! #[repr(C)]
+//! struct MyExtensionInterface {
+//!     value: i32,
+//! }
+//!
+//! struct MyExtension;
+//!
+//! impl Extension for MyExtension {
+//!     const NAME: &'static str = "my_vendor::my_extension";
+//!     const VERSION: Version = Version {
+//!         major: 1,
+//!         minor: 0,
+//!         patch: 0,
+//!         build: 0,
+//!         release_number: 0,
+//!         release_type: ReleaseType::Stable,
+//!     };
+//!     type Interface = MyExtensionInterface;
+//! }
+//!
+//! let extension = ExtensionInterface::<MyExtension>::fetch(base_interface)?;
+//! println!("{}", extension.value);
+//! # Ok::<(), emf_core_base_rs::Error<emf_core_base_rs::ownership::Owned>>(())
+//! ```
+use crate::ffi::collections::NonNullConst;
+use crate::ffi::extensions::{fetch_extension, has_extension};
+use crate::ownership::Owned;
+use crate::{CBaseInterfaceInfo, Error};
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::Deref;
 
+#[cfg(feature = "shared_alloc")]
+pub mod alloc;
+#[cfg(feature = "log")]
+pub mod log;
 #[cfg(feature = "unwind_internal")]
 pub mod unwind_internal;
+
+pub use crate::ffi::extensions::Extension;
+
+/// Interface of an extension.
+pub struct ExtensionInterface<'interface, E: Extension> {
+    _interface: NonNullConst<E::Interface>,
+    _phantom: PhantomData<&'interface E::Interface>,
+}
+
+unsafe impl<E: Extension> Send for ExtensionInterface<'_, E> where E::Interface: Sync {}
+unsafe impl<E: Extension> Sync for ExtensionInterface<'_, E> where E::Interface: Sync {}
+
+impl<'interface, E: Extension> ExtensionInterface<'interface, E> {
+    /// Checks whether the extension is available.
+    #[inline]
+    pub fn is_available(interface: &impl CBaseInterfaceInfo) -> bool {
+        unsafe { has_extension::<E>(interface.internal_interface()) }
+    }
+
+    /// Fetches the interface of the extension.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the extension is not available.
+    ///
+    /// # Return
+    ///
+    /// Interface on success, error otherwise.
+    #[inline]
+    pub fn fetch(interface: &impl CBaseInterfaceInfo) -> Result<Self, Error<Owned>> {
+        unsafe {
+            fetch_extension::<E>(interface.internal_interface())
+                .into_rust()
+                .map(|extension| Self::new(extension))
+                .map_err(Error::from)
+        }
+    }
+
+    /// Construct a new instance from a pointer.
+    ///
+    /// # Safety
+    ///
+    /// This function allows the creation of invalid interfaces
+    /// by bypassing lifetimes.
+    #[inline]
+    pub const unsafe fn new(interface: NonNullConst<E::Interface>) -> Self {
+        Self {
+            _interface: interface,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Retrieves a pointer to the native interface.
+    #[inline]
+    pub fn to_interface(&self) -> NonNullConst<E::Interface> {
+        self._interface
+    }
+}
+
+impl<E: Extension> Deref for ExtensionInterface<'_, E> {
+    type Target = E::Interface;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self._interface.as_ref() }
+    }
+}
+
+impl<E: Extension> Copy for ExtensionInterface<'_, E> {}
+
+impl<E: Extension> Clone for ExtensionInterface<'_, E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: Extension> Debug for ExtensionInterface<'_, E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtensionInterface")
+            .field("name", &E::NAME)
+            .field("version", &E::VERSION)
+            .field("_interface", &self._interface)
+            .finish()
+    }
+}
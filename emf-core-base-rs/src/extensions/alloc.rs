@@ -0,0 +1,610 @@
+//! The `alloc` extension.
+//!
+//! The host exports an allocator with an [OwnedAllocator], which wraps any [GlobalAlloc].
+//! Modules fetch it with [Allocator::fetch] and may route their whole heap to it with the
+//! [SharedAlloc] adapter, so that allocations can be released by any module.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use emf_core_base_rs::CBaseRef;
+//! # let base_interface: &'static CBaseRef<'static> = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+//! use emf_core_base_rs::extensions::alloc::{Allocator, SharedAlloc};
+//! use std::alloc::System;
+//!
+//! #[global_allocator]
+//! static GLOBAL: SharedAlloc = SharedAlloc::new(System);
+//!
+//! let allocator = Allocator::fetch(base_interface)?;
+//! GLOBAL.install(allocator)?;
+//! # Ok::<(), emf_core_base_rs::Error<emf_core_base_rs::ownership::Owned>>(())
+//! ```
+use crate::extensions::ExtensionInterface;
+use crate::ffi::collections::NonNullConst;
+use crate::ffi::errors::StaticError;
+use crate::ffi::extensions::alloc::{
+    AllocBinding, AllocInterface, AllocVTable, Allocator as AllocatorFFI,
+};
+use crate::ffi::TypeWrapper;
+use crate::guard::ffi_guard;
+use crate::ownership::Owned;
+use crate::{CBaseInterfaceInfo, Error};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+pub use crate::ffi::extensions::alloc::ALLOC_VERSION_RELEASE_TYPE;
+pub use crate::ffi::extensions::alloc::DEFAULT_ALIGNMENT;
+pub use crate::ffi::extensions::alloc::{ALLOC_INTERFACE_NAME, ALLOC_VERSION_STRING};
+pub use crate::ffi::extensions::alloc::{ALLOC_VERSION_BUILD, ALLOC_VERSION_RELEASE_NUMBER};
+pub use crate::ffi::extensions::alloc::{
+    ALLOC_VERSION_MAJOR, ALLOC_VERSION_MINOR, ALLOC_VERSION_PATCH,
+};
+
+/// A borrowed allocator.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Allocator<'a> {
+    allocator: AllocInterface,
+    phantom: PhantomData<&'a ()>,
+}
+
+impl<'a> Allocator<'a> {
+    /// Fetches the allocator exported to the interface.
+    ///
+    /// # Failure
+    ///
+    /// Fails if no module exports the `alloc` extension.
+    ///
+    /// # Return
+    ///
+    /// Allocator on success, error otherwise.
+    #[inline]
+    pub fn fetch(interface: &'a impl CBaseInterfaceInfo) -> Result<Self, Error<Owned>> {
+        ExtensionInterface::<'a, AllocInterface>::fetch(interface)
+            .map(|extension| unsafe { Self::from_raw(*extension) })
+    }
+
+    /// Construct a new instance from a raw allocator.
+    ///
+    /// # Safety
+    ///
+    /// This function allows the creation of invalid allocators
+    /// by bypassing lifetimes.
+    #[inline]
+    pub const unsafe fn from_raw(allocator: AllocInterface) -> Self {
+        Self {
+            allocator,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Fetches the raw allocator.
+    #[inline]
+    pub const fn to_raw(&self) -> AllocInterface {
+        self.allocator
+    }
+
+    /// Allocates memory as described by `layout`.
+    ///
+    /// # Return
+    ///
+    /// Pointer to the memory, [Option::None] on failure.
+    ///
+    /// # Safety
+    ///
+    /// `layout` must have a non-zero size.
+    #[inline]
+    pub unsafe fn alloc(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let ptr = if layout.align() <= DEFAULT_ALIGNMENT {
+            self.allocator.alloc(layout.size())
+        } else {
+            self.allocator.alloc_aligned(layout.size(), layout.align())
+        };
+        ptr.map(NonNull::cast)
+    }
+
+    /// Resizes the memory pointed to by `ptr` to `new_size` bytes.
+    ///
+    /// # Return
+    ///
+    /// Pointer to the resized memory, [Option::None] on failure,
+    /// in which case `ptr` remains valid.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by this allocator with `layout`
+    /// and `new_size` must be non-zero.
+    #[inline]
+    pub unsafe fn realloc(
+        &self,
+        ptr: NonNull<u8>,
+        layout: Layout,
+        new_size: usize,
+    ) -> Option<NonNull<u8>> {
+        let ptr = Some(ptr.cast());
+        let ptr = if layout.align() <= DEFAULT_ALIGNMENT {
+            self.allocator.realloc(ptr, new_size)
+        } else {
+            self.allocator
+                .realloc_aligned(ptr, new_size, layout.align())
+        };
+        ptr.map(NonNull::cast)
+    }
+
+    /// Releases the memory pointed to by `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must have been allocated by this allocator with `layout`.
+    #[inline]
+    pub unsafe fn dealloc(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.align() <= DEFAULT_ALIGNMENT {
+            self.allocator.dealloc(Some(ptr.cast()))
+        } else {
+            self.allocator
+                .dealloc_aligned(Some(ptr.cast()), layout.align())
+        }
+    }
+}
+
+/// An allocator backed by a [GlobalAlloc].
+///
+/// The allocator is pinned on the heap, so that the interface returned by
+/// [OwnedAllocator::to_raw] stays valid for the lifetime of the allocator.
+/// The size of every allocation is stored in front of it, as the functions of the
+/// extension don't pass it when releasing the memory.
+#[derive(Debug)]
+pub struct OwnedAllocator<A: GlobalAlloc + Send + Sync> {
+    allocator: Pin<Box<A>>,
+}
+
+impl<A: GlobalAlloc + Send + Sync> OwnedAllocator<A> {
+    const VTABLE: AllocVTable = AllocVTable {
+        alloc_fn: TypeWrapper(Self::alloc_fn),
+        realloc_fn: TypeWrapper(Self::realloc_fn),
+        dealloc_fn: TypeWrapper(Self::dealloc_fn),
+        alloc_aligned_fn: TypeWrapper(Self::alloc_aligned_fn),
+        realloc_aligned_fn: TypeWrapper(Self::realloc_aligned_fn),
+        dealloc_aligned_fn: TypeWrapper(Self::dealloc_aligned_fn),
+    };
+
+    /// Constructs a new allocator.
+    #[inline]
+    pub fn new(allocator: A) -> Self {
+        Self {
+            allocator: Box::pin(allocator),
+        }
+    }
+
+    /// Fetches a reference to the wrapped allocator.
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        &self.allocator
+    }
+
+    /// Borrows the allocator.
+    #[inline]
+    pub fn as_allocator(&self) -> Allocator<'_> {
+        unsafe { Allocator::from_raw(self.to_raw()) }
+    }
+
+    /// Fetches the raw allocator, which can be exported as the `alloc` extension.
+    #[inline]
+    pub fn to_raw(&self) -> AllocInterface {
+        AllocInterface {
+            allocator: Some(NonNull::from(&*self.allocator).cast()),
+            vtable: NonNullConst::from(&Self::VTABLE),
+        }
+    }
+
+    /// Leaks the allocator, returning a borrowed allocator valid for the rest of the program.
+    #[inline]
+    pub fn leak(self) -> Allocator<'static> {
+        let allocator = unsafe { Allocator::from_raw(self.to_raw()) };
+        std::mem::forget(self);
+        allocator
+    }
+
+    unsafe extern "C-unwind" fn alloc_fn(
+        allocator: Option<NonNull<AllocatorFFI>>,
+        size: usize,
+    ) -> Option<NonNull<c_void>> {
+        Self::alloc_aligned_fn(allocator, size, DEFAULT_ALIGNMENT)
+    }
+
+    unsafe extern "C-unwind" fn realloc_fn(
+        allocator: Option<NonNull<AllocatorFFI>>,
+        ptr: Option<NonNull<c_void>>,
+        size: usize,
+    ) -> Option<NonNull<c_void>> {
+        Self::realloc_aligned_fn(allocator, ptr, size, DEFAULT_ALIGNMENT)
+    }
+
+    unsafe extern "C-unwind" fn dealloc_fn(
+        allocator: Option<NonNull<AllocatorFFI>>,
+        ptr: Option<NonNull<c_void>>,
+    ) {
+        Self::dealloc_aligned_fn(allocator, ptr, DEFAULT_ALIGNMENT)
+    }
+
+    unsafe extern "C-unwind" fn alloc_aligned_fn(
+        allocator: Option<NonNull<AllocatorFFI>>,
+        size: usize,
+        alignment: usize,
+    ) -> Option<NonNull<c_void>> {
+        ffi_guard(|| {
+            let allocator = allocator.unwrap().cast::<A>().as_ref();
+            let (layout, offset) = sized_layout(size, alignment)?;
+            let base = NonNull::new(allocator.alloc(layout))?;
+            Some(write_header(base, offset, size))
+        })
+    }
+
+    unsafe extern "C-unwind" fn realloc_aligned_fn(
+        allocator: Option<NonNull<AllocatorFFI>>,
+        ptr: Option<NonNull<c_void>>,
+        size: usize,
+        alignment: usize,
+    ) -> Option<NonNull<c_void>> {
+        let ptr = match ptr {
+            Some(ptr) => ptr,
+            None => return Self::alloc_aligned_fn(allocator, size, alignment),
+        };
+
+        ffi_guard(|| {
+            let allocator = allocator.unwrap().cast::<A>().as_ref();
+            let (base, layout, offset) = read_header(ptr, alignment);
+            let (new_layout, _) = sized_layout(size, alignment)?;
+            let base = NonNull::new(allocator.realloc(base.as_ptr(), layout, new_layout.size()))?;
+            Some(write_header(base, offset, size))
+        })
+    }
+
+    unsafe extern "C-unwind" fn dealloc_aligned_fn(
+        allocator: Option<NonNull<AllocatorFFI>>,
+        ptr: Option<NonNull<c_void>>,
+        alignment: usize,
+    ) {
+        if let Some(ptr) = ptr {
+            ffi_guard(|| {
+                let allocator = allocator.unwrap().cast::<A>().as_ref();
+                let (base, layout, _) = read_header(ptr, alignment);
+                allocator.dealloc(base.as_ptr(), layout)
+            })
+        }
+    }
+}
+
+impl<A: GlobalAlloc + Send + Sync + Default> Default for OwnedAllocator<A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(A::default())
+    }
+}
+
+/// Size of the header storing the size of an allocation.
+const HEADER_SIZE: usize = std::mem::size_of::<usize>();
+
+/// Computes the layout of an allocation of `size` bytes including its header.
+///
+/// # Return
+///
+/// Layout and offset of the user memory.
+fn sized_layout(size: usize, alignment: usize) -> Option<(Layout, usize)> {
+    let alignment = alignment.max(std::mem::align_of::<usize>());
+    let offset = alignment.max(HEADER_SIZE);
+    let layout = Layout::from_size_align(size.checked_add(offset)?, alignment).ok()?;
+    Some((layout, offset))
+}
+
+/// Stores `value` in the header of an allocation.
+///
+/// # Return
+///
+/// Pointer to the user memory.
+unsafe fn write_header(base: NonNull<u8>, offset: usize, value: usize) -> NonNull<c_void> {
+    let ptr = base.as_ptr().add(offset);
+    ptr.sub(HEADER_SIZE).cast::<usize>().write(value);
+    NonNull::new_unchecked(ptr).cast()
+}
+
+/// Reads the size stored in front of an allocation of an [OwnedAllocator].
+///
+/// # Return
+///
+/// Base pointer, layout and offset of the user memory.
+unsafe fn read_header(ptr: NonNull<c_void>, alignment: usize) -> (NonNull<u8>, Layout, usize) {
+    let ptr = ptr.cast::<u8>().as_ptr();
+    let size = ptr.sub(HEADER_SIZE).cast::<usize>().read();
+    let (layout, offset) = sized_layout(size, alignment).unwrap();
+    (NonNull::new_unchecked(ptr.sub(offset)), layout, offset)
+}
+
+/// A [GlobalAlloc] routing the allocations to the `alloc` extension.
+///
+/// Until an allocator is installed with [SharedAlloc::install], the allocations are served
+/// by the fallback allocator. Every allocation remembers its origin:
+///
+/// - Memory of the shared allocator can be released by any [SharedAlloc],
+///   which has installed the allocator.
+/// - Memory of the fallback allocator can only be released by the [SharedAlloc]
+///   it was obtained from.
+///
+/// Releasing memory of any other origin aborts the process, as it can not be released
+/// by the correct allocator.
+#[derive(Debug)]
+pub struct SharedAlloc<F: GlobalAlloc = System> {
+    fallback: F,
+    allocator: AtomicPtr<AllocInterface>,
+}
+
+/// Origin of an allocation made by the shared allocator.
+///
+/// Allocations made by the fallback allocator store the address of their [SharedAlloc].
+const ORIGIN_SHARED: usize = 1;
+
+impl<F: GlobalAlloc> SharedAlloc<F> {
+    /// Constructs a new adapter, which uses `fallback` until an allocator is installed.
+    #[inline]
+    pub const fn new(fallback: F) -> Self {
+        Self {
+            fallback,
+            allocator: AtomicPtr::new(std::ptr::null_mut()),
+        }
+    }
+
+    /// Routes all future allocations to `allocator`.
+    ///
+    /// # Failure
+    ///
+    /// Fails if an allocator has already been installed.
+    ///
+    /// # Return
+    ///
+    /// Error on failure.
+    pub fn install(&self, allocator: Allocator<'static>) -> Result<(), Error<Owned>> {
+        static ALREADY_INSTALLED: StaticError<str> =
+            StaticError::new("An allocator has already been installed.");
+
+        let allocator = Box::into_raw(Box::new(allocator.to_raw()));
+        match self.allocator.compare_exchange(
+            std::ptr::null_mut(),
+            allocator,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => Ok(()),
+            Err(_) => {
+                drop(unsafe { Box::from_raw(allocator) });
+                Err(Error::from_static(&ALREADY_INSTALLED))
+            }
+        }
+    }
+
+    /// Fetches the installed allocator.
+    #[inline]
+    pub fn allocator(&self) -> Option<Allocator<'static>> {
+        let allocator = self.allocator.load(Ordering::Acquire);
+        unsafe { allocator.as_ref().map(|a| Allocator::from_raw(*a)) }
+    }
+
+    /// Computes the layout of an allocation including its origin.
+    #[inline]
+    fn tagged_layout(layout: Layout) -> Option<(Layout, usize)> {
+        sized_layout(layout.size(), layout.align())
+    }
+
+    /// Origin of the allocations made by the fallback allocator.
+    #[inline]
+    fn fallback_origin(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    /// Fetches the installed allocator, aborting if no allocator has been installed.
+    #[inline]
+    fn shared_allocator(&self) -> Allocator<'static> {
+        self.allocator().unwrap_or_else(|| foreign_allocation())
+    }
+}
+
+/// Aborts the process after an attempt to release memory of a foreign origin.
+#[cold]
+#[inline(never)]
+fn foreign_allocation() -> ! {
+    use std::io::Write;
+
+    // The message is written directly, as the allocator can not be used.
+    let _ = std::io::stderr().write_all(
+        b"SharedAlloc: attempted to release memory allocated by another allocator, aborting\n",
+    );
+    std::process::abort()
+}
+
+unsafe impl<F: GlobalAlloc> GlobalAlloc for SharedAlloc<F> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let (layout, offset) = match Self::tagged_layout(layout) {
+            Some(tagged) => tagged,
+            None => return std::ptr::null_mut(),
+        };
+
+        let (base, origin) = match self.allocator() {
+            Some(allocator) => (allocator.alloc(layout), ORIGIN_SHARED),
+            None => (
+                NonNull::new(self.fallback.alloc(layout)),
+                self.fallback_origin(),
+            ),
+        };
+        match base {
+            Some(base) => write_header(base, offset, origin).cast().as_ptr(),
+            None => std::ptr::null_mut(),
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        let (layout, offset) = Self::tagged_layout(layout).unwrap();
+        let origin = ptr.sub(HEADER_SIZE).cast::<usize>().read();
+        let base = ptr.sub(offset);
+
+        match origin {
+            ORIGIN_SHARED => self
+                .shared_allocator()
+                .dealloc(NonNull::new_unchecked(base), layout),
+            origin if origin == self.fallback_origin() => self.fallback.dealloc(base, layout),
+            _ => foreign_allocation(),
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let (old_layout, offset) = Self::tagged_layout(layout).unwrap();
+        let new_layout = match Layout::from_size_align(new_size, layout.align())
+            .ok()
+            .and_then(Self::tagged_layout)
+        {
+            Some((new_layout, _)) => new_layout,
+            None => return std::ptr::null_mut(),
+        };
+        let origin = ptr.sub(HEADER_SIZE).cast::<usize>().read();
+        let base = ptr.sub(offset);
+
+        let base = match origin {
+            ORIGIN_SHARED => self.shared_allocator().realloc(
+                NonNull::new_unchecked(base),
+                old_layout,
+                new_layout.size(),
+            ),
+            origin if origin == self.fallback_origin() => {
+                NonNull::new(self.fallback.realloc(base, old_layout, new_layout.size()))
+            }
+            _ => foreign_allocation(),
+        };
+        match base {
+            Some(base) => base.as_ptr().add(offset),
+            None => std::ptr::null_mut(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Allocator, OwnedAllocator, SharedAlloc};
+    use crate::ffi::collections::{FfiBox, FfiString, FfiVec};
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct CountingAlloc {
+        live: AtomicUsize,
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            self.live.fetch_add(1, Ordering::SeqCst);
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            self.live.fetch_sub(1, Ordering::SeqCst);
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[test]
+    fn owned_allocator() {
+        let owned = OwnedAllocator::new(CountingAlloc::default());
+        let allocator = owned.as_allocator();
+
+        for &align in &[1, 16, 64, 4096] {
+            let layout = Layout::from_size_align(24, align).unwrap();
+            unsafe {
+                let ptr = allocator.alloc(layout).unwrap();
+                assert_eq!(ptr.as_ptr() as usize % align, 0);
+                ptr.as_ptr().write_bytes(0xAB, 24);
+
+                let ptr = allocator.realloc(ptr, layout, 1000).unwrap();
+                assert_eq!(ptr.as_ptr() as usize % align, 0);
+                assert!(std::slice::from_raw_parts(ptr.as_ptr(), 24)
+                    .iter()
+                    .all(|&b| b == 0xAB));
+
+                assert_eq!(owned.allocator().live.load(Ordering::SeqCst), 1);
+                allocator.dealloc(ptr, Layout::from_size_align(1000, align).unwrap());
+            }
+        }
+        assert_eq!(owned.allocator().live.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn ffi_collections() {
+        let owned = OwnedAllocator::new(CountingAlloc::default());
+        let live = || owned.allocator().live.load(Ordering::SeqCst);
+        let value = Rc::new(0);
+
+        unsafe {
+            let vec =
+                FfiVec::from_vec_in(vec![value.clone(), value.clone()], owned.to_raw()).unwrap();
+            assert_eq!(vec.len(), 2);
+            assert_eq!(live(), 1);
+            drop(vec);
+            assert_eq!(Rc::strong_count(&value), 1);
+
+            let vec = FfiVec::from_vec_in(vec![value.clone()], owned.to_raw()).unwrap();
+            let vec = Vec::from(vec);
+            assert_eq!(Rc::strong_count(&value), 2);
+            drop(vec);
+
+            #[repr(align(64))]
+            #[derive(Debug, PartialEq)]
+            struct Aligned(Rc<i32>);
+
+            let boxed = FfiBox::new_in(Aligned(value.clone()), owned.to_raw()).unwrap();
+            assert_eq!(&*boxed as *const Aligned as usize % 64, 0);
+            assert_eq!(boxed.into_inner(), Aligned(value.clone()));
+            drop(FfiBox::new_in(value.clone(), owned.to_raw()).unwrap());
+            assert_eq!(Rc::strong_count(&value), 1);
+
+            let string = FfiString::from_string_in(String::from("shared"), owned.to_raw()).unwrap();
+            assert_eq!(live(), 1);
+            assert_eq!(string.clone().into_string(), "shared");
+            drop(string);
+        }
+        assert_eq!(live(), 0);
+    }
+
+    #[test]
+    fn shared_alloc() {
+        let owned = OwnedAllocator::new(CountingAlloc::default());
+        let shared = SharedAlloc::new(System);
+        let layout = Layout::from_size_align(8, 32).unwrap();
+
+        unsafe {
+            let fallback = shared.alloc(layout);
+            assert!(!fallback.is_null());
+            assert_eq!(owned.allocator().live.load(Ordering::SeqCst), 0);
+
+            let allocator = owned.as_allocator();
+            shared
+                .install(Allocator::from_raw(allocator.to_raw()))
+                .unwrap();
+            assert!(shared
+                .install(Allocator::from_raw(allocator.to_raw()))
+                .is_err());
+
+            let routed = shared.alloc(layout);
+            assert_eq!(routed as usize % 32, 0);
+            assert_eq!(owned.allocator().live.load(Ordering::SeqCst), 1);
+
+            let routed = shared.realloc(routed, layout, 512);
+            let fallback = shared.realloc(fallback, layout, 512);
+            assert_eq!(owned.allocator().live.load(Ordering::SeqCst), 1);
+
+            let layout = Layout::from_size_align(512, 32).unwrap();
+            shared.dealloc(fallback, layout);
+            shared.dealloc(routed, layout);
+        }
+        assert_eq!(owned.allocator().live.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,305 @@
+//! The `log` extension.
+//!
+//! The host exports a logger with an [OwnedLogger], which wraps any [LogSink], for instance
+//! the [StderrSink]. Modules fetch the logger with [Logger::fetch] and can route the
+//! messages of the `log` crate to it with [bridge::install].
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use emf_core_base_rs::CBaseRef;
+//! # let base_interface: &CBaseRef<'static> = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+//! use emf_core_base_rs::extensions::log::{bridge, Level, Logger};
+//!
+//! let logger = Logger::fetch(base_interface)?;
+//! logger.log(Level::Info, "my_module", "Hello!", &[("answer", "42")]);
+//!
+//! bridge::install(logger).unwrap();
+//! # Ok::<(), emf_core_base_rs::Error<emf_core_base_rs::ownership::Owned>>(())
+//! ```
+use crate::extensions::ExtensionInterface;
+use crate::ffi::collections::{ConstSpan, NonNullConst};
+use crate::ffi::extensions::log::{
+    KeyValue, LogBinding, LogInterface, LogVTable, Logger as LoggerFFI, Record as RecordFFI,
+};
+use crate::ffi::TypeWrapper;
+use crate::guard::ffi_guard;
+use crate::ownership::Owned;
+use crate::{CBaseInterfaceInfo, Error};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::ptr::NonNull;
+
+pub mod bridge;
+mod stderr;
+
+pub use crate::ffi::extensions::log::LOG_VERSION_RELEASE_TYPE;
+pub use crate::ffi::extensions::log::{Level, LevelFilter};
+pub use crate::ffi::extensions::log::{LOG_INTERFACE_NAME, LOG_VERSION_STRING};
+pub use crate::ffi::extensions::log::{LOG_VERSION_BUILD, LOG_VERSION_RELEASE_NUMBER};
+pub use crate::ffi::extensions::log::{LOG_VERSION_MAJOR, LOG_VERSION_MINOR, LOG_VERSION_PATCH};
+pub use stderr::StderrSink;
+
+/// A message received by a [LogSink].
+#[derive(Debug, Copy, Clone)]
+pub struct Record<'a> {
+    record: &'a RecordFFI,
+}
+
+impl<'a> Record<'a> {
+    /// Fetches the verbosity level.
+    #[inline]
+    pub fn level(&self) -> Level {
+        self.record.level
+    }
+
+    /// Fetches the target.
+    ///
+    /// Invalid utf-8 sequences are replaced.
+    #[inline]
+    pub fn target(&self) -> Cow<'a, str> {
+        to_str(&self.record.target)
+    }
+
+    /// Fetches the message.
+    ///
+    /// Invalid utf-8 sequences are replaced.
+    #[inline]
+    pub fn message(&self) -> Cow<'a, str> {
+        to_str(&self.record.message)
+    }
+
+    /// Fetches the key/value pairs attached to the message.
+    ///
+    /// Invalid utf-8 sequences are replaced.
+    #[inline]
+    pub fn key_values(&self) -> impl Iterator<Item = (Cow<'a, str>, Cow<'a, str>)> {
+        let key_values: &'a [KeyValue] = self.record.key_values.as_ref();
+        key_values
+            .iter()
+            .map(|kv| (to_str(&kv.key), to_str(&kv.value)))
+    }
+}
+
+fn to_str(span: &ConstSpan<u8>) -> Cow<'_, str> {
+    String::from_utf8_lossy(span.as_ref())
+}
+
+/// A destination of the messages.
+pub trait LogSink: Send + Sync {
+    /// Handles a message.
+    fn log(&self, record: &Record<'_>);
+
+    /// Fetches the maximum verbosity level accepted by the sink.
+    #[inline]
+    fn max_level(&self) -> LevelFilter {
+        LevelFilter::Trace
+    }
+
+    /// Flushes the buffered messages.
+    #[inline]
+    fn flush(&self) {}
+}
+
+/// A borrowed logger.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Logger<'a> {
+    logger: LogInterface,
+    phantom: PhantomData<&'a ()>,
+}
+
+impl<'a> Logger<'a> {
+    /// Fetches the logger exported to the interface.
+    ///
+    /// # Failure
+    ///
+    /// Fails if no module exports the `log` extension.
+    ///
+    /// # Return
+    ///
+    /// Logger on success, error otherwise.
+    #[inline]
+    pub fn fetch(interface: &'a impl CBaseInterfaceInfo) -> Result<Self, Error<Owned>> {
+        ExtensionInterface::<'a, LogInterface>::fetch(interface)
+            .map(|extension| unsafe { Self::from_raw(*extension) })
+    }
+
+    /// Construct a new instance from a raw logger.
+    ///
+    /// # Safety
+    ///
+    /// This function allows the creation of invalid loggers
+    /// by bypassing lifetimes.
+    #[inline]
+    pub const unsafe fn from_raw(logger: LogInterface) -> Self {
+        Self {
+            logger,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Fetches the raw logger.
+    #[inline]
+    pub const fn to_raw(&self) -> LogInterface {
+        self.logger
+    }
+
+    /// Sends a message to the logger.
+    #[inline]
+    pub fn log(&self, level: Level, target: &str, message: &str, key_values: &[(&str, &str)]) {
+        let key_values: Vec<_> = key_values
+            .iter()
+            .map(|&(key, value)| KeyValue {
+                key: ConstSpan::from(key),
+                value: ConstSpan::from(value),
+            })
+            .collect();
+        let record = RecordFFI {
+            level,
+            target: ConstSpan::from(target),
+            message: ConstSpan::from(message),
+            key_values: ConstSpan::from(&key_values),
+        };
+        unsafe { self.logger.log(&record) }
+    }
+
+    /// Fetches the maximum verbosity level accepted by the logger.
+    #[inline]
+    pub fn max_level(&self) -> LevelFilter {
+        unsafe { self.logger.max_level() }
+    }
+
+    /// Checks whether messages of the verbosity `level` are accepted by the logger.
+    #[inline]
+    pub fn enabled(&self, level: Level) -> bool {
+        self.max_level().allows(level)
+    }
+
+    /// Flushes the buffered messages.
+    #[inline]
+    pub fn flush(&self) {
+        unsafe { self.logger.flush() }
+    }
+}
+
+/// A logger owning its [LogSink].
+///
+/// The sink is pinned on the heap, so that the interface returned by
+/// [OwnedLogger::to_raw] stays valid for the lifetime of the logger.
+#[derive(Debug)]
+pub struct OwnedLogger<S: LogSink> {
+    sink: Pin<Box<S>>,
+}
+
+impl<S: LogSink> OwnedLogger<S> {
+    const VTABLE: LogVTable = LogVTable {
+        log_fn: TypeWrapper(Self::log_fn),
+        max_level_fn: TypeWrapper(Self::max_level_fn),
+        flush_fn: TypeWrapper(Self::flush_fn),
+    };
+
+    /// Constructs a new logger from a sink.
+    #[inline]
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink: Box::pin(sink),
+        }
+    }
+
+    /// Fetches a reference to the sink.
+    #[inline]
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Borrows the logger.
+    #[inline]
+    pub fn logger(&self) -> Logger<'_> {
+        unsafe { Logger::from_raw(self.to_raw()) }
+    }
+
+    /// Fetches the raw logger, which can be exported as the `log` extension.
+    #[inline]
+    pub fn to_raw(&self) -> LogInterface {
+        LogInterface {
+            logger: Some(NonNull::from(&*self.sink).cast()),
+            vtable: NonNullConst::from(&Self::VTABLE),
+        }
+    }
+
+    /// Leaks the logger, returning a borrowed logger valid for the rest of the program.
+    #[inline]
+    pub fn leak(self) -> Logger<'static> {
+        let logger = unsafe { Logger::from_raw(self.to_raw()) };
+        std::mem::forget(self);
+        logger
+    }
+
+    unsafe extern "C-unwind" fn log_fn(
+        logger: Option<NonNull<LoggerFFI>>,
+        record: NonNullConst<RecordFFI>,
+    ) {
+        ffi_guard(|| {
+            let record = Record {
+                record: record.as_ref(),
+            };
+            logger.unwrap().cast::<S>().as_ref().log(&record)
+        })
+    }
+
+    unsafe extern "C-unwind" fn max_level_fn(logger: Option<NonNull<LoggerFFI>>) -> LevelFilter {
+        ffi_guard(|| logger.unwrap().cast::<S>().as_ref().max_level())
+    }
+
+    unsafe extern "C-unwind" fn flush_fn(logger: Option<NonNull<LoggerFFI>>) {
+        ffi_guard(|| logger.unwrap().cast::<S>().as_ref().flush())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Level, LevelFilter, LogSink, OwnedLogger, Record};
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    pub(super) struct CaptureSink {
+        pub(super) lines: Mutex<Vec<String>>,
+    }
+
+    impl LogSink for CaptureSink {
+        fn log(&self, record: &Record<'_>) {
+            let mut line = format!(
+                "{} {}: {}",
+                record.level(),
+                record.target(),
+                record.message()
+            );
+            for (key, value) in record.key_values() {
+                line.push_str(&format!(" {}={}", key, value));
+            }
+            self.lines.lock().unwrap().push(line);
+        }
+
+        fn max_level(&self) -> LevelFilter {
+            LevelFilter::Info
+        }
+    }
+
+    #[test]
+    fn owned_logger() {
+        let logger = OwnedLogger::new(CaptureSink::default());
+        let borrowed = logger.logger();
+
+        borrowed.log(Level::Warn, "test", "Hello", &[("a", "1"), ("b", "2")]);
+        borrowed.log(Level::Info, "test", "World", &[]);
+        borrowed.flush();
+
+        assert!(borrowed.enabled(Level::Info));
+        assert!(!borrowed.enabled(Level::Debug));
+        assert_eq!(
+            *logger.sink().lines.lock().unwrap(),
+            vec!["WARN test: Hello a=1 b=2", "INFO test: World"]
+        );
+    }
+}
@@ -0,0 +1,143 @@
+//! Bridge from the `log` crate to the `log` extension.
+//!
+//! Once installed, the messages emitted with the macros of the `log` crate, like
+//! `log::info!`, are sent to the logger of the host.
+use crate::extensions::log::{Level, LevelFilter, Logger};
+use log_crate::kv::{Error as KvError, Key, Value, VisitSource};
+use log_crate::{Log, Metadata, SetLoggerError};
+use std::borrow::Cow;
+
+/// Adapter implementing [Log] for a [Logger].
+#[derive(Debug, Copy, Clone)]
+pub struct LogBridge<'a> {
+    logger: Logger<'a>,
+}
+
+impl<'a> LogBridge<'a> {
+    /// Constructs a bridge forwarding to `logger`.
+    #[inline]
+    pub const fn new(logger: Logger<'a>) -> Self {
+        Self { logger }
+    }
+
+    /// Fetches the logger.
+    #[inline]
+    pub const fn logger(&self) -> Logger<'a> {
+        self.logger
+    }
+}
+
+impl Log for LogBridge<'_> {
+    #[inline]
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.logger.enabled(from_log_level(metadata.level()))
+    }
+
+    fn log(&self, record: &log_crate::Record<'_>) {
+        let level = from_log_level(record.level());
+        if !self.logger.enabled(level) {
+            return;
+        }
+
+        let message = match record.args().as_str() {
+            Some(message) => Cow::Borrowed(message),
+            None => Cow::Owned(record.args().to_string()),
+        };
+
+        let mut collector = Collector(Vec::new());
+        let _ = record.key_values().visit(&mut collector);
+        let key_values: Vec<_> = collector
+            .0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+
+        self.logger
+            .log(level, record.target(), &message, &key_values)
+    }
+
+    #[inline]
+    fn flush(&self) {
+        self.logger.flush()
+    }
+}
+
+/// Collects the key/value pairs of a record.
+struct Collector(Vec<(String, String)>);
+
+impl<'kvs> VisitSource<'kvs> for Collector {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), KvError> {
+        self.0.push((key.as_str().to_string(), value.to_string()));
+        Ok(())
+    }
+}
+
+/// Routes the messages of the `log` crate to `logger`.
+///
+/// The maximum level of the `log` crate is set to the one of the logger.
+///
+/// # Failure
+///
+/// Fails if a logger has already been installed for the `log` crate.
+pub fn install(logger: Logger<'static>) -> Result<(), SetLoggerError> {
+    log_crate::set_boxed_logger(Box::new(LogBridge::new(logger)))?;
+    log_crate::set_max_level(to_log_level_filter(logger.max_level()));
+    Ok(())
+}
+
+fn from_log_level(level: log_crate::Level) -> Level {
+    match level {
+        log_crate::Level::Error => Level::Error,
+        log_crate::Level::Warn => Level::Warn,
+        log_crate::Level::Info => Level::Info,
+        log_crate::Level::Debug => Level::Debug,
+        log_crate::Level::Trace => Level::Trace,
+    }
+}
+
+fn to_log_level_filter(level: LevelFilter) -> log_crate::LevelFilter {
+    match level {
+        LevelFilter::Off => log_crate::LevelFilter::Off,
+        LevelFilter::Error => log_crate::LevelFilter::Error,
+        LevelFilter::Warn => log_crate::LevelFilter::Warn,
+        LevelFilter::Info => log_crate::LevelFilter::Info,
+        LevelFilter::Debug => log_crate::LevelFilter::Debug,
+        LevelFilter::Trace => log_crate::LevelFilter::Trace,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogBridge;
+    use crate::extensions::log::tests::CaptureSink;
+    use crate::extensions::log::OwnedLogger;
+    use log_crate::{Level, Log, Record};
+
+    #[test]
+    fn forward_records() {
+        let logger = OwnedLogger::new(CaptureSink::default());
+        let bridge = LogBridge::new(logger.logger());
+
+        bridge.log(
+            &Record::builder()
+                .level(Level::Info)
+                .target("bridge")
+                .args(format_args!("{} + {}", 1, 2))
+                .key_values(&[("sum", 3)])
+                .build(),
+        );
+        bridge.log(
+            &Record::builder()
+                .level(Level::Debug)
+                .target("bridge")
+                .args(format_args!("filtered"))
+                .build(),
+        );
+
+        assert!(bridge.enabled(&log_crate::Metadata::builder().level(Level::Warn).build()));
+        assert_eq!(
+            *logger.sink().lines.lock().unwrap(),
+            vec!["INFO bridge: 1 + 2 sum=3"]
+        );
+    }
+}
@@ -0,0 +1,59 @@
+//! Default sink writing to the standard error stream.
+use crate::extensions::log::{LevelFilter, LogSink, Record};
+use std::io::Write;
+
+/// A sink writing the messages to the standard error stream.
+///
+/// Every message is written on its own line, in the form
+/// `[LEVEL target] message key=value ...`.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct StderrSink {
+    max_level: LevelFilter,
+}
+
+impl StderrSink {
+    /// Constructs a sink accepting the messages up to the verbosity `max_level`.
+    #[inline]
+    pub const fn new(max_level: LevelFilter) -> Self {
+        Self { max_level }
+    }
+}
+
+impl Default for StderrSink {
+    #[inline]
+    fn default() -> Self {
+        Self::new(LevelFilter::Info)
+    }
+}
+
+impl LogSink for StderrSink {
+    fn log(&self, record: &Record<'_>) {
+        if !self.max_level.allows(record.level()) {
+            return;
+        }
+
+        let stderr = std::io::stderr();
+        let mut stderr = stderr.lock();
+        let _ = write!(
+            stderr,
+            "[{} {}] {}",
+            record.level(),
+            record.target(),
+            record.message()
+        );
+        for (key, value) in record.key_values() {
+            let _ = write!(stderr, " {}={}", key, value);
+        }
+        let _ = writeln!(stderr);
+    }
+
+    #[inline]
+    fn max_level(&self) -> LevelFilter {
+        self.max_level
+    }
+
+    #[inline]
+    fn flush(&self) {
+        let _ = std::io::stderr().flush();
+    }
+}
@@ -1,21 +1,20 @@
 //! The `unwind_internal` extension.
-use crate::ffi::collections::NonNullConst;
-use crate::ffi::errors::StaticError;
+use crate::extensions::ExtensionInterface;
 use crate::ffi::extensions::unwind_internal;
 use crate::ffi::extensions::unwind_internal::{
     Context, PanicFn, ShutdownFn, UnwindInternalBinding,
 };
-use crate::ffi::CBaseBinding;
 use crate::ownership::Owned;
-use crate::sys::SysAPIMin;
-use crate::CBaseInterfaceInfo;
 use crate::{CBaseAPI, Error};
 use std::any::Any;
-use std::marker::PhantomData;
 use std::panic::UnwindSafe;
 use std::ptr::NonNull;
 
+mod guard;
+pub mod stack;
+
 pub use default_context::DefaultContext;
+pub use guard::{ffi_guard, GuardedReturn};
 pub use unwind_internal::UNWIND_INTERNAL_INTERFACE_NAME;
 pub use unwind_internal::UNWIND_INTERNAL_VERSION_BUILD;
 pub use unwind_internal::UNWIND_INTERNAL_VERSION_MAJOR;
@@ -51,23 +50,13 @@ unsafe impl Send for UnwindInternalContextRef {}
 unsafe impl Sync for UnwindInternalContextRef {}
 
 /// Interface of the `unwind_internal` extension.
-#[derive(Debug, Hash)]
-pub struct UnwindInternalInterface<'interface> {
-    _interface: NonNullConst<unwind_internal::UnwindInternalInterface>,
-    _phantom: PhantomData<&'interface unwind_internal::UnwindInternalInterface>,
-}
-
-unsafe impl Send for UnwindInternalInterface<'_> {}
-unsafe impl Sync for UnwindInternalInterface<'_> {}
+///
+/// The interface is fetched like any other extension with [ExtensionInterface::fetch].
+pub type UnwindInternalInterface<'interface> =
+    ExtensionInterface<'interface, unwind_internal::UnwindInternalInterface>;
 
 /// API of the `unwind_internal` interface.
 pub trait UnwindInternalAPI<'interface> {
-    /// Fetches the `unwind_internal` interface from the main interface.
-    fn from_interface(interface: &(impl SysAPIMin<'interface> + CBaseInterfaceInfo)) -> Self;
-
-    /// Retrieves a pointer to the native interface.
-    fn to_interface(&self) -> NonNullConst<unwind_internal::UnwindInternalInterface>;
-
     /// Fetches the active context.
     ///
     /// # Return
@@ -87,44 +76,23 @@ pub trait UnwindInternalAPI<'interface> {
 }
 
 impl<'interface> UnwindInternalAPI<'interface> for UnwindInternalInterface<'interface> {
-    fn from_interface(interface: &(impl SysAPIMin<'interface> + CBaseInterfaceInfo)) -> Self {
-        use crate::fn_caster::extensions::unwind_internal as casters;
-        if let Some(unw_int_fn) =
-            SysAPIMin::get_function(interface, &casters::GetUnwindInternalInterfaceCaster {})
-        {
-            Self {
-                _interface: unsafe { unw_int_fn(interface.internal_interface().base_module()) },
-                _phantom: PhantomData,
-            }
-        } else {
-            let error = Error::from(StaticError::new(
-                "Could not fetch the `unwind_internal` interface!",
-            ));
-            SysAPIMin::panic(interface, Some(error))
-        }
-    }
-
-    fn to_interface(&self) -> NonNullConst<unwind_internal::UnwindInternalInterface> {
-        self._interface
-    }
-
     fn get_context(
         &self,
         interface: &impl CBaseAPI<'interface>,
     ) -> Option<UnwindInternalContextRef> {
         unsafe {
             if let Some(context) = self
-                ._interface
+                .to_interface()
                 .as_ref()
                 .get_context(interface.internal_interface())
             {
                 if let Some(shutdown) = self
-                    ._interface
+                    .to_interface()
                     .as_ref()
                     .get_shutdown_fn(interface.internal_interface())
                 {
                     if let Some(panic) = self
-                        ._interface
+                        .to_interface()
                         .as_ref()
                         .get_panic_fn(interface.internal_interface())
                     {
@@ -148,28 +116,28 @@ impl<'interface> UnwindInternalAPI<'interface> for UnwindInternalInterface<'inte
     ) {
         unsafe {
             if let Some(context) = context {
-                self._interface
+                self.to_interface()
                     .into_mut()
                     .as_mut()
                     .set_context(interface.internal_interface(), Some(context._context));
-                self._interface
+                self.to_interface()
                     .into_mut()
                     .as_mut()
                     .set_shutdown_fn(interface.internal_interface(), Some(context._shutdown));
-                self._interface
+                self.to_interface()
                     .into_mut()
                     .as_mut()
                     .set_panic_fn(interface.internal_interface(), Some(context._panic));
             } else {
-                self._interface
+                self.to_interface()
                     .into_mut()
                     .as_mut()
                     .set_context(interface.internal_interface(), None);
-                self._interface
+                self.to_interface()
                     .into_mut()
                     .as_mut()
                     .set_shutdown_fn(interface.internal_interface(), None);
-                self._interface
+                self.to_interface()
                     .into_mut()
                     .as_mut()
                     .set_panic_fn(interface.internal_interface(), None);
@@ -323,16 +291,7 @@ pub mod default_context {
             std::panic::set_hook(saved_panic_hook);
             extension.set_context(interface, saved_context);
 
-            result.map_err(|e| {
-                if e.is::<ShutdownSignal>() {
-                    Signal::Shutdown
-                } else if e.is::<PanicSignal>() {
-                    let err = e.downcast::<PanicSignal>().unwrap();
-                    Signal::Panic(err.error.map(From::from))
-                } else {
-                    Signal::Other(e)
-                }
-            })
+            result.map_err(Signal::from_payload)
         }
     }
 
@@ -0,0 +1,179 @@
+//! Guard preventing panics from unwinding across the ffi boundary.
+//!
+//! Functions implemented in Rust and called by the host, like the functions of a
+//! sync handler or a loader, must not unwind into foreign code. Wrapping their body
+//! with [ffi_guard] catches any panic and converts it to an error, while a
+//! shutdown [Signal] is raised again.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use emf_core_base_rs::extensions::unwind_internal::ffi_guard;
+//! use emf_core_base_rs::ffi::collections::Result;
+//! use emf_core_base_rs::ffi::errors::Error;
+//!
+//! unsafe extern "C-unwind" fn callback(value: i32) -> Result<i32, Error> {
+//!     ffi_guard(|| {
+//!         if value < 0 {
+//!             panic!("negative value");
+//!         }
+//!         Result::Ok(value)
+//!     })
+//! }
+//! ```
+use crate::extensions::unwind_internal::default_context::{PanicSignal, ShutdownSignal};
+use crate::extensions::unwind_internal::Signal;
+use crate::ffi::collections::Result as ResultFFI;
+use crate::ffi::errors::{Error as ErrorFFI, SimpleError};
+use std::any::Any;
+use std::panic::UnwindSafe;
+
+impl Signal {
+    /// Constructs a signal from the payload of a caught panic.
+    pub fn from_payload(payload: Box<dyn Any + Send + 'static>) -> Self {
+        if payload.is::<ShutdownSignal>() {
+            Signal::Shutdown
+        } else if payload.is::<PanicSignal>() {
+            let signal = payload.downcast::<PanicSignal>().unwrap();
+            Signal::Panic(signal.error.map(From::from))
+        } else {
+            Signal::Other(payload)
+        }
+    }
+
+    /// Converts the signal to an error describing it.
+    ///
+    /// Panics carrying a `&str` or [String] message are converted to an error
+    /// with the same message.
+    pub fn into_error(self) -> ErrorFFI {
+        match self {
+            Signal::Shutdown => {
+                ErrorFFI::from(SimpleError::new("The interface has been shut down"))
+            }
+            Signal::Panic(Some(error)) => error.into_inner(),
+            Signal::Panic(None) => ErrorFFI::from(SimpleError::new("The interface panicked")),
+            Signal::Other(payload) => {
+                let message = match payload.downcast::<String>() {
+                    Ok(message) => *message,
+                    Err(payload) => match payload.downcast::<&'static str>() {
+                        Ok(message) => (*message).to_string(),
+                        Err(_) => "Unknown panic".to_string(),
+                    },
+                };
+                ErrorFFI::from(SimpleError::new(message))
+            }
+        }
+    }
+}
+
+/// Return types of the functions protected by [ffi_guard].
+pub trait GuardedReturn: Sized {
+    /// Handles a signal caught while computing the return value.
+    fn from_signal(signal: Signal) -> Self;
+}
+
+impl<T> GuardedReturn for T {
+    default fn from_signal(signal: Signal) -> Self {
+        raise(signal)
+    }
+}
+
+impl<T> GuardedReturn for ResultFFI<T, ErrorFFI> {
+    fn from_signal(signal: Signal) -> Self {
+        match signal {
+            Signal::Shutdown => raise(Signal::Shutdown),
+            signal => ResultFFI::Err(signal.into_error()),
+        }
+    }
+}
+
+/// Calls `f`, preventing panics from unwinding out of it.
+///
+/// A caught panic is converted to an [ErrorFFI] carrying the panic message, if `f`
+/// returns a [ResultFFI]. Otherwise, the panic is forwarded to the `panic` function
+/// of the global interface, or the process is aborted, if it is not available.
+/// A caught [Signal::Shutdown] is always forwarded to the `shutdown` function.
+///
+/// # Return
+///
+/// Return value of `f`.
+#[inline]
+pub fn ffi_guard<R>(f: impl FnOnce() -> R + UnwindSafe) -> R {
+    match std::panic::catch_unwind(f) {
+        Ok(value) => value,
+        Err(payload) => R::from_signal(Signal::from_payload(payload)),
+    }
+}
+
+/// Forwards a signal to the global interface.
+#[cold]
+fn raise(signal: Signal) -> ! {
+    #[cfg(feature = "global_api")]
+    if let Some(interface) = crate::global::try_get_interface() {
+        use crate::ffi::collections::Optional;
+        use crate::ffi::sys::api::SysBinding;
+        use crate::CBaseInterfaceInfo;
+
+        let mut interface = *interface.internal_interface();
+        unsafe {
+            match signal {
+                Signal::Shutdown => interface.shutdown(),
+                signal => interface.panic(Optional::Some(signal.into_error())),
+            }
+        }
+    }
+
+    eprintln!("{}", signal.into_error().display_info());
+    std::process::abort()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ffi_guard;
+    use crate::ffi::collections::Result;
+    use crate::ffi::errors::Error;
+
+    #[test]
+    fn panic_to_error() {
+        let saved_panic_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let result = ffi_guard(|| -> Result<i32, Error> { panic!("My panic message!") });
+        let formatted = ffi_guard(|| -> Result<i32, Error> { panic!("{} panic message!", "My") });
+        std::panic::set_hook(saved_panic_hook);
+
+        let error = result.into_rust().unwrap_err();
+        assert_eq!(error.display_info().as_ref(), "My panic message!");
+        let error = formatted.into_rust().unwrap_err();
+        assert_eq!(error.display_info().as_ref(), "My panic message!");
+
+        let result = ffi_guard(|| Result::<i32, Error>::Ok(5));
+        assert_eq!(result.into_rust().ok(), Some(5));
+        assert_eq!(ffi_guard(|| 5), 5);
+    }
+    #[test]
+    #[cfg(feature = "global_api")]
+    fn shutdown_is_raised() {
+        use crate::extensions::unwind_internal::default_context::ShutdownSignal;
+        use crate::ffi::record::{Recording, ReplayInterface};
+        use crate::global::GlobalContext;
+        use crate::CBaseRef;
+        use std::panic::AssertUnwindSafe;
+
+        let replay = ReplayInterface::new(Recording::new());
+        let context = GlobalContext::from_interface(unsafe { CBaseRef::new(replay.interface()) });
+
+        let saved_panic_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let payload = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            context.enter(|| {
+                ffi_guard(|| -> Result<i32, Error> { std::panic::panic_any(ShutdownSignal {}) })
+            })
+        }))
+        .unwrap_err();
+        std::panic::set_hook(saved_panic_hook);
+
+        // The signal reaches the `shutdown` function of the interface.
+        let message = payload.downcast::<&'static str>().unwrap();
+        assert_eq!(*message, "The replayed interface was shut down");
+    }
+}
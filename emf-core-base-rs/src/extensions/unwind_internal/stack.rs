@@ -0,0 +1,533 @@
+//! Nested unwinding contexts.
+//!
+//! Every thread maintains its own stack of [NamedContext]s. A signal raised through the
+//! interface is dispatched to the [ContextHandler] of the innermost context of the
+//! calling thread and is caught by that context, which records the stack of contexts
+//! active at the time the signal was raised.
+//!
+//! A context only extends the stack of the context it replaces, if that context has
+//! been installed by a [NamedContext] of the same thread. The stack restarts at the
+//! contexts installed by other threads, or through other copies of the crate, as their
+//! layout is unknown.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use emf_core_base_rs::{CBaseAccess, CBase};
+//! # let base_interface: &mut CBase = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+//! use emf_core_base_rs::extensions::unwind_internal::stack::NamedContext;
+//! use emf_core_base_rs::extensions::unwind_internal::{
+//!     UnwindInternalAPI, UnwindInternalInterface,
+//! };
+//! use emf_core_base_rs::sys::SysAPI;
+//!
+//! CBaseAccess::lock(base_interface, |interface| {
+//!     let mut extension = UnwindInternalInterface::fetch(interface).unwrap();
+//!     let outer = NamedContext::new("host");
+//!     let inner = NamedContext::new("my_module");
+//!
+//!     let result = outer.catch_unwind_traced(&mut extension, interface, |interface| {
+//!         let mut extension = UnwindInternalInterface::fetch(interface).unwrap();
+//!         inner.catch_unwind_traced(&mut extension, interface, |interface| interface.shutdown())
+//!     });
+//!
+//!     let caught = result.unwrap().unwrap_err();
+//!     assert_eq!(caught.caught_by, "my_module");
+//!     assert_eq!(caught.origin, vec!["host", "my_module"]);
+//! });
+//! ```
+use crate::extensions::unwind_internal::default_context::{PanicSignal, ShutdownSignal};
+use crate::extensions::unwind_internal::{
+    Signal, UnwindInternalAPI, UnwindInternalContextAPI, UnwindInternalContextRef,
+};
+use crate::ffi::collections::Optional;
+use crate::ffi::errors::{Error as ErrorFFI, SimpleError};
+use crate::ffi::extensions::unwind_internal::Context;
+use crate::ffi::TypeWrapper;
+use crate::ownership::Owned;
+use crate::{CBaseAPI, Error};
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::fmt::{Debug, Display, Formatter};
+use std::panic::{AssertUnwindSafe, UnwindSafe};
+use std::ptr::NonNull;
+use std::rc::Rc;
+use std::sync::Once;
+use std::thread::ThreadId;
+
+thread_local!(static CURRENT: Cell<Option<NonNull<Frame>>> = const { Cell::new(None) });
+
+/// A context on the stack of the current thread.
+///
+/// The frame is passed to the interface as the context pointer.
+struct Frame {
+    thread: ThreadId,
+    handler: Rc<dyn ContextHandler>,
+    origin: Vec<String>,
+}
+
+impl Frame {
+    fn new(origin: Vec<String>, handler: Rc<dyn ContextHandler>) -> Self {
+        Self {
+            thread: std::thread::current().id(),
+            handler,
+            origin,
+        }
+    }
+
+    /// Fetches the frame of a context installed by a [NamedContext] of the current thread.
+    ///
+    /// Other contexts are recognized by their shutdown function, so that their
+    /// context pointer is never read.
+    fn from_context_ref<'a>(context: &UnwindInternalContextRef) -> Option<&'a Frame> {
+        let shutdown: extern "C-unwind" fn(Option<NonNull<Context>>) -> ! = shutdown_fn;
+        if context._shutdown.0 as usize == shutdown as usize {
+            // SAFETY: `shutdown_fn` is only installed together with a `Frame`.
+            unsafe { Frame::from_context(Some(context._context)) }
+        } else {
+            None
+        }
+    }
+
+    /// Fetches the frame of a context installed by the current thread.
+    ///
+    /// # Safety
+    ///
+    /// `context` must have been installed by [NamedContext::catch_unwind_traced].
+    unsafe fn from_context<'a>(context: Option<NonNull<Context>>) -> Option<&'a Frame> {
+        let frame = context?.cast::<Frame>().as_ref();
+        if frame.thread == std::thread::current().id() {
+            Some(frame)
+        } else {
+            None
+        }
+    }
+}
+
+/// Keeps the panic hook from reporting the signals caught by a [NamedContext].
+///
+/// The hook is wrapped once, instead of being replaced for every call, as it is shared
+/// by all threads.
+fn silence_signals() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            let payload = info.payload();
+            if !payload.is::<StackSignal>()
+                && !payload.is::<ShutdownSignal>()
+                && !payload.is::<PanicSignal>()
+            {
+                hook(info)
+            }
+        }))
+    });
+}
+
+/// Handler of the signals raised within a [NamedContext].
+pub trait ContextHandler: 'static {
+    /// Handles a shutdown request.
+    ///
+    /// The default implementation unwinds to the context with [unwind_shutdown].
+    fn shutdown(&self, origin: &[String]) -> ! {
+        unwind_shutdown(origin)
+    }
+
+    /// Handles a panic.
+    ///
+    /// The default implementation unwinds to the context with [unwind_panic].
+    fn panic(&self, error: Option<Error<Owned>>, origin: &[String]) -> ! {
+        unwind_panic(error, origin)
+    }
+}
+
+/// Handler unwinding to the context on every signal.
+#[derive(Debug, Default, Copy, Clone, Hash)]
+pub struct UnwindHandler;
+
+impl ContextHandler for UnwindHandler {}
+
+/// Payload of a signal raised within a [NamedContext].
+#[derive(Debug)]
+struct StackSignal {
+    signal: Signal,
+    origin: Vec<String>,
+}
+
+/// Unwinds to the innermost [NamedContext] with a shutdown signal.
+pub fn unwind_shutdown(origin: &[String]) -> ! {
+    std::panic::panic_any(StackSignal {
+        signal: Signal::Shutdown,
+        origin: origin.to_vec(),
+    })
+}
+
+/// Unwinds to the innermost [NamedContext] with a panic signal.
+pub fn unwind_panic(error: Option<Error<Owned>>, origin: &[String]) -> ! {
+    std::panic::panic_any(StackSignal {
+        signal: Signal::Panic(error),
+        origin: origin.to_vec(),
+    })
+}
+
+/// A signal caught by a [NamedContext].
+#[derive(Debug)]
+pub struct CaughtSignal {
+    /// The caught signal.
+    pub signal: Signal,
+    /// Names of the contexts active when the signal was raised, from the outermost
+    /// to the innermost one.
+    pub origin: Vec<String>,
+    /// Name of the context which caught the signal.
+    pub caught_by: String,
+}
+
+impl Display for CaughtSignal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.signal {
+            Signal::Shutdown => write!(f, "shutdown")?,
+            Signal::Panic(Some(error)) => write!(f, "panic: {}", error)?,
+            Signal::Panic(None) => write!(f, "panic")?,
+            Signal::Other(_) => write!(f, "unknown panic")?,
+        }
+        write!(
+            f,
+            " raised in `{}`, caught by `{}`",
+            self.origin.join(" > "),
+            self.caught_by
+        )
+    }
+}
+
+/// Fetches the names of the contexts on the stack of the current thread,
+/// from the outermost to the innermost one.
+pub fn current_stack() -> Vec<String> {
+    CURRENT.with(|current| match current.get() {
+        Some(frame) => unsafe { frame.as_ref().origin.clone() },
+        None => Vec::new(),
+    })
+}
+
+/// A named unwinding context, which can be nested.
+pub struct NamedContext<H = UnwindHandler> {
+    name: Cow<'static, str>,
+    handler: Rc<H>,
+}
+
+impl NamedContext {
+    /// Constructs a context unwinding on every signal.
+    #[inline]
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self::with_handler(name, UnwindHandler)
+    }
+}
+
+impl<H: ContextHandler> NamedContext<H> {
+    /// Constructs a context with a custom handler.
+    #[inline]
+    pub fn with_handler(name: impl Into<Cow<'static, str>>, handler: H) -> Self {
+        Self {
+            name: name.into(),
+            handler: Rc::new(handler),
+        }
+    }
+
+    /// Fetches the name of the context.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Fetches the handler of the context.
+    #[inline]
+    pub fn handler(&self) -> &H {
+        &self.handler
+    }
+
+    /// Sets up the unwinding for the closure `f`
+    ///
+    /// Any panic or termination signal, that occurs within `f`, is caught and returned
+    /// together with its origin.
+    ///
+    /// # Return
+    ///
+    /// Return value from `f` or caught signal.
+    pub fn catch_unwind_traced<'interface, T, U>(
+        &self,
+        extension: &mut impl UnwindInternalAPI<'interface>,
+        interface: &mut T,
+        f: impl FnOnce(&mut T) -> U + UnwindSafe,
+    ) -> Result<U, CaughtSignal>
+    where
+        T: CBaseAPI<'interface>,
+    {
+        let saved_context = extension.get_context(interface);
+        let mut origin = match saved_context.as_ref().and_then(Frame::from_context_ref) {
+            Some(frame) => frame.origin.clone(),
+            None => Vec::new(),
+        };
+        origin.push(self.name.to_string());
+
+        let frame = Frame::new(origin, self.handler.clone());
+        let context = UnwindInternalContextRef {
+            _context: NonNull::from(&frame).cast(),
+            _shutdown: TypeWrapper(shutdown_fn),
+            _panic: TypeWrapper(panic_fn),
+        };
+
+        silence_signals();
+        let saved_frame = CURRENT.with(|current| current.replace(Some(NonNull::from(&frame))));
+        extension.set_context(interface, Some(context));
+
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| f(interface)));
+
+        extension.set_context(interface, saved_context);
+        CURRENT.with(|current| current.set(saved_frame));
+
+        result.map_err(|payload| match payload.downcast::<StackSignal>() {
+            Ok(signal) => CaughtSignal {
+                signal: signal.signal,
+                origin: signal.origin,
+                caught_by: self.name.to_string(),
+            },
+            Err(payload) => CaughtSignal {
+                signal: Signal::from_payload(payload),
+                origin: frame.origin.clone(),
+                caught_by: self.name.to_string(),
+            },
+        })
+    }
+}
+
+impl<H: ContextHandler> Debug for NamedContext<H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NamedContext")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl<'interface, H: ContextHandler> UnwindInternalContextAPI<'interface> for NamedContext<H> {
+    fn setup_unwind<T, U>(
+        &self,
+        extension: &mut impl UnwindInternalAPI<'interface>,
+        interface: &mut T,
+        f: impl FnOnce(&mut T) -> U + UnwindSafe,
+    ) -> U
+    where
+        T: CBaseAPI<'interface>,
+    {
+        match self.catch_unwind_traced(extension, interface, f) {
+            Ok(v) => v,
+            Err(caught) => match caught.signal {
+                Signal::Shutdown => interface.shutdown(),
+                Signal::Panic(err) => interface.panic(err),
+                Signal::Other(err) => interface.panic(Some(From::from(SimpleError::new(format!(
+                    "Unknown error: {:?}",
+                    err
+                ))))),
+            },
+        }
+    }
+
+    fn catch_unwind<T, U>(
+        &self,
+        extension: &mut impl UnwindInternalAPI<'interface>,
+        interface: &mut T,
+        f: impl FnOnce(&mut T) -> U + UnwindSafe,
+    ) -> Result<U, Signal>
+    where
+        T: CBaseAPI<'interface>,
+    {
+        self.catch_unwind_traced(extension, interface, f)
+            .map_err(|caught| caught.signal)
+    }
+}
+
+extern "C-unwind" fn shutdown_fn(context: Option<NonNull<Context>>) -> ! {
+    match unsafe { Frame::from_context(context) } {
+        Some(frame) => frame.handler.shutdown(&frame.origin),
+        // The context was installed by another thread.
+        None => std::panic::panic_any(ShutdownSignal {}),
+    }
+}
+
+extern "C-unwind" fn panic_fn(context: Option<NonNull<Context>>, err: Optional<ErrorFFI>) -> ! {
+    match unsafe { Frame::from_context(context) } {
+        Some(frame) => frame
+            .handler
+            .panic(err.into_rust().map(From::from), &frame.origin),
+        None => std::panic::panic_any(PanicSignal {
+            error: err.into_rust(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CaughtSignal;
+    use super::{current_stack, ContextHandler, NamedContext};
+    use crate::extensions::unwind_internal::{Signal, UnwindInternalAPI, UnwindInternalContextRef};
+    use crate::ffi::collections::Optional;
+    use crate::ffi::errors::StaticError;
+    use crate::ffi::record::{Recording, ReplayInterface};
+    use crate::ffi::TypeWrapper;
+    use crate::ownership::Owned;
+    use crate::{CBaseAPI, CBaseRef, Error};
+    use std::cell::Cell;
+    use std::ptr::NonNull;
+
+    thread_local! {
+        static CONTEXT: Cell<Option<UnwindInternalContextRef>> = const { Cell::new(None) };
+        static SHUTDOWNS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Extension storing the active context of the thread.
+    struct ThreadExtension;
+
+    impl<'interface> UnwindInternalAPI<'interface> for ThreadExtension {
+        fn get_context(
+            &self,
+            _interface: &impl CBaseAPI<'interface>,
+        ) -> Option<UnwindInternalContextRef> {
+            CONTEXT.with(Cell::get)
+        }
+
+        fn set_context(
+            &mut self,
+            _interface: &mut impl CBaseAPI<'interface>,
+            context: Option<UnwindInternalContextRef>,
+        ) {
+            CONTEXT.with(|active| active.set(context))
+        }
+    }
+
+    struct CountingHandler;
+
+    impl ContextHandler for CountingHandler {
+        fn shutdown(&self, origin: &[String]) -> ! {
+            SHUTDOWNS.with(|shutdowns| shutdowns.set(shutdowns.get() + 1));
+            super::unwind_shutdown(origin)
+        }
+    }
+
+    fn raise_shutdown() -> ! {
+        let context = CONTEXT.with(Cell::get).unwrap();
+        unsafe { (context._shutdown)(Some(context._context)) }
+    }
+
+    fn raise_panic(message: &'static str) -> ! {
+        let context = CONTEXT.with(Cell::get).unwrap();
+        let error = StaticError::new(message);
+        unsafe { (context._panic)(Some(context._context), Optional::Some(From::from(error))) }
+    }
+
+    #[test]
+    fn nested_origin() {
+        let replay = ReplayInterface::new(Recording::new());
+        let mut interface = unsafe { CBaseRef::new(replay.interface()) };
+
+        let outer = NamedContext::new("outer");
+        let result = outer.catch_unwind_traced(&mut ThreadExtension, &mut interface, |interface| {
+            let inner = NamedContext::with_handler("inner", CountingHandler);
+            let caught = inner
+                .catch_unwind_traced(&mut ThreadExtension, interface, |_| {
+                    assert_eq!(current_stack(), vec!["outer", "inner"]);
+                    raise_shutdown()
+                })
+                .unwrap_err();
+            assert!(matches!(caught.signal, Signal::Shutdown));
+            assert_eq!(caught.origin, vec!["outer", "inner"]);
+            assert_eq!(caught.caught_by, "inner");
+            assert_eq!(SHUTDOWNS.with(Cell::get), 1);
+
+            assert_eq!(current_stack(), vec!["outer"]);
+            raise_panic("My panic message!")
+        });
+
+        let caught = result.unwrap_err();
+        assert_eq!(caught.origin, vec!["outer"]);
+        assert_eq!(caught.caught_by, "outer");
+        match caught.signal {
+            Signal::Panic(Some(error)) => assert_eq!(error.to_string(), "My panic message!"),
+            signal => panic!("unexpected signal {:?}", signal),
+        }
+
+        assert!(current_stack().is_empty());
+        assert!(CONTEXT.with(Cell::get).is_none());
+    }
+
+    #[test]
+    fn foreign_origin() {
+        extern "C-unwind" fn unreachable_shutdown(_: Option<NonNull<Context>>) -> ! {
+            unreachable!()
+        }
+        extern "C-unwind" fn unreachable_panic(
+            _: Option<NonNull<Context>>,
+            _: Optional<crate::ffi::errors::Error>,
+        ) -> ! {
+            unreachable!()
+        }
+        use crate::ffi::extensions::unwind_internal::Context;
+
+        let replay = ReplayInterface::new(Recording::new());
+        let mut interface = unsafe { CBaseRef::new(replay.interface()) };
+
+        // A context installed by another copy of the crate, e.g. a `DefaultContext`,
+        // whose pointer must not be read.
+        let foreign = UnwindInternalContextRef {
+            _context: NonNull::dangling(),
+            _shutdown: TypeWrapper(unreachable_shutdown),
+            _panic: TypeWrapper(unreachable_panic),
+        };
+        CONTEXT.with(|active| active.set(Some(foreign)));
+
+        let context = NamedContext::new("module");
+        let caught = context
+            .catch_unwind_traced(&mut ThreadExtension, &mut interface, |_| {
+                assert_eq!(current_stack(), vec!["module"]);
+                raise_shutdown()
+            })
+            .unwrap_err();
+        assert_eq!(caught.origin, vec!["module"]);
+        assert_eq!(CONTEXT.with(Cell::get), Some(foreign));
+
+        // Rust panics are reported with the stack of the catching context.
+        let caught = context
+            .catch_unwind_traced(&mut ThreadExtension, &mut interface, |_| {
+                panic!("My panic message!")
+            })
+            .unwrap_err();
+        assert!(matches!(caught.signal, Signal::Other(_)));
+        assert_eq!(caught.origin, vec!["module"]);
+    }
+
+    #[test]
+    fn per_thread() {
+        let replay = ReplayInterface::new(Recording::new());
+        let mut interface = unsafe { CBaseRef::new(replay.interface()) };
+
+        let context = NamedContext::new("main");
+        let other = context
+            .catch_unwind_traced(&mut ThreadExtension, &mut interface, |_| {
+                assert_eq!(current_stack(), vec!["main"]);
+                std::thread::spawn(current_stack).join().unwrap()
+            })
+            .unwrap();
+        assert!(other.is_empty());
+    }
+
+    #[test]
+    fn display() {
+        static ERROR: StaticError<str> = StaticError::new("oops");
+        let caught = CaughtSignal {
+            signal: Signal::Panic(Some(Error::<Owned>::from_static(&ERROR))),
+            origin: vec!["host".to_string(), "module".to_string()],
+            caught_by: "module".to_string(),
+        };
+        assert_eq!(
+            caught.to_string(),
+            "panic: oops raised in `host > module`, caught by `module`"
+        );
+    }
+}
@@ -1,4 +1,52 @@
 //! Casters for the interface extensions.
+use crate::ffi::extensions::{Extension, GetExtensionInterfaceFn};
+use crate::ffi::{CBaseFn, FnId};
+use crate::fn_caster::FnCaster;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
 
-#[cfg(feature = "unwind_internal")]
-pub mod unwind_internal;
+/// Caster for the function returning the interface of the extension `E`.
+///
+/// Only extensions exposed through a function, i.e. with an [Extension::FN_ID],
+/// can be cast. Using the caster with other extensions fails to compile.
+pub struct ExtensionCaster<E> {
+    _phantom: PhantomData<fn() -> E>,
+}
+
+impl<E> ExtensionCaster<E> {
+    /// Constructs a new caster.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<E> Default for ExtensionCaster<E> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Extension> Debug for ExtensionCaster<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtensionCaster")
+            .field("name", &E::NAME)
+            .finish()
+    }
+}
+
+impl<E: Extension> FnCaster for ExtensionCaster<E> {
+    type Type = GetExtensionInterfaceFn<E::Interface>;
+    const ID: FnId = match E::FN_ID {
+        Some(id) => id,
+        None => panic!("The extension is not exposed through a function"),
+    };
+
+    #[inline]
+    fn cast(&self, func: CBaseFn) -> Self::Type {
+        unsafe { std::mem::transmute(func) }
+    }
+}
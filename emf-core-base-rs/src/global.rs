@@ -1,14 +1,29 @@
 //! Global api that can be used instead of the local api.
 //!
 //! The global api is the preferred way of interfacing with the interface.
+use crate::ffi::errors::StaticError;
 use crate::ffi::{
     sys::api::{GetFunctionFn as GetFunctionFnFFI, SysBinding},
-    Bool, CBase as CBaseFFI,
+    Bool, CBase as CBaseFFI, CBaseInterface,
 };
 use crate::init::CBaseAPILoader;
-use crate::{CBase, CBaseInterfaceInfo, CBaseRef};
-use std::mem::MaybeUninit;
+use crate::ownership::Owned;
+use crate::sys::sync_handler::lock_interface_timeout;
+use crate::wake_queue;
+use crate::{CBase, CBaseInterfaceInfo, CBaseRef, Error};
+use init_cell::InitCell;
+
+pub use context::GlobalContext;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::pin::Pin;
 use std::ptr::NonNull;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+mod context;
+mod init_cell;
+mod reentrancy;
 
 pub mod library;
 pub mod module;
@@ -18,28 +33,71 @@ pub mod version;
 #[cfg(feature = "extensions")]
 pub mod extensions;
 
-static mut INTERFACE: MaybeUninit<CBaseRef<'static>> = MaybeUninit::uninit();
+static INTERFACE: InitCell<GlobalInterface> = InitCell::new();
+
+static ALREADY_INITIALIZED: StaticError<str> =
+    StaticError::new("The interface has already been initialized.");
+static NOT_INITIALIZED: StaticError<str> =
+    StaticError::new("The interface has not been initialized.");
+
+/// Interface shared by the threads using the global api.
+#[derive(Debug)]
+struct GlobalInterface(CBaseRef<'static>);
+
+// Mutable access from multiple threads is synchronized with `LockToken`s.
+unsafe impl Sync for GlobalInterface {}
 
 /// Type indicating that dropping unlocks the interface.
 #[derive(Debug, Hash)]
-pub struct Unlock {}
+pub struct Unlock {
+    _interface: CBaseInterface,
+}
+
+unsafe impl Send for Unlock {}
+unsafe impl Sync for Unlock {}
 
 /// Type indicating that dropping does not unlock the interface.
 #[derive(Debug, Hash)]
 pub struct ForgetUnlock {}
 
+impl Unlock {
+    /// Records the acquisition of the lock of `interface`.
+    #[inline]
+    fn acquired(interface: &CBaseRef<'static>) -> Self {
+        reentrancy::acquire(interface);
+        Self {
+            _interface: *interface.internal_interface(),
+        }
+    }
+}
+
 impl Drop for Unlock {
     fn drop(&mut self) {
-        unsafe { SysBinding::unlock(get_interface()) }
+        let interface = unsafe { CBaseRef::new(self._interface) };
+        reentrancy::release(&interface);
+        unsafe { SysBinding::unlock(&interface) }
+        wake_queue::notify_unlock(&interface);
     }
 }
 
 /// A token indicating a locked interface.
-#[derive(Debug, Hash)]
+///
+/// The token is bound to the interface it was constructed from and the functions
+/// of the [global](crate::global) module operate on that interface, even if the
+/// token outlives the [GlobalContext] it was constructed in.
+#[derive(Debug)]
 pub struct LockToken<T> {
+    _interface: CBaseRef<'static>,
     _phantom: T,
 }
 
+impl<T: Hash> Hash for LockToken<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self._interface.internal_interface().hash(state);
+        self._phantom.hash(state);
+    }
+}
+
 impl LockToken<Unlock> {
     /// Takes ownership of the token and exchanges it with
     /// a token which does not unlock the interface.
@@ -48,13 +106,27 @@ impl LockToken<Unlock> {
     ///
     /// Improper usage can leave the interface in a locked state.
     pub unsafe fn relinquish_locking(self) -> LockToken<ForgetUnlock> {
+        let interface = CBaseRef::new(self._phantom._interface);
+        reentrancy::release(&interface);
         std::mem::forget(self);
         LockToken {
+            _interface: interface,
             _phantom: ForgetUnlock {},
         }
     }
 }
 
+impl LockToken<Unlock> {
+    /// Constructs a token for the locked `interface`.
+    #[inline]
+    fn acquired(interface: &CBaseRef<'static>) -> Self {
+        LockToken {
+            _interface: unsafe { CBaseRef::new(*interface.internal_interface()) },
+            _phantom: Unlock::acquired(interface),
+        }
+    }
+}
+
 impl LockToken<ForgetUnlock> {
     /// Takes ownership of the token and exchanges it with
     /// a token which unlocks the interface.
@@ -63,14 +135,23 @@ impl LockToken<ForgetUnlock> {
     ///
     /// Improper usage can unlock the interface multiple times.
     pub unsafe fn take_ownership(self) -> LockToken<Unlock> {
-        std::mem::forget(self);
-        LockToken {
-            _phantom: Unlock {},
-        }
+        LockToken::acquired(&self._interface)
     }
 }
 
 impl<T> LockToken<T> {
+    /// Fetches a reference to the interface locked by the token.
+    #[inline]
+    pub fn interface(&self) -> &CBaseRef<'static> {
+        &self._interface
+    }
+
+    /// Fetches a mutable reference to the interface locked by the token.
+    #[inline]
+    pub fn interface_mut(&mut self) -> &mut CBaseRef<'static> {
+        &mut self._interface
+    }
+
     /// Constructs a new token by locking the interface.
     ///
     /// The calling thread is stalled until the lock can be acquired.
@@ -79,15 +160,28 @@ impl<T> LockToken<T> {
     /// # Return
     ///
     /// A token.
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, this function panics if the calling thread already
+    /// holds a [LockToken] and the active sync handler is not reentrant,
+    /// as the call would deadlock otherwise. A reentrant sync handler can be
+    /// installed with [ReentrantMutex](crate::sys::sync_handler::ReentrantMutex).
     #[inline]
     #[must_use]
     pub fn lock() -> LockToken<Unlock> {
+        let interface = get_interface();
         unsafe {
-            SysBinding::lock(get_interface());
-            LockToken {
-                _phantom: Unlock {},
+            if reentrancy::is_held(interface) {
+                if SysBinding::try_lock(interface) == Bool::False {
+                    reentrancy::deadlock()
+                }
+            } else {
+                SysBinding::lock(interface);
             }
         }
+
+        LockToken::acquired(interface)
     }
 
     /// Tries to lock the interface.
@@ -100,13 +194,42 @@ impl<T> LockToken<T> {
     #[inline]
     #[must_use]
     pub fn try_lock() -> Option<LockToken<Unlock>> {
-        unsafe {
-            match SysBinding::try_lock(get_interface()) {
-                Bool::False => None,
-                Bool::True => Some(LockToken {
-                    _phantom: Unlock {},
-                }),
-            }
+        let interface = get_interface();
+        match unsafe { SysBinding::try_lock(interface) } {
+            Bool::False => None,
+            Bool::True => Some(LockToken::acquired(interface)),
+        }
+    }
+
+    /// Tries to lock the interface, waiting at most `timeout`.
+    ///
+    /// # Return
+    ///
+    /// [LockToken] on success and [None] otherwise.
+    #[inline]
+    #[must_use]
+    pub fn lock_timeout(timeout: Duration) -> Option<LockToken<Unlock>> {
+        let interface = get_interface();
+        if unsafe { lock_interface_timeout(interface, timeout) } {
+            Some(LockToken::acquired(interface))
+        } else {
+            None
+        }
+    }
+
+    /// Locks the interface asynchronously.
+    ///
+    /// The returned future resolves once the lock has been acquired.
+    /// See [CBase::lock_async] for details.
+    ///
+    /// # Return
+    ///
+    /// A future resolving to a token.
+    #[inline]
+    pub fn lock_async() -> LockTokenFuture {
+        LockTokenFuture {
+            _interface: *get_interface().internal_interface(),
+            _key: None,
         }
     }
 
@@ -124,36 +247,169 @@ impl<T> LockToken<T> {
     #[inline]
     pub unsafe fn assume_locked() -> LockToken<ForgetUnlock> {
         LockToken {
+            _interface: CBaseRef::new(*get_interface().internal_interface()),
             _phantom: ForgetUnlock {},
         }
     }
 }
 
+/// Future returned by [LockToken::lock_async].
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct LockTokenFuture {
+    _interface: CBaseInterface,
+    _key: Option<usize>,
+}
+
+unsafe impl Send for LockTokenFuture {}
+
+impl Future for LockTokenFuture {
+    type Output = LockToken<Unlock>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let interface = unsafe { CBaseRef::new(this._interface) };
+        let try_lock = || unsafe { SysBinding::try_lock(&interface) == Bool::True };
+        match wake_queue::poll_lock(&interface, &mut this._key, cx, try_lock) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(_) => Poll::Ready(LockToken::acquired(&interface)),
+        }
+    }
+}
+
+impl Drop for LockTokenFuture {
+    #[inline]
+    fn drop(&mut self) {
+        let interface = unsafe { CBaseRef::new(self._interface) };
+        wake_queue::cancel(&interface, self._key.take())
+    }
+}
+
 /// Initializes the interface.
+///
+/// An [InterceptedInterface](crate::ffi::intercept::InterceptedInterface) is installed
+/// by passing its `base_module` and `get_function_fn`.
+///
+/// # Example
+///
+/// ```no_run
+/// # use emf_core_base_rs::ffi::CBaseInterface;
+/// # let interface: CBaseInterface = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// use emf_core_base_rs::ffi::intercept::{InterceptHooks, InterceptedInterface};
+/// use emf_core_base_rs::ffi::{CBaseBinding, FnId};
+/// use emf_core_base_rs::global;
+///
+/// #[derive(Debug)]
+/// struct PrintCalls;
+///
+/// impl InterceptHooks for PrintCalls {
+///     fn before(&self, id: FnId) {
+///         println!("calling {:?}", id);
+///     }
+/// }
+///
+/// let intercepted = unsafe { InterceptedInterface::new(interface, PrintCalls) };
+/// global::initialize(intercepted.base_module(), intercepted.get_function_fn()).unwrap();
+///
+/// // The decorator must outlive the interface.
+/// std::mem::forget(intercepted);
+/// ```
+///
+/// # Failure
+///
+/// Fails if the interface has already been initialized or if the
+/// host does not export a compatible interface.
+///
+/// # Return
+///
+/// Error on failure.
 #[inline]
-pub fn initialize(base_module: Option<NonNull<CBaseFFI>>, get_function_fn: GetFunctionFnFFI) {
-    unsafe {
-        INTERFACE = MaybeUninit::new(CBaseRef::new(
-            *CBase::fetch_interface(base_module, get_function_fn).internal_interface(),
-        ));
-    }
+pub fn initialize(
+    base_module: Option<NonNull<CBaseFFI>>,
+    get_function_fn: GetFunctionFnFFI,
+) -> Result<(), Error<Owned>> {
+    INTERFACE
+        .try_init(|| unsafe {
+            CBase::try_fetch_interface(base_module, get_function_fn)
+                .map(|interface| GlobalInterface(CBaseRef::new(*interface.internal_interface())))
+        })
+        .unwrap_or_else(|| Err(Error::from_static(&ALREADY_INITIALIZED)))
+}
+
+/// Deinitializes the interface.
+///
+/// Tears down the state of the interface, allowing a subsequent call to [initialize].
+///
+/// # Failure
+///
+/// Fails if the interface has not been initialized.
+///
+/// # Return
+///
+/// Error on failure.
+///
+/// # Safety
+///
+/// No references returned by [get_interface], [get_mut_interface] or
+/// [try_get_interface] and no [LockToken] may be alive.
+pub unsafe fn deinitialize() -> Result<(), Error<Owned>> {
+    INTERFACE
+        .take()
+        .map(|_| ())
+        .ok_or_else(|| Error::from_static(&NOT_INITIALIZED))
+}
+
+/// Checks whether the interface has been initialized.
+#[inline]
+pub fn is_initialized() -> bool {
+    INTERFACE.is_initialized()
+}
 
-    #[cfg(feature = "unwind_internal")]
-    extensions::unwind_internal::initialize();
+/// Fetches a reference to the interface, if it has been initialized.
+///
+/// Returns the interface of the current [GlobalContext], if one has been entered.
+/// Using the interface is safe, as long as a [LockToken] is constructed.
+#[inline]
+pub fn try_get_interface<'a>() -> Option<&'a CBaseRef<'static>> {
+    match context::current_interface() {
+        Some(interface) => Some(unsafe { &*interface.as_ptr() }),
+        None => INTERFACE.get().map(|interface| &interface.0),
+    }
 }
 
 /// Fetches a reference to the interface.
 ///
+/// Returns the interface of the current [GlobalContext], if one has been entered.
 /// Using the interface is safe, as long as a [LockToken] is constructed.
+///
+/// # Panics
+///
+/// Panics if the interface has not been initialized.
 #[inline]
 pub fn get_interface<'a>() -> &'a CBaseRef<'static> {
-    unsafe { &*INTERFACE.as_ptr() }
+    try_get_interface().unwrap_or_else(|| not_initialized())
 }
 
 /// Fetches a mutable reference to the interface.
 ///
+/// Returns the interface of the current [GlobalContext], if one has been entered.
 /// Using the interface is safe, as long as a [LockToken] is constructed.
+///
+/// # Panics
+///
+/// Panics if the interface has not been initialized.
 #[inline]
 pub fn get_mut_interface<'a>() -> &'a mut CBaseRef<'static> {
-    unsafe { &mut *INTERFACE.as_mut_ptr() }
+    match context::current_interface() {
+        Some(interface) => unsafe { &mut *interface.as_ptr() },
+        None => unsafe { INTERFACE.get_mut() }
+            .map(|interface| &mut interface.0)
+            .unwrap_or_else(|| not_initialized()),
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn not_initialized() -> ! {
+    panic!("{}", NOT_INITIALIZED)
 }
@@ -0,0 +1,143 @@
+//! Isolated instances of the global api.
+use crate::ffi::{sys::api::GetFunctionFn as GetFunctionFnFFI, CBase as CBaseFFI};
+use crate::init::CBaseAPILoader;
+use crate::ownership::Owned;
+use crate::{CBase, CBaseInterfaceInfo, CBaseRef, Error};
+use std::cell::{Cell, UnsafeCell};
+use std::ptr::NonNull;
+
+thread_local!(static CURRENT: Cell<Option<NonNull<GlobalContext>>> = const { Cell::new(None) });
+
+/// An isolated instance of the global api.
+///
+/// By default, the functions of the [global](crate::global) module operate on the
+/// interface set up by [initialize](crate::global::initialize). Inside of
+/// [GlobalContext::enter] they operate on the interface of the context instead,
+/// which allows multiple interfaces to coexist in one process.
+///
+/// # Example
+///
+/// ```no_run
+/// # use emf_core_base_rs::ffi::{CBase, sys::api::GetFunctionFn};
+/// # let base_module: Option<std::ptr::NonNull<CBase>> = None;
+/// # let get_function_fn: GetFunctionFn = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// use emf_core_base_rs::global::{GlobalContext, LockToken, Unlock, module};
+///
+/// let context = GlobalContext::new(base_module, get_function_fn).unwrap();
+/// let num_modules = context.enter(|| {
+///     let lock = LockToken::<Unlock>::lock();
+///     module::get_num_modules(&lock)
+/// });
+/// ```
+#[derive(Debug)]
+pub struct GlobalContext {
+    interface: UnsafeCell<CBaseRef<'static>>,
+}
+
+unsafe impl Send for GlobalContext {}
+unsafe impl Sync for GlobalContext {}
+
+impl GlobalContext {
+    /// Constructs a new context by fetching the interface.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the host does not export a compatible interface.
+    ///
+    /// # Return
+    ///
+    /// Context on success, error otherwise.
+    #[inline]
+    pub fn new(
+        base_module: Option<NonNull<CBaseFFI>>,
+        get_function_fn: GetFunctionFnFFI,
+    ) -> Result<Self, Error<Owned>> {
+        unsafe {
+            CBase::try_fetch_interface(base_module, get_function_fn).map(|interface| {
+                Self::from_interface(CBaseRef::new(*interface.internal_interface()))
+            })
+        }
+    }
+
+    /// Constructs a new context from an existing interface.
+    #[inline]
+    pub fn from_interface(interface: CBaseRef<'static>) -> Self {
+        Self {
+            interface: UnsafeCell::new(interface),
+        }
+    }
+
+    /// Makes the context the current context of the calling thread while executing `f`.
+    ///
+    /// Contexts can be nested. The previous context is restored when `f` returns or panics.
+    /// [LockToken](crate::global::LockToken)s acquired within `f` lock the interface of
+    /// the context and remain bound to it, even if they escape `f`.
+    ///
+    /// # Return
+    ///
+    /// Return value from `f`.
+    pub fn enter<U>(&self, f: impl FnOnce() -> U) -> U {
+        struct Restore(Option<NonNull<GlobalContext>>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0))
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(NonNull::from(self)))));
+        f()
+    }
+
+    /// Fetches a reference to the interface of the context.
+    #[inline]
+    pub fn interface(&self) -> &CBaseRef<'static> {
+        unsafe { &*self.interface.get() }
+    }
+}
+
+/// Fetches the interface of the current context, if any.
+#[inline]
+pub(crate) fn current_interface() -> Option<NonNull<CBaseRef<'static>>> {
+    CURRENT.with(|current| {
+        current
+            .get()
+            .map(|context| unsafe { NonNull::new_unchecked(context.as_ref().interface.get()) })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ffi::record::{Recording, ReplayInterface};
+    use crate::ffi::{CBase, CBaseInterface, TypeWrapper};
+    use crate::global::{GlobalContext, LockToken, Unlock};
+    use crate::{CBaseInterfaceInfo, CBaseRef};
+    use std::ptr::NonNull;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static UNLOCKS: AtomicUsize = AtomicUsize::new(0);
+
+    unsafe extern "C-unwind" fn count_unlock(_base_module: Option<NonNull<CBase>>) {
+        UNLOCKS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn escaped_token() {
+        let replay = ReplayInterface::new(Recording::new());
+        let mut vtable = unsafe { *replay.interface().vtable.as_ref() };
+        vtable.sys_unlock_fn = TypeWrapper(count_unlock);
+        let interface = CBaseInterface {
+            base_module: replay.interface().base_module,
+            vtable: (&vtable).into(),
+        };
+
+        let token = {
+            let context = GlobalContext::from_interface(unsafe { CBaseRef::new(interface) });
+            context.enter(LockToken::<Unlock>::lock)
+        };
+
+        assert_eq!(*token.interface().internal_interface(), interface);
+        drop(token);
+        assert_eq!(UNLOCKS.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,4 +1,30 @@
 //! Extensions
+use crate::extensions::{Extension, ExtensionInterface};
+use crate::global::LockToken;
+use crate::ownership::Owned;
+use crate::Error;
 
 #[cfg(feature = "unwind_internal")]
 pub mod unwind_internal;
+
+/// Checks whether the extension is available.
+#[inline]
+pub fn is_available<E: Extension, T>(token: &LockToken<T>) -> bool {
+    ExtensionInterface::<E>::is_available(token.interface())
+}
+
+/// Fetches the interface of an extension.
+///
+/// # Failure
+///
+/// Fails if the extension is not available.
+///
+/// # Return
+///
+/// Interface on success, error otherwise.
+#[inline]
+pub fn fetch<E: Extension, T>(
+    token: &LockToken<T>,
+) -> Result<ExtensionInterface<'static, E>, Error<Owned>> {
+    ExtensionInterface::fetch(token.interface())
+}
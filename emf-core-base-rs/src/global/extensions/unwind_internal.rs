@@ -18,35 +18,38 @@
 use super::super::{
     get_interface as get_base_interface, get_mut_interface as get_mut_base_interface,
 };
+use crate::extensions::unwind_internal::stack::{CaughtSignal, ContextHandler, NamedContext};
 use crate::extensions::unwind_internal::{
     default_context::DefaultContext, Signal, UnwindInternalAPI, UnwindInternalContextAPI,
     UnwindInternalContextRef, UnwindInternalInterface,
 };
-use std::mem::MaybeUninit;
+use crate::ffi::errors::StaticError;
+use crate::sys::SysAPIMin;
+use crate::Error;
 use std::panic::UnwindSafe;
 
-static mut INTERFACE: MaybeUninit<UnwindInternalInterface<'static>> = MaybeUninit::uninit();
-
-/// Initializes the interface.
+/// Fetches the interface.
+///
+/// The extension is fetched from the interface returned by
+/// [get_interface](crate::global::get_interface) on every call, so that it always
+/// matches the current [GlobalContext](crate::global::GlobalContext).
+///
+/// # Panics
+///
+/// Panics if the extension is not available.
 #[inline]
-pub fn initialize() {
-    unsafe {
-        INTERFACE = MaybeUninit::new(UnwindInternalInterface::from_interface(get_base_interface()))
+pub fn get_interface() -> UnwindInternalInterface<'static> {
+    let interface = get_base_interface();
+    match UnwindInternalInterface::fetch(interface) {
+        Ok(extension) => extension,
+        Err(_) => {
+            static ERROR: StaticError<str> =
+                StaticError::new("Could not fetch the `unwind_internal` interface!");
+            SysAPIMin::panic(interface, Some(Error::from_static(&ERROR)))
+        }
     }
 }
 
-/// Fetches a reference to the interface.
-#[inline]
-pub fn get_interface<'a>() -> &'a UnwindInternalInterface<'static> {
-    unsafe { &*INTERFACE.as_ptr() }
-}
-
-/// Fetches a mutable reference to the interface.
-#[inline]
-pub fn get_mut_interface<'a>() -> &'a mut UnwindInternalInterface<'static> {
-    unsafe { &mut *INTERFACE.as_mut_ptr() }
-}
-
 /// Fetches the active context.
 ///
 /// # Return
@@ -60,7 +63,7 @@ pub fn get_context() -> Option<UnwindInternalContextRef> {
 /// Sets the new active context.
 #[inline]
 pub fn set_context(context: Option<UnwindInternalContextRef>) {
-    get_mut_interface().set_context(get_mut_base_interface(), context)
+    get_interface().set_context(get_mut_base_interface(), context)
 }
 
 /// Sets up the unwinding for the closure `f`
@@ -73,7 +76,7 @@ pub fn set_context(context: Option<UnwindInternalContextRef>) {
 #[inline]
 pub fn setup_unwind<T>(f: impl FnOnce() -> T + UnwindSafe) -> T {
     DefaultContext::default().setup_unwind(
-        get_mut_interface(),
+        &mut get_interface(),
         get_mut_base_interface(),
         move |_interface| f(),
     )
@@ -89,7 +92,27 @@ pub fn setup_unwind<T>(f: impl FnOnce() -> T + UnwindSafe) -> T {
 #[inline]
 pub fn catch_unwind<T>(f: impl FnOnce() -> T + UnwindSafe) -> Result<T, Signal> {
     DefaultContext::default().catch_unwind(
-        get_mut_interface(),
+        &mut get_interface(),
+        get_mut_base_interface(),
+        move |_interface| f(),
+    )
+}
+
+/// Sets up the unwinding for the closure `f` within a [NamedContext].
+///
+/// Any panic or termination signal, that occurs within `f`, is caught and returned
+/// together with its origin.
+///
+/// # Return
+///
+/// Return value from `f` or caught signal.
+#[inline]
+pub fn catch_unwind_traced<T, H: ContextHandler>(
+    context: &NamedContext<H>,
+    f: impl FnOnce() -> T + UnwindSafe,
+) -> Result<T, CaughtSignal> {
+    context.catch_unwind_traced(
+        &mut get_interface(),
         get_mut_base_interface(),
         move |_interface| f(),
     )
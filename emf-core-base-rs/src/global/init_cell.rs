@@ -0,0 +1,171 @@
+//! Cell for global state that can be initialized and torn down at runtime.
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+const UNINITIALIZED: u8 = 0;
+const INITIALIZING: u8 = 1;
+const INITIALIZED: u8 = 2;
+
+/// A cell which is written at most once between two resets.
+#[derive(Debug)]
+pub struct InitCell<T> {
+    state: AtomicU8,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+// Mutable access to the value is synchronized by the users of the cell.
+unsafe impl<T: Send + Sync> Sync for InitCell<T> {}
+
+impl<T> InitCell<T> {
+    /// Constructs a new uninitialized cell.
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(UNINITIALIZED),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Checks whether the cell contains a value.
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.state.load(Ordering::Acquire) == INITIALIZED
+    }
+
+    /// Initializes the cell with the value returned by `f`.
+    ///
+    /// The cell is left uninitialized, if `f` fails or panics.
+    ///
+    /// # Return
+    ///
+    /// [Option::None] if the cell is already initialized or another
+    /// initialization is in progress, the result of `f` otherwise.
+    pub fn try_init<E>(&self, f: impl FnOnce() -> Result<T, E>) -> Option<Result<(), E>> {
+        struct ResetOnUnwind<'a>(&'a AtomicU8);
+
+        impl Drop for ResetOnUnwind<'_> {
+            fn drop(&mut self) {
+                self.0.store(UNINITIALIZED, Ordering::Release)
+            }
+        }
+
+        if self
+            .state
+            .compare_exchange(
+                UNINITIALIZED,
+                INITIALIZING,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            )
+            .is_err()
+        {
+            return None;
+        }
+
+        let reset = ResetOnUnwind(&self.state);
+        let value = match f() {
+            Ok(value) => value,
+            Err(e) => return Some(Err(e)),
+        };
+        std::mem::forget(reset);
+
+        unsafe { (*self.value.get()).as_mut_ptr().write(value) };
+        self.state.store(INITIALIZED, Ordering::Release);
+        Some(Ok(()))
+    }
+
+    /// Fetches a reference to the value.
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        if self.is_initialized() {
+            Some(unsafe { &*(*self.value.get()).as_ptr() })
+        } else {
+            None
+        }
+    }
+
+    /// Fetches a mutable reference to the value.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee unique access to the value.
+    #[inline]
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_mut(&self) -> Option<&mut T> {
+        if self.is_initialized() {
+            Some(&mut *(*self.value.get()).as_mut_ptr())
+        } else {
+            None
+        }
+    }
+
+    /// Takes the value out of the cell, leaving it uninitialized.
+    ///
+    /// # Safety
+    ///
+    /// No references to the value may be alive.
+    pub unsafe fn take(&self) -> Option<T> {
+        if self
+            .state
+            .compare_exchange(
+                INITIALIZED,
+                INITIALIZING,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            )
+            .is_err()
+        {
+            return None;
+        }
+
+        let value = (*self.value.get()).as_ptr().read();
+        self.state.store(UNINITIALIZED, Ordering::Release);
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::InitCell;
+    use std::panic::AssertUnwindSafe;
+
+    #[test]
+    fn init_and_take() {
+        let cell = InitCell::new();
+        assert!(!cell.is_initialized());
+        assert_eq!(cell.get(), None);
+
+        assert_eq!(cell.try_init(|| Ok::<_, ()>(5)), Some(Ok(())));
+        assert!(cell.is_initialized());
+        assert_eq!(cell.get(), Some(&5));
+
+        assert_eq!(cell.try_init(|| Ok::<_, ()>(6)), None);
+        assert_eq!(cell.get(), Some(&5));
+
+        unsafe {
+            *cell.get_mut().unwrap() = 7;
+            assert_eq!(cell.take(), Some(7));
+            assert_eq!(cell.take(), None);
+        }
+        assert!(!cell.is_initialized());
+
+        assert_eq!(cell.try_init(|| Ok::<_, ()>(8)), Some(Ok(())));
+        assert_eq!(cell.get(), Some(&8));
+    }
+
+    #[test]
+    fn failed_init() {
+        let cell = InitCell::<i32>::new();
+        assert_eq!(cell.try_init(|| Err("error")), Some(Err("error")));
+        assert!(!cell.is_initialized());
+
+        // The panic message is captured by the test harness.
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            cell.try_init(|| -> Result<_, ()> { panic!("initialization failed") })
+        }));
+
+        assert!(result.is_err());
+        assert!(!cell.is_initialized());
+        assert_eq!(cell.try_init(|| Ok::<_, ()>(1)), Some(Ok(())));
+    }
+}
@@ -31,7 +31,7 @@
 //! ```
 use crate::ffi::collections::NonNullConst;
 use crate::ffi::CBaseFn;
-use crate::global::{get_interface, get_mut_interface, LockToken};
+use crate::global::LockToken;
 use crate::library::library_loader::{LibraryLoader, LibraryLoaderABICompat, LibraryLoaderAPI};
 use crate::library::{InternalLibrary, Library, LibraryAPI, LibraryType, Loader, Symbol};
 use crate::ownership::{BorrowMutable, ImmutableAccessIdentifier, MutableAccessIdentifier, Owned};
@@ -54,7 +54,7 @@ use std::pin::Pin;
 /// Handle on success, error otherwise.
 #[inline]
 pub fn register_loader<L, LT, T>(
-    _token: &mut LockToken<L>,
+    token: &mut LockToken<L>,
     loader: Pin<&'static LT>,
     lib_type: impl AsRef<str>,
 ) -> Result<Loader<'static, Owned>, Error<Owned>>
@@ -62,7 +62,7 @@ where
     T: LibraryLoaderAPI<'static> + LibraryLoaderABICompat,
     LibraryLoader<T, Owned>: From<&'static LT>,
 {
-    LibraryAPI::register_loader(get_mut_interface(), loader, lib_type)
+    LibraryAPI::register_loader(token.interface_mut(), loader, lib_type)
 }
 
 /// Unregisters an existing loader.
@@ -76,10 +76,10 @@ where
 /// Error on failure.
 #[inline]
 pub fn unregister_loader<L>(
-    _token: &mut LockToken<L>,
+    token: &mut LockToken<L>,
     loader: Loader<'_, Owned>,
 ) -> Result<(), Error<Owned>> {
-    LibraryAPI::unregister_loader(get_mut_interface(), loader)
+    LibraryAPI::unregister_loader(token.interface_mut(), loader)
 }
 
 /// Fetches the interface of a library loader.
@@ -93,14 +93,14 @@ pub fn unregister_loader<L>(
 /// Interface on success, error otherwise.
 #[inline]
 pub fn get_loader_interface<'loader, L, O, T>(
-    _token: &LockToken<L>,
+    token: &LockToken<L>,
     loader: &Loader<'loader, O>,
 ) -> Result<LibraryLoader<T, O>, Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
     T: LibraryLoaderAPI<'loader> + LibraryLoaderABICompat,
 {
-    LibraryAPI::get_loader_interface(get_interface(), loader)
+    LibraryAPI::get_loader_interface(token.interface(), loader)
 }
 
 /// Fetches the loader handle associated with the library type.
@@ -114,10 +114,10 @@ where
 /// Handle on success, error otherwise.
 #[inline]
 pub fn get_loader_handle_from_type<'tok, L>(
-    _token: &'tok LockToken<L>,
+    token: &'tok LockToken<L>,
     lib_type: impl AsRef<str>,
 ) -> Result<Loader<'static, BorrowMutable<'tok>>, Error<Owned>> {
-    LibraryAPI::get_loader_handle_from_type(get_interface(), lib_type)
+    LibraryAPI::get_loader_handle_from_type(token.interface(), lib_type)
 }
 
 /// Fetches the loader handle linked with the library handle.
@@ -131,13 +131,13 @@ pub fn get_loader_handle_from_type<'tok, L>(
 /// Handle on success, error otherwise.
 #[inline]
 pub fn get_loader_handle_from_library<'l, 'library, L, O>(
-    _token: &'l LockToken<L>,
+    token: &'l LockToken<L>,
     library: &Library<'library, O>,
 ) -> Result<Loader<'library, BorrowMutable<'l>>, Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
 {
-    LibraryAPI::get_loader_handle_from_library(get_interface(), library)
+    LibraryAPI::get_loader_handle_from_library(token.interface(), library)
 }
 
 /// Fetches the number of registered loaders.
@@ -146,8 +146,8 @@ where
 ///
 /// Number of registered loaders.
 #[inline]
-pub fn get_num_loaders<L>(_token: &LockToken<L>) -> usize {
-    LibraryAPI::get_num_loaders(get_interface())
+pub fn get_num_loaders<L>(token: &LockToken<L>) -> usize {
+    LibraryAPI::get_num_loaders(token.interface())
 }
 
 /// Checks if a the library handle is valid.
@@ -156,11 +156,11 @@ pub fn get_num_loaders<L>(_token: &LockToken<L>) -> usize {
 ///
 /// [true] if the handle is valid, [false] otherwise.
 #[inline]
-pub fn library_exists<'library, L, O>(_token: &LockToken<L>, library: &Library<'library, O>) -> bool
+pub fn library_exists<'library, L, O>(token: &LockToken<L>, library: &Library<'library, O>) -> bool
 where
     O: ImmutableAccessIdentifier,
 {
-    LibraryAPI::library_exists(get_interface(), library)
+    LibraryAPI::library_exists(token.interface(), library)
 }
 
 /// Checks if a library type exists.
@@ -170,10 +170,10 @@ where
 /// [true] if the type exists, [false] otherwise.
 #[inline]
 pub fn type_exists<L>(
-    _token: &LockToken<L>,
+    token: &LockToken<L>,
     lib_type: impl AsRef<str>,
 ) -> Result<bool, Error<Owned>> {
-    LibraryAPI::type_exists(get_interface(), lib_type)
+    LibraryAPI::type_exists(token.interface(), lib_type)
 }
 
 /// Copies the strings of the registered library types into a buffer.
@@ -187,10 +187,10 @@ pub fn type_exists<L>(
 /// Number of written types on success, error otherwise.
 #[inline]
 pub fn get_library_types<L>(
-    _token: &LockToken<L>,
+    token: &LockToken<L>,
     buffer: impl AsMut<[LibraryType]>,
 ) -> Result<usize, Error<Owned>> {
-    LibraryAPI::get_library_types(get_interface(), buffer)
+    LibraryAPI::get_library_types(token.interface(), buffer)
 }
 
 /// Creates a new unlinked library handle.
@@ -203,8 +203,8 @@ pub fn get_library_types<L>(
 ///
 /// The handle must be linked before use.
 #[inline]
-pub unsafe fn create_library_handle<L>(_token: &mut LockToken<L>) -> Library<'static, Owned> {
-    LibraryAPI::create_library_handle(get_mut_interface())
+pub unsafe fn create_library_handle<L>(token: &mut LockToken<L>) -> Library<'static, Owned> {
+    LibraryAPI::create_library_handle(token.interface_mut())
 }
 
 /// Removes an existing library handle.
@@ -222,10 +222,10 @@ pub unsafe fn create_library_handle<L>(_token: &mut LockToken<L>) -> Library<'st
 /// Removing the handle does not unload the library.
 #[inline]
 pub unsafe fn remove_library_handle<L>(
-    _token: &mut LockToken<L>,
+    token: &mut LockToken<L>,
     library: Library<'_, Owned>,
 ) -> Result<(), Error<Owned>> {
-    LibraryAPI::remove_library_handle(get_mut_interface(), library)
+    LibraryAPI::remove_library_handle(token.interface_mut(), library)
 }
 
 /// Links a library handle to an internal library handle.
@@ -246,7 +246,7 @@ pub unsafe fn remove_library_handle<L>(
 /// Incorrect usage can lead to dangling handles or use-after-free errors.
 #[inline]
 pub unsafe fn link_library<'library, 'loader, L, O, LO, IO>(
-    _token: &mut LockToken<L>,
+    token: &mut LockToken<L>,
     library: &Library<'library, O>,
     loader: &Loader<'loader, LO>,
     internal: &InternalLibrary<IO>,
@@ -257,7 +257,7 @@ where
     LO: ImmutableAccessIdentifier,
     IO: ImmutableAccessIdentifier,
 {
-    LibraryAPI::link_library(get_mut_interface(), library, loader, internal)
+    LibraryAPI::link_library(token.interface_mut(), library, loader, internal)
 }
 
 /// Fetches the internal handle linked with the library handle.
@@ -271,13 +271,13 @@ where
 /// Handle on success, error otherwise.
 #[inline]
 pub fn get_internal_library_handle<'library, L, O>(
-    _token: &LockToken<L>,
+    token: &LockToken<L>,
     library: &Library<'library, O>,
 ) -> Result<InternalLibrary<O>, Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
 {
-    LibraryAPI::get_internal_library_handle(get_interface(), library)
+    LibraryAPI::get_internal_library_handle(token.interface(), library)
 }
 
 /// Loads a library. The resulting handle is unique.
@@ -292,14 +292,14 @@ where
 /// Handle on success, error otherwise.
 #[inline]
 pub fn load<L, O>(
-    _token: &mut LockToken<L>,
+    token: &mut LockToken<L>,
     loader: &Loader<'static, O>,
     path: impl AsRef<Path>,
 ) -> Result<Library<'static, Owned>, Error<Owned>>
 where
     O: MutableAccessIdentifier,
 {
-    LibraryAPI::load(get_mut_interface(), loader, path)
+    LibraryAPI::load(token.interface_mut(), loader, path)
 }
 
 /// Unloads a library.
@@ -313,10 +313,10 @@ where
 /// Error on failure.
 #[inline]
 pub fn unload<L>(
-    _token: &mut LockToken<L>,
+    token: &mut LockToken<L>,
     library: Library<'_, Owned>,
 ) -> Result<(), Error<Owned>> {
-    LibraryAPI::unload(get_mut_interface(), library)
+    LibraryAPI::unload(token.interface_mut(), library)
 }
 
 /// Fetches a data symbol from a library.
@@ -335,7 +335,7 @@ pub fn unload<L>(
 /// Symbol on success, error otherwise.
 #[inline]
 pub fn get_data_symbol<'library, 'handle, L, O, U>(
-    _token: &LockToken<L>,
+    token: &LockToken<L>,
     library: &'handle Library<'library, O>,
     symbol: impl AsRef<CStr>,
     caster: impl FnOnce(NonNullConst<c_void>) -> &'library U,
@@ -343,7 +343,7 @@ pub fn get_data_symbol<'library, 'handle, L, O, U>(
 where
     O: ImmutableAccessIdentifier,
 {
-    LibraryAPI::get_data_symbol(get_interface(), library, symbol, caster)
+    LibraryAPI::get_data_symbol(token.interface(), library, symbol, caster)
 }
 
 /// Fetches a function symbol from a library.
@@ -362,7 +362,7 @@ where
 /// Symbol on success, error otherwise.
 #[inline]
 pub fn get_function_symbol<'library, 'handle, L, O, U>(
-    _token: &LockToken<L>,
+    token: &LockToken<L>,
     library: &'handle Library<'library, O>,
     symbol: impl AsRef<CStr>,
     caster: impl FnOnce(CBaseFn) -> U,
@@ -370,5 +370,5 @@ pub fn get_function_symbol<'library, 'handle, L, O, U>(
 where
     O: ImmutableAccessIdentifier,
 {
-    LibraryAPI::get_function_symbol(get_interface(), library, symbol, caster)
+    LibraryAPI::get_function_symbol(token.interface(), library, symbol, caster)
 }
@@ -5,7 +5,8 @@
 //! ```no_run
 //! use emf_core_base_rs::global::{LockToken, Unlock, module, version};
 //! use emf_core_base_rs::module::{DEFAULT_HANDLE, InterfaceDescriptor, InterfaceName};
-//! use emf_core_base_rs::ffi::collections::ConstSpan;
+//! use emf_core_base_rs::ffi::collections::SpanRef;
+//! use std::convert::TryFrom;
 //! use std::path::Path;
 //!
 //! # use emf_core_base_rs::Error;
@@ -15,9 +16,9 @@
 //!
 //! let module_path = Path::new("path to a module");
 //! let interface_desc = InterfaceDescriptor {
-//!     name: InterfaceName::from("my_interface"),
+//!     name: InterfaceName::try_from("my_interface").unwrap(),
 //!     version: version::new_short(1, 0, 0),
-//!     extensions: ConstSpan::new()
+//!     extensions: SpanRef::new()
 //! };
 //!
 //! let mut module = module::add_module(&mut lock, &DEFAULT_HANDLE, module_path)?;
@@ -27,8 +28,7 @@
 //! # Ok(())
 //! # }
 //! ```
-use crate::ffi::library::OSPathChar;
-use crate::global::{get_interface as get_interface_glob, get_mut_interface, LockToken};
+use crate::global::LockToken;
 use crate::module::module_loader::{ModuleLoader, ModuleLoaderABICompat, ModuleLoaderAPI};
 use crate::module::{
     Interface, InterfaceDescriptor, InternalModule, Loader, Module, ModuleAPI, ModuleInfo,
@@ -37,7 +37,7 @@ use crate::module::{
 use crate::ownership::{
     BorrowImmutable, BorrowMutable, ImmutableAccessIdentifier, MutableAccessIdentifier, Owned,
 };
-use crate::Error;
+use crate::{Error, OsPath};
 use std::path::Path;
 use std::pin::Pin;
 
@@ -54,7 +54,7 @@ use std::pin::Pin;
 /// Handle on success, error otherwise.
 #[inline]
 pub fn register_loader<LT, L, T>(
-    _token: &mut LockToken<T>,
+    token: &mut LockToken<T>,
     loader: Pin<&'static LT>,
     mod_type: impl AsRef<str>,
 ) -> Result<Loader<'static, Owned>, Error<Owned>>
@@ -62,7 +62,7 @@ where
     L: ModuleLoaderAPI<'static> + ModuleLoaderABICompat,
     ModuleLoader<L, Owned>: From<&'static LT>,
 {
-    ModuleAPI::register_loader(get_mut_interface(), loader, mod_type)
+    ModuleAPI::register_loader(token.interface_mut(), loader, mod_type)
 }
 
 /// Unregisters an existing module loader.
@@ -78,10 +78,10 @@ where
 /// Error on failure.
 #[inline]
 pub fn unregister_loader<T>(
-    _token: &mut LockToken<T>,
+    token: &mut LockToken<T>,
     loader: Loader<'_, Owned>,
 ) -> Result<(), Error<Owned>> {
-    ModuleAPI::unregister_loader(get_mut_interface(), loader)
+    ModuleAPI::unregister_loader(token.interface_mut(), loader)
 }
 
 /// Fetches the interface of a module loader.
@@ -95,14 +95,14 @@ pub fn unregister_loader<T>(
 /// Interface on success, error otherwise.
 #[inline]
 pub fn get_loader_interface<'loader, O, L, T>(
-    _token: &LockToken<T>,
+    token: &LockToken<T>,
     loader: &Loader<'loader, O>,
 ) -> Result<ModuleLoader<L, O>, Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
     L: ModuleLoaderAPI<'loader> + ModuleLoaderABICompat,
 {
-    ModuleAPI::get_loader_interface(get_interface_glob(), loader)
+    ModuleAPI::get_loader_interface(token.interface(), loader)
 }
 
 /// Fetches the handle of the loader associated with a module type.
@@ -116,10 +116,10 @@ where
 /// Handle on success, error otherwise.
 #[inline]
 pub fn get_loader_handle_from_type<'tok, T>(
-    _token: &'tok LockToken<T>,
+    token: &'tok LockToken<T>,
     mod_type: impl AsRef<str>,
 ) -> Result<Loader<'static, BorrowMutable<'tok>>, Error<Owned>> {
-    ModuleAPI::get_loader_handle_from_type(get_interface_glob(), mod_type)
+    ModuleAPI::get_loader_handle_from_type(token.interface(), mod_type)
 }
 
 /// Fetches the handle of the loader linked with the module handle.
@@ -133,13 +133,13 @@ pub fn get_loader_handle_from_type<'tok, T>(
 /// Handle on success, error otherwise.
 #[inline]
 pub fn get_loader_handle_from_module<'m, 'module, O, T>(
-    _token: &'m LockToken<T>,
+    token: &'m LockToken<T>,
     module: &Module<'module, O>,
 ) -> Result<Loader<'module, BorrowMutable<'m>>, Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
 {
-    ModuleAPI::get_loader_handle_from_module(get_interface_glob(), module)
+    ModuleAPI::get_loader_handle_from_module(token.interface(), module)
 }
 
 /// Fetches the number of loaded modules.
@@ -148,8 +148,8 @@ where
 ///
 /// Number of modules.
 #[inline]
-pub fn get_num_modules<T>(_token: &LockToken<T>) -> usize {
-    ModuleAPI::get_num_modules(get_interface_glob())
+pub fn get_num_modules<T>(token: &LockToken<T>) -> usize {
+    ModuleAPI::get_num_modules(token.interface())
 }
 
 /// Fetches the number of loaders.
@@ -158,8 +158,8 @@ pub fn get_num_modules<T>(_token: &LockToken<T>) -> usize {
 ///
 /// Number of module loaders.
 #[inline]
-pub fn get_num_loaders<T>(_token: &LockToken<T>) -> usize {
-    ModuleAPI::get_num_loaders(get_interface_glob())
+pub fn get_num_loaders<T>(token: &LockToken<T>) -> usize {
+    ModuleAPI::get_num_loaders(token.interface())
 }
 
 /// Fetches the number of exported interfaces.
@@ -168,8 +168,8 @@ pub fn get_num_loaders<T>(_token: &LockToken<T>) -> usize {
 ///
 /// Number of exported interfaces.
 #[inline]
-pub fn get_num_exported_interfaces<T>(_token: &LockToken<T>) -> usize {
-    ModuleAPI::get_num_exported_interfaces(get_interface_glob())
+pub fn get_num_exported_interfaces<T>(token: &LockToken<T>) -> usize {
+    ModuleAPI::get_num_exported_interfaces(token.interface())
 }
 
 /// Checks if a module exists.
@@ -178,11 +178,11 @@ pub fn get_num_exported_interfaces<T>(_token: &LockToken<T>) -> usize {
 ///
 /// [true] if it exists, [false] otherwise.
 #[inline]
-pub fn module_exists<O, T>(_token: &LockToken<T>, module: &Module<'_, O>) -> bool
+pub fn module_exists<O, T>(token: &LockToken<T>, module: &Module<'_, O>) -> bool
 where
     O: ImmutableAccessIdentifier,
 {
-    ModuleAPI::module_exists(get_interface_glob(), module)
+    ModuleAPI::module_exists(token.interface(), module)
 }
 
 /// Checks if a module type exists.
@@ -192,10 +192,10 @@ where
 /// [true] if it exists, [false] otherwise.
 #[inline]
 pub fn type_exists<T>(
-    _token: &LockToken<T>,
+    token: &LockToken<T>,
     mod_type: impl AsRef<str>,
 ) -> Result<bool, Error<Owned>> {
-    ModuleAPI::type_exists(get_interface_glob(), mod_type)
+    ModuleAPI::type_exists(token.interface(), mod_type)
 }
 
 /// Checks whether an exported interface exists.
@@ -205,10 +205,10 @@ pub fn type_exists<T>(
 /// [true] if it exists, [false] otherwise.
 #[inline]
 pub fn exported_interface_exists<T>(
-    _token: &LockToken<T>,
-    interface: &InterfaceDescriptor,
+    token: &LockToken<T>,
+    interface: &InterfaceDescriptor<'_>,
 ) -> bool {
-    ModuleAPI::exported_interface_exists(get_interface_glob(), interface)
+    ModuleAPI::exported_interface_exists(token.interface(), interface)
 }
 
 /// Copies the available module info into a buffer.
@@ -222,10 +222,10 @@ pub fn exported_interface_exists<T>(
 /// Number if written module info on success, error otherwise.
 #[inline]
 pub fn get_modules<T>(
-    _token: &LockToken<T>,
+    token: &LockToken<T>,
     buffer: impl AsMut<[ModuleInfo]>,
 ) -> Result<usize, Error<Owned>> {
-    ModuleAPI::get_modules(get_interface_glob(), buffer)
+    ModuleAPI::get_modules(token.interface(), buffer)
 }
 
 /// Copies the available module types into a buffer.
@@ -239,10 +239,10 @@ pub fn get_modules<T>(
 /// Number if written module types on success, error otherwise.
 #[inline]
 pub fn get_module_types<T>(
-    _token: &LockToken<T>,
+    token: &LockToken<T>,
     buffer: impl AsMut<[ModuleType]>,
 ) -> Result<usize, Error<Owned>> {
-    ModuleAPI::get_module_types(get_interface_glob(), buffer)
+    ModuleAPI::get_module_types(token.interface(), buffer)
 }
 
 /// Copies the descriptors of the exported interfaces into a buffer.
@@ -256,10 +256,10 @@ pub fn get_module_types<T>(
 /// Number if written descriptors on success, error otherwise.
 #[inline]
 pub fn get_exported_interfaces<T>(
-    _token: &LockToken<T>,
-    buffer: impl AsMut<[InterfaceDescriptor]>,
+    token: &LockToken<T>,
+    buffer: impl AsMut<[InterfaceDescriptor<'static>]>,
 ) -> Result<usize, Error<Owned>> {
-    ModuleAPI::get_exported_interfaces(get_interface_glob(), buffer)
+    ModuleAPI::get_exported_interfaces(token.interface(), buffer)
 }
 
 /// Fetches the module handle of the exported interface.
@@ -273,10 +273,10 @@ pub fn get_exported_interfaces<T>(
 /// Module handle on success, error otherwise.
 #[inline]
 pub fn get_exported_interface_handle<'tok, T>(
-    _token: &'tok LockToken<T>,
-    interface: &InterfaceDescriptor,
+    token: &'tok LockToken<T>,
+    interface: &InterfaceDescriptor<'_>,
 ) -> Result<Module<'static, BorrowImmutable<'tok>>, Error<Owned>> {
-    ModuleAPI::get_exported_interface_handle(get_interface_glob(), interface)
+    ModuleAPI::get_exported_interface_handle(token.interface(), interface)
 }
 
 /// Creates a new unlinked module handle.
@@ -289,8 +289,8 @@ pub fn get_exported_interface_handle<'tok, T>(
 ///
 /// The handle remains invalid until it's linked with [link_module].
 #[inline]
-pub unsafe fn create_module_handle<T>(_token: &mut LockToken<T>) -> Module<'static, Owned> {
-    ModuleAPI::create_module_handle(get_mut_interface())
+pub unsafe fn create_module_handle<T>(token: &mut LockToken<T>) -> Module<'static, Owned> {
+    ModuleAPI::create_module_handle(token.interface_mut())
 }
 
 /// Links a module handle to an internal module handle.
@@ -308,10 +308,10 @@ pub unsafe fn create_module_handle<T>(_token: &mut LockToken<T>) -> Module<'stat
 /// Removing the handle does not unload the module.
 #[inline]
 pub unsafe fn remove_module_handle<T>(
-    _token: &mut LockToken<T>,
+    token: &mut LockToken<T>,
     module: Module<'_, Owned>,
 ) -> Result<(), Error<Owned>> {
-    ModuleAPI::remove_module_handle(get_mut_interface(), module)
+    ModuleAPI::remove_module_handle(token.interface_mut(), module)
 }
 
 /// Links a module handle to an internal module handle.
@@ -329,7 +329,7 @@ pub unsafe fn remove_module_handle<T>(
 /// Incorrect usage can lead to dangling handles or use-after-free errors.
 #[inline]
 pub unsafe fn link_module<'module, 'loader, O, LO, IO, T>(
-    _token: &mut LockToken<T>,
+    token: &mut LockToken<T>,
     module: &Module<'module, O>,
     loader: &Loader<'loader, LO>,
     internal: &InternalModule<IO>,
@@ -340,7 +340,7 @@ where
     LO: ImmutableAccessIdentifier,
     IO: ImmutableAccessIdentifier,
 {
-    ModuleAPI::link_module(get_mut_interface(), module, loader, internal)
+    ModuleAPI::link_module(token.interface_mut(), module, loader, internal)
 }
 
 /// Fetches the internal handle linked with the module handle.
@@ -354,13 +354,13 @@ where
 /// Internal handle on success, error otherwise.
 #[inline]
 pub fn get_internal_module_handle<O, T>(
-    _token: &LockToken<T>,
+    token: &LockToken<T>,
     module: &Module<'_, O>,
 ) -> Result<InternalModule<O>, Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
 {
-    ModuleAPI::get_internal_module_handle(get_interface_glob(), module)
+    ModuleAPI::get_internal_module_handle(token.interface(), module)
 }
 
 /// Adds a new module.
@@ -375,14 +375,14 @@ where
 /// Module handle on success, error otherwise.
 #[inline]
 pub fn add_module<O, T>(
-    _token: &mut LockToken<T>,
+    token: &mut LockToken<T>,
     loader: &Loader<'static, O>,
     path: impl AsRef<Path>,
 ) -> Result<Module<'static, Owned>, Error<Owned>>
 where
     O: MutableAccessIdentifier,
 {
-    ModuleAPI::add_module(get_mut_interface(), loader, path)
+    ModuleAPI::add_module(token.interface_mut(), loader, path)
 }
 
 /// Removes a module.
@@ -396,10 +396,10 @@ where
 /// Error on failure.
 #[inline]
 pub fn remove_module<T>(
-    _token: &mut LockToken<T>,
+    token: &mut LockToken<T>,
     module: Module<'_, Owned>,
 ) -> Result<(), Error<Owned>> {
-    ModuleAPI::remove_module(get_mut_interface(), module)
+    ModuleAPI::remove_module(token.interface_mut(), module)
 }
 
 /// Loads a module.
@@ -413,11 +413,11 @@ pub fn remove_module<T>(
 ///
 /// Error on failure.
 #[inline]
-pub fn load<O, T>(_token: &mut LockToken<T>, module: &mut Module<'_, O>) -> Result<(), Error<Owned>>
+pub fn load<O, T>(token: &mut LockToken<T>, module: &mut Module<'_, O>) -> Result<(), Error<Owned>>
 where
     O: MutableAccessIdentifier,
 {
-    ModuleAPI::load(get_mut_interface(), module)
+    ModuleAPI::load(token.interface_mut(), module)
 }
 
 /// Unloads a module.
@@ -431,13 +431,13 @@ where
 /// Error on failure.
 #[inline]
 pub fn unload<O, T>(
-    _token: &mut LockToken<T>,
+    token: &mut LockToken<T>,
     module: &mut Module<'_, O>,
 ) -> Result<(), Error<Owned>>
 where
     O: MutableAccessIdentifier,
 {
-    ModuleAPI::unload(get_mut_interface(), module)
+    ModuleAPI::unload(token.interface_mut(), module)
 }
 
 /// Initializes a module.
@@ -452,13 +452,13 @@ where
 /// Error on failure.
 #[inline]
 pub fn initialize<O, T>(
-    _token: &mut LockToken<T>,
+    token: &mut LockToken<T>,
     module: &mut Module<'_, O>,
 ) -> Result<(), Error<Owned>>
 where
     O: MutableAccessIdentifier,
 {
-    ModuleAPI::initialize(get_mut_interface(), module)
+    ModuleAPI::initialize(token.interface_mut(), module)
 }
 
 /// Terminates a module.
@@ -476,13 +476,13 @@ where
 /// Error on failure.
 #[inline]
 pub fn terminate<O, T>(
-    _token: &mut LockToken<T>,
+    token: &mut LockToken<T>,
     module: &mut Module<'_, O>,
 ) -> Result<(), Error<Owned>>
 where
     O: MutableAccessIdentifier,
 {
-    ModuleAPI::terminate(get_mut_interface(), module)
+    ModuleAPI::terminate(token.interface_mut(), module)
 }
 
 /// Registers a new runtime dependency of the module.
@@ -496,14 +496,14 @@ where
 /// Error on failure.
 #[inline]
 pub fn add_dependency<O, T>(
-    _token: &mut LockToken<T>,
+    token: &mut LockToken<T>,
     module: &mut Module<'_, O>,
-    interface: &InterfaceDescriptor,
+    interface: &InterfaceDescriptor<'_>,
 ) -> Result<(), Error<Owned>>
 where
     O: MutableAccessIdentifier,
 {
-    ModuleAPI::add_dependency(get_mut_interface(), module, interface)
+    ModuleAPI::add_dependency(token.interface_mut(), module, interface)
 }
 
 /// Removes an existing runtime dependency from the module.
@@ -517,14 +517,14 @@ where
 /// Error on failure.
 #[inline]
 pub fn remove_dependency<O, T>(
-    _token: &mut LockToken<T>,
+    token: &mut LockToken<T>,
     module: &mut Module<'_, O>,
-    interface: &InterfaceDescriptor,
+    interface: &InterfaceDescriptor<'_>,
 ) -> Result<(), Error<Owned>>
 where
     O: MutableAccessIdentifier,
 {
-    ModuleAPI::remove_dependency(get_mut_interface(), module, interface)
+    ModuleAPI::remove_dependency(token.interface_mut(), module, interface)
 }
 
 /// Exports an interface of a module.
@@ -539,14 +539,14 @@ where
 /// Error on failure.
 #[inline]
 pub fn export_interface<O, T>(
-    _token: &mut LockToken<T>,
+    token: &mut LockToken<T>,
     module: &Module<'_, O>,
-    interface: &InterfaceDescriptor,
+    interface: &InterfaceDescriptor<'_>,
 ) -> Result<(), Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
 {
-    ModuleAPI::export_interface(get_mut_interface(), module, interface)
+    ModuleAPI::export_interface(token.interface_mut(), module, interface)
 }
 
 /// Fetches the load dependencies of a module.
@@ -560,13 +560,13 @@ where
 /// Load dependencies on success, error otherwise.
 #[inline]
 pub fn get_load_dependencies<'module, O, T>(
-    _token: &LockToken<T>,
+    token: &LockToken<T>,
     module: &Module<'module, O>,
-) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
 {
-    ModuleAPI::get_load_dependencies(get_interface_glob(), module)
+    ModuleAPI::get_load_dependencies(token.interface(), module)
 }
 
 /// Fetches the runtime dependencies of a module.
@@ -580,13 +580,13 @@ where
 /// Runtime dependencies on success, error otherwise.
 #[inline]
 pub fn get_runtime_dependencies<'module, O, T>(
-    _token: &LockToken<T>,
+    token: &LockToken<T>,
     module: &Module<'module, O>,
-) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
 {
-    ModuleAPI::get_runtime_dependencies(get_interface_glob(), module)
+    ModuleAPI::get_runtime_dependencies(token.interface(), module)
 }
 
 /// Fetches the exportable interfaces of a module.
@@ -600,13 +600,13 @@ where
 /// Exportable interfaces on success, error otherwise.
 #[inline]
 pub fn get_exportable_interfaces<'module, O, T>(
-    _token: &LockToken<T>,
+    token: &LockToken<T>,
     module: &Module<'module, O>,
-) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
 {
-    ModuleAPI::get_exportable_interfaces(get_interface_glob(), module)
+    ModuleAPI::get_exportable_interfaces(token.interface(), module)
 }
 
 /// Fetches the load status of a module.
@@ -620,13 +620,13 @@ where
 /// Module status on success, error otherwise.
 #[inline]
 pub fn fetch_status<O, T>(
-    _token: &LockToken<T>,
+    token: &LockToken<T>,
     module: &Module<'_, O>,
 ) -> Result<ModuleStatus, Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
 {
-    ModuleAPI::fetch_status(get_interface_glob(), module)
+    ModuleAPI::fetch_status(token.interface(), module)
 }
 
 /// Fetches the path a module was loaded from.
@@ -640,13 +640,13 @@ where
 /// Module path on success, error otherwise.
 #[inline]
 pub fn get_module_path<'module, O, T>(
-    _token: &LockToken<T>,
+    token: &LockToken<T>,
     module: &Module<'module, O>,
-) -> Result<&'module [OSPathChar], Error<Owned>>
+) -> Result<OsPath<'module>, Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
 {
-    ModuleAPI::get_module_path(get_interface_glob(), module)
+    ModuleAPI::get_module_path(token.interface(), module)
 }
 
 /// Fetches the module info from a module.
@@ -660,13 +660,13 @@ where
 /// Module info on success, error otherwise.
 #[inline]
 pub fn get_module_info<'module, O, T>(
-    _token: &LockToken<T>,
+    token: &LockToken<T>,
     module: &Module<'module, O>,
 ) -> Result<&'module ModuleInfo, Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
 {
-    ModuleAPI::get_module_info(get_interface_glob(), module)
+    ModuleAPI::get_module_info(token.interface(), module)
 }
 
 /// Fetches an interface from a module.
@@ -681,13 +681,13 @@ where
 /// Interface on success, error otherwise.
 #[inline]
 pub fn get_interface<'module, O, L, T>(
-    _token: &LockToken<L>,
+    token: &LockToken<L>,
     module: &'module Module<'_, O>,
-    interface: &InterfaceDescriptor,
+    interface: &InterfaceDescriptor<'_>,
     caster: impl FnOnce(crate::ffi::module::Interface) -> T,
 ) -> Result<Interface<'module, T>, Error<Owned>>
 where
     O: ImmutableAccessIdentifier,
 {
-    ModuleAPI::get_interface(get_interface_glob(), module, interface, caster)
+    ModuleAPI::get_interface(token.interface(), module, interface, caster)
 }
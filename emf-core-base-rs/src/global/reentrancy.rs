@@ -0,0 +1,109 @@
+//! Detection of reentrant locking in debug builds.
+//!
+//! Keeps track of the interfaces locked by a [LockToken](super::LockToken)
+//! held by the current thread. In release builds all functions are no-ops.
+use crate::CBaseRef;
+
+#[cfg(debug_assertions)]
+thread_local!(static HELD_LOCKS: std::cell::RefCell<Vec<usize>> = const { std::cell::RefCell::new(Vec::new()) });
+
+#[cfg(debug_assertions)]
+#[inline]
+fn key(interface: &CBaseRef<'_>) -> usize {
+    crate::wake_queue::queue_id(interface)
+}
+
+/// Checks whether the current thread holds the lock of the interface.
+#[inline]
+#[allow(unused_variables)]
+pub fn is_held(interface: &CBaseRef<'_>) -> bool {
+    #[cfg(debug_assertions)]
+    return HELD_LOCKS.with(|locks| locks.borrow().contains(&key(interface)));
+
+    #[cfg(not(debug_assertions))]
+    false
+}
+
+/// Records the acquisition of the interface lock.
+#[inline]
+#[allow(unused_variables)]
+pub fn acquire(interface: &CBaseRef<'_>) {
+    #[cfg(debug_assertions)]
+    HELD_LOCKS.with(|locks| locks.borrow_mut().push(key(interface)));
+}
+
+/// Records the release of the interface lock.
+#[inline]
+#[allow(unused_variables)]
+pub fn release(interface: &CBaseRef<'_>) {
+    #[cfg(debug_assertions)]
+    HELD_LOCKS.with(|locks| {
+        let mut locks = locks.borrow_mut();
+        if let Some(pos) = locks.iter().rposition(|&k| k == key(interface)) {
+            locks.swap_remove(pos);
+        }
+    });
+}
+
+/// Reports a reentrant lock of a non-reentrant sync handler.
+#[cold]
+#[inline(never)]
+pub fn deadlock() -> ! {
+    panic!(
+        "Deadlock detected: the current thread already holds the interface lock \
+        and the active sync handler is not reentrant. Reuse the existing `LockToken` \
+        or install a reentrant sync handler."
+    )
+}
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+    use crate::ffi::record::{Recording, ReplayInterface};
+    use crate::ffi::{Bool, CBase, CBaseInterface, TypeWrapper};
+    use crate::global::{GlobalContext, LockToken, Unlock};
+    use crate::CBaseRef;
+    use std::ptr::NonNull;
+
+    unsafe extern "C-unwind" fn try_lock_fails(_base_module: Option<NonNull<CBase>>) -> Bool {
+        Bool::False
+    }
+
+    /// Runs `f` on an interface whose sync handler is reentrant if `reentrant` is set.
+    fn with_interface(reentrant: bool, f: impl FnOnce()) {
+        let replay = ReplayInterface::new(Recording::new());
+        let interface = replay.interface();
+        let mut vtable = unsafe { *interface.vtable.as_ref() };
+        if !reentrant {
+            vtable.sys_try_lock_fn = TypeWrapper(try_lock_fails);
+        }
+
+        let interface = CBaseInterface {
+            base_module: interface.base_module,
+            vtable: (&vtable).into(),
+        };
+        let context = GlobalContext::from_interface(unsafe { CBaseRef::new(interface) });
+        context.enter(f);
+    }
+
+    #[test]
+    fn reentrant_lock() {
+        with_interface(true, || {
+            let outer = LockToken::<Unlock>::lock();
+            let inner = LockToken::<Unlock>::lock();
+            drop(inner);
+            drop(outer);
+
+            // The lock is no longer held by the thread.
+            drop(LockToken::<Unlock>::lock());
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Deadlock detected")]
+    fn deadlock() {
+        with_interface(false, || {
+            let _outer = LockToken::<Unlock>::lock();
+            let _inner = LockToken::<Unlock>::lock();
+        });
+    }
+}
@@ -1,7 +1,8 @@
 //! Global sys api.
 use crate::fn_caster::FnCaster;
-use crate::global::{get_interface, get_mut_interface, LockToken};
+use crate::global::{get_interface, LockToken};
 use crate::ownership::Owned;
+use crate::sys::capabilities::Capabilities;
 use crate::sys::sync_handler::SyncHandlerAPI;
 use crate::sys::{SysAPI, SysAPIMin};
 use crate::Error;
@@ -14,8 +15,8 @@ pub fn panic(error: Option<Error<Owned>>) -> ! {
 
 /// Sends a termination signal.
 #[inline]
-pub fn shutdown<L>(_token: &mut LockToken<L>) -> ! {
-    SysAPI::shutdown(get_mut_interface())
+pub fn shutdown<L>(token: &mut LockToken<L>) -> ! {
+    SysAPI::shutdown(token.interface_mut())
 }
 
 /// Checks if a function is implemented.
@@ -31,6 +32,16 @@ where
     SysAPIMin::has_function::<U>(get_interface())
 }
 
+/// Probes the interface for all implemented functions.
+///
+/// # Return
+///
+/// Set of the implemented functions.
+#[inline]
+pub fn capabilities() -> Capabilities {
+    Capabilities::probe(get_interface())
+}
+
 /// Fetches a function from the interface.
 ///
 /// # Return
@@ -50,11 +61,11 @@ where
 ///
 /// The active synchronization handler.
 #[inline]
-pub fn get_sync_handler<L, U>(_token: &LockToken<L>) -> <U as SyncHandlerAPI<'static>>::Handler
+pub fn get_sync_handler<L, U>(token: &LockToken<L>) -> <U as SyncHandlerAPI<'static>>::Handler
 where
     U: SyncHandlerAPI<'static>,
 {
-    SysAPI::get_sync_handler::<U>(get_interface())
+    SysAPI::get_sync_handler::<U>(token.interface())
 }
 
 /// Sets a new synchronization handler.
@@ -80,8 +91,8 @@ where
 /// if they depend on a specific synchronization handler.
 #[inline]
 pub unsafe fn set_sync_handler<L>(
-    _token: &mut LockToken<L>,
+    token: &mut LockToken<L>,
     handler: Option<&impl SyncHandlerAPI<'static>>,
 ) {
-    SysAPI::set_sync_handler(get_mut_interface(), handler)
+    SysAPI::set_sync_handler(token.interface_mut(), handler)
 }
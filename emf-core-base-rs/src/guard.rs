@@ -0,0 +1,24 @@
+//! Guard used by the functions implemented in Rust and called through the interface.
+use std::panic::UnwindSafe;
+
+/// Prevents panics from unwinding out of `f` into foreign code.
+///
+/// Uses [ffi_guard](crate::extensions::unwind_internal::ffi_guard), if the
+/// `unwind_internal` extension is enabled, and aborts the process on a panic otherwise.
+#[inline]
+pub(crate) fn ffi_guard<R>(f: impl FnOnce() -> R + UnwindSafe) -> R {
+    #[cfg(feature = "unwind_internal")]
+    {
+        crate::extensions::unwind_internal::ffi_guard(f)
+    }
+    #[cfg(not(feature = "unwind_internal"))]
+    {
+        match std::panic::catch_unwind(f) {
+            Ok(value) => value,
+            Err(_) => {
+                eprintln!("A panic attempted to unwind across the ffi boundary, aborting");
+                std::process::abort()
+            }
+        }
+    }
+}
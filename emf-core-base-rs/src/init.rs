@@ -1,7 +1,8 @@
 use crate::ffi::{
     sys::api::GetFunctionFn as GetFunctionFnFFI, CBase as CBaseFFI, CBaseInterface, CBaseLoader,
 };
-use crate::{CBase, CBaseAccess, CBaseRef};
+use crate::ownership::Owned;
+use crate::{CBase, CBaseAccess, CBaseRef, Error};
 use std::ptr::NonNull;
 
 /// Trait for loading the interface.
@@ -9,6 +10,28 @@ pub trait CBaseAPILoader<'interface> {
     /// Type of the interface.
     type Interface: CBaseAccess<'interface>;
 
+    /// Fetches the `emf-core-base` interface.
+    ///
+    /// Accepts every host interface that is compatible with the targeted version.
+    /// Use [SysAPIMin::has_function](crate::sys::api::SysAPIMin::has_function) to check
+    /// for functions that are newer than the version of the host.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the host does not export a compatible interface.
+    ///
+    /// # Return
+    ///
+    /// Interface on success, error otherwise.
+    ///
+    /// # Safety
+    ///
+    /// The parameter `get_function_fn` must be able to accept `base_module`.
+    unsafe fn try_fetch_interface(
+        base_module: Option<NonNull<CBaseFFI>>,
+        get_function_fn: GetFunctionFnFFI,
+    ) -> Result<Self::Interface, Error<Owned>>;
+
     /// Fetches the `emf-core-base` interface.
     ///
     /// # Safety
@@ -27,6 +50,16 @@ pub trait CBaseAPILoader<'interface> {
 impl<'interface> CBaseAPILoader<'interface> for CBase<'interface> {
     type Interface = Self;
 
+    unsafe fn try_fetch_interface(
+        base_module: Option<NonNull<CBaseFFI>>,
+        get_function_fn: GetFunctionFnFFI,
+    ) -> Result<Self::Interface, Error<Owned>> {
+        CBaseInterface::try_fetch_interface(base_module, get_function_fn)
+            .into_rust()
+            .map(|interface| Self::new(CBaseRef::new(interface)))
+            .map_err(Error::from)
+    }
+
     unsafe fn fetch_interface(
         base_module: Option<NonNull<CBaseFFI>>,
         get_function_fn: GetFunctionFnFFI,
@@ -15,10 +15,16 @@
 #![allow(incomplete_features)]
 pub use emf_core_base_rs_ffi as ffi;
 
+#[macro_use]
+mod trace;
+
 mod cbase;
 mod error;
 mod fat_ptr;
+mod guard;
+mod os_path;
 mod to_os_path_buff;
+mod wake_queue;
 
 #[cfg(feature = "init")]
 mod init;
@@ -36,8 +42,11 @@ pub mod global;
 #[cfg(feature = "extensions")]
 pub mod extensions;
 
-pub use cbase::{CBase, CBaseAPI, CBaseAccess, CBaseInterfaceInfo, CBaseRef};
+pub use cbase::{
+    CBase, CBaseAPI, CBaseAccess, CBaseGuard, CBaseInterfaceInfo, CBaseLockFuture, CBaseRef,
+};
 pub use error::{Error, ErrorInfo};
 pub use fat_ptr::FatPtr;
 pub use init::CBaseAPILoader;
+pub use os_path::OsPath;
 pub use to_os_path_buff::ToOsPathBuff;
@@ -1,15 +1,14 @@
-use crate::ffi::collections::{MutSpan, NonNullConst};
+use crate::ffi::collections::{NonNullConst, SpanMut};
 use crate::ffi::errors::SimpleError;
 use crate::ffi::library::api::LibraryBinding;
 use crate::ffi::library::{OSPathString, SymbolName};
 use crate::ffi::{Bool, CBaseFn};
 use crate::library::library_loader::{LibraryLoader, LibraryLoaderABICompat, LibraryLoaderAPI};
-use crate::library::{
-    InternalLibrary, Library, LibraryType, Loader, Symbol, LOADER_TYPE_MAX_LENGTH,
-};
+use crate::library::{InternalLibrary, Library, LibraryType, Loader, Symbol};
 use crate::ownership::{BorrowMutable, ImmutableAccessIdentifier, MutableAccessIdentifier, Owned};
 use crate::Error;
 use crate::ToOsPathBuff;
+use std::convert::TryFrom;
 use std::ffi::{c_void, CStr};
 use std::path::Path;
 use std::pin::Pin;
@@ -280,7 +279,7 @@ pub trait LibraryAPI<'interface> {
 
 impl<'interface, T> LibraryAPI<'interface> for T
 where
-    T: LibraryBinding,
+    T: LibraryBinding + 'interface,
 {
     #[inline]
     fn register_loader<LT, L>(
@@ -293,14 +292,12 @@ where
         LibraryLoader<L, Owned>: From<&'interface LT>,
     {
         let lib_str = lib_type.as_ref();
-        if lib_str.as_bytes().len() > LOADER_TYPE_MAX_LENGTH {
-            return Err(Error::from(SimpleError::new(format!(
+        let lib_type = LibraryType::try_from(lib_str).map_err(|_| {
+            Error::from(SimpleError::new(format!(
                 "Loader type too long: {}",
                 lib_str
-            ))));
-        }
-
-        let lib_type = LibraryType::from(lib_str);
+            )))
+        })?;
 
         unsafe {
             self.register_loader(
@@ -343,14 +340,12 @@ where
         lib_type: impl AsRef<str>,
     ) -> Result<Loader<'interface, BorrowMutable<'_>>, Error<Owned>> {
         let lib_str = lib_type.as_ref();
-        if lib_str.as_bytes().len() > LOADER_TYPE_MAX_LENGTH {
-            return Err(Error::from(SimpleError::new(format!(
+        let lib_type = LibraryType::try_from(lib_str).map_err(|_| {
+            Error::from(SimpleError::new(format!(
                 "Loader type too long: {}",
                 lib_str
-            ))));
-        }
-
-        let lib_type = LibraryType::from(lib_str);
+            )))
+        })?;
 
         unsafe {
             self.get_loader_handle_from_type(NonNullConst::from(&lib_type))
@@ -390,14 +385,12 @@ where
     #[inline]
     fn type_exists(&self, lib_type: impl AsRef<str>) -> Result<bool, Error<Owned>> {
         let lib_str = lib_type.as_ref();
-        if lib_str.as_bytes().len() > LOADER_TYPE_MAX_LENGTH {
-            return Err(Error::from(SimpleError::new(format!(
+        let lib_type = LibraryType::try_from(lib_str).map_err(|_| {
+            Error::from(SimpleError::new(format!(
                 "Loader type too long: {}",
                 lib_str
-            ))));
-        }
-
-        let lib_type = LibraryType::from(lib_str);
+            )))
+        })?;
 
         unsafe { Ok(self.type_exists(NonNullConst::from(&lib_type)) == Bool::True) }
     }
@@ -408,7 +401,7 @@ where
         mut buffer: impl AsMut<[LibraryType]>,
     ) -> Result<usize, Error<Owned>> {
         unsafe {
-            self.get_library_types(MutSpan::from(buffer.as_mut()))
+            self.get_library_types(SpanMut::from(buffer.as_mut()))
                 .into_rust()
                 .map_err(Error::from)
         }
@@ -538,3 +531,45 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::LibraryAPI;
+    use crate::ffi::library::LibraryHandle;
+    use crate::ffi::record::{Call, Recording, ReplayInterface, Value};
+    use crate::ffi::FnId;
+    use crate::library::Library;
+    use crate::ownership::Owned;
+    use std::ffi::CString;
+
+    #[test]
+    fn long_symbol_names() {
+        static DATA: u8 = 0;
+
+        // Symbol names are borrowed, and must reach the host without being shortened.
+        let symbol = CString::new("symbol_".repeat(20)).unwrap();
+        let name = Value::Bytes(symbol.as_bytes_with_nul().to_vec());
+        let replay = ReplayInterface::new(Recording::from(vec![
+            Call {
+                id: FnId::LibraryGetDataSymbol,
+                args: vec![Value::Int(1), name.clone()],
+                result: Value::Ptr(1),
+            },
+            Call {
+                id: FnId::LibraryGetFunctionSymbol,
+                args: vec![Value::Int(1), name],
+                result: Value::Ptr(1),
+            },
+        ]));
+
+        let interface = replay.interface();
+        let library = unsafe { Library::<Owned>::new(LibraryHandle { id: 1 }) };
+        assert!(interface
+            .get_data_symbol(&library, &symbol, |_| &DATA)
+            .is_ok());
+        assert!(interface
+            .get_function_symbol(&library, &symbol, |f| f)
+            .is_ok());
+        assert_eq!(replay.finish(), Ok(()));
+    }
+}
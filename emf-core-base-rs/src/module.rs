@@ -10,16 +10,17 @@
 //! use emf_core_base_rs::module::{
 //!     ModuleAPI, DEFAULT_HANDLE, InterfaceDescriptor, InterfaceName, Module
 //! };
-//! use emf_core_base_rs::ffi::collections::ConstSpan;
+//! use emf_core_base_rs::ffi::collections::SpanRef;
 //! use emf_core_base_rs::Error;
+//! use std::convert::TryFrom;
 //! use std::path::Path;
 //!
 //! let result = CBaseAccess::lock(base_interface, |interface| -> Result<Module<'_, _>, Error<_>> {
 //!     let module_path = Path::new("path to a module");
 //!     let interface_desc = InterfaceDescriptor {
-//!         name: InterfaceName::from("my_interface"),
+//!         name: InterfaceName::try_from("my_interface").unwrap(),
 //!         version: VersionAPI::new_short(interface, 1, 0, 0),
-//!         extensions: ConstSpan::new()
+//!         extensions: SpanRef::new()
 //!     };
 //!
 //!     let mut module = ModuleAPI::add_module(interface, &DEFAULT_HANDLE, &module_path)?;
@@ -40,6 +41,9 @@ mod api;
 pub mod module_loader;
 pub mod native_module;
 
+#[cfg(feature = "init")]
+pub mod policy;
+
 pub use crate::ffi::module::InterfaceDescriptor;
 pub use crate::ffi::module::InterfaceExtension;
 pub use crate::ffi::module::InterfaceName;
@@ -1,18 +1,19 @@
-use crate::ffi::collections::{MutSpan, NonNullConst};
+use crate::ffi::collections::{NonNullConst, SpanMut};
 use crate::ffi::errors::SimpleError;
-use crate::ffi::library::{OSPathChar, OSPathString};
+use crate::ffi::library::OSPathString;
 use crate::ffi::module::api::ModuleBinding;
 use crate::ffi::Bool;
 use crate::module::module_loader::{ModuleLoader, ModuleLoaderABICompat, ModuleLoaderAPI};
 use crate::module::{
     Interface, InterfaceDescriptor, InternalModule, Loader, Module, ModuleInfo, ModuleStatus,
-    ModuleType, MODULE_LOADER_TYPE_MAX_LENGTH,
+    ModuleType,
 };
 use crate::ownership::{
     BorrowImmutable, BorrowMutable, ImmutableAccessIdentifier, MutableAccessIdentifier, Owned,
 };
-use crate::Error;
 use crate::ToOsPathBuff;
+use crate::{Error, OsPath};
+use std::convert::TryFrom;
 use std::path::Path;
 use std::pin::Pin;
 
@@ -142,7 +143,7 @@ pub trait ModuleAPI<'interface> {
     /// # Return
     ///
     /// [true] if it exists, [false] otherwise.
-    fn exported_interface_exists(&self, interface: &InterfaceDescriptor) -> bool;
+    fn exported_interface_exists(&self, interface: &InterfaceDescriptor<'_>) -> bool;
 
     /// Copies the available module info into a buffer.
     ///
@@ -177,7 +178,7 @@ pub trait ModuleAPI<'interface> {
     /// Number if written descriptors on success, error otherwise.
     fn get_exported_interfaces(
         &self,
-        buffer: impl AsMut<[InterfaceDescriptor]>,
+        buffer: impl AsMut<[InterfaceDescriptor<'interface>]>,
     ) -> Result<usize, Error<Owned>>;
 
     /// Fetches the module handle of the exported interface.
@@ -191,7 +192,7 @@ pub trait ModuleAPI<'interface> {
     /// Module handle on success, error otherwise.
     fn get_exported_interface_handle(
         &self,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
     ) -> Result<Module<'interface, BorrowImmutable<'_>>, Error<Owned>>;
 
     /// Creates a new unlinked module handle.
@@ -363,7 +364,7 @@ pub trait ModuleAPI<'interface> {
     fn add_dependency<O>(
         &mut self,
         module: &mut Module<'_, O>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
     ) -> Result<(), Error<Owned>>
     where
         O: MutableAccessIdentifier;
@@ -380,7 +381,7 @@ pub trait ModuleAPI<'interface> {
     fn remove_dependency<O>(
         &mut self,
         module: &mut Module<'_, O>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
     ) -> Result<(), Error<Owned>>
     where
         O: MutableAccessIdentifier;
@@ -398,7 +399,7 @@ pub trait ModuleAPI<'interface> {
     fn export_interface<O>(
         &mut self,
         module: &Module<'_, O>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
     ) -> Result<(), Error<Owned>>
     where
         O: ImmutableAccessIdentifier;
@@ -415,7 +416,7 @@ pub trait ModuleAPI<'interface> {
     fn get_load_dependencies<'module, O>(
         &self,
         module: &Module<'module, O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier;
 
@@ -431,7 +432,7 @@ pub trait ModuleAPI<'interface> {
     fn get_runtime_dependencies<'module, O>(
         &self,
         module: &Module<'module, O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier;
 
@@ -447,7 +448,7 @@ pub trait ModuleAPI<'interface> {
     fn get_exportable_interfaces<'module, O>(
         &self,
         module: &Module<'module, O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier;
 
@@ -476,7 +477,7 @@ pub trait ModuleAPI<'interface> {
     fn get_module_path<'module, O>(
         &self,
         module: &Module<'module, O>,
-    ) -> Result<&'module [OSPathChar], Error<Owned>>
+    ) -> Result<OsPath<'module>, Error<Owned>>
     where
         O: ImmutableAccessIdentifier;
 
@@ -509,7 +510,7 @@ pub trait ModuleAPI<'interface> {
     fn get_interface<'module, O, T>(
         &self,
         module: &'module Module<'_, O>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
         caster: impl FnOnce(crate::ffi::module::Interface) -> T,
     ) -> Result<Interface<'module, T>, Error<Owned>>
     where
@@ -518,7 +519,7 @@ pub trait ModuleAPI<'interface> {
 
 impl<'interface, T> ModuleAPI<'interface> for T
 where
-    T: ModuleBinding,
+    T: ModuleBinding + 'interface,
 {
     #[inline]
     fn register_loader<LT, L>(
@@ -531,14 +532,12 @@ where
         ModuleLoader<L, Owned>: From<&'interface LT>,
     {
         let mod_str = mod_type.as_ref();
-        if mod_str.as_bytes().len() > MODULE_LOADER_TYPE_MAX_LENGTH {
-            return Err(Error::from(SimpleError::new(format!(
+        let mod_type = ModuleType::try_from(mod_str).map_err(|_| {
+            Error::from(SimpleError::new(format!(
                 "{}: {}",
                 MODULE_TYPE_LENGTH_ERROR, mod_str
-            ))));
-        }
-
-        let mod_type = ModuleType::from(mod_str);
+            )))
+        })?;
 
         unsafe {
             self.register_loader(
@@ -581,14 +580,12 @@ where
         mod_type: impl AsRef<str>,
     ) -> Result<Loader<'interface, BorrowMutable<'_>>, Error<Owned>> {
         let mod_str = mod_type.as_ref();
-        if mod_str.as_bytes().len() > MODULE_LOADER_TYPE_MAX_LENGTH {
-            return Err(Error::from(SimpleError::new(format!(
+        let mod_type = ModuleType::try_from(mod_str).map_err(|_| {
+            Error::from(SimpleError::new(format!(
                 "{}: {}",
                 MODULE_TYPE_LENGTH_ERROR, mod_str
-            ))));
-        }
-
-        let mod_type = ModuleType::from(mod_str);
+            )))
+        })?;
 
         unsafe {
             self.get_loader_handle_from_type(NonNullConst::from(&mod_type))
@@ -638,27 +635,25 @@ where
     #[inline]
     fn type_exists(&self, mod_type: impl AsRef<str>) -> Result<bool, Error<Owned>> {
         let mod_str = mod_type.as_ref();
-        if mod_str.as_bytes().len() > MODULE_LOADER_TYPE_MAX_LENGTH {
-            return Err(Error::from(SimpleError::new(format!(
+        let mod_type = ModuleType::try_from(mod_str).map_err(|_| {
+            Error::from(SimpleError::new(format!(
                 "{}: {}",
                 MODULE_TYPE_LENGTH_ERROR, mod_str
-            ))));
-        }
-
-        let mod_type = ModuleType::from(mod_str);
+            )))
+        })?;
 
         unsafe { Ok(self.type_exists(NonNullConst::from(&mod_type)) == Bool::True) }
     }
 
     #[inline]
-    fn exported_interface_exists(&self, interface: &InterfaceDescriptor) -> bool {
+    fn exported_interface_exists(&self, interface: &InterfaceDescriptor<'_>) -> bool {
         unsafe { self.exported_interface_exists(NonNullConst::from(interface)) == Bool::True }
     }
 
     #[inline]
     fn get_modules(&self, mut buffer: impl AsMut<[ModuleInfo]>) -> Result<usize, Error<Owned>> {
         unsafe {
-            self.get_modules(MutSpan::from(buffer.as_mut()))
+            self.get_modules(SpanMut::from(buffer.as_mut()))
                 .into_rust()
                 .map_err(Error::from)
         }
@@ -670,7 +665,7 @@ where
         mut buffer: impl AsMut<[ModuleType]>,
     ) -> Result<usize, Error<Owned>> {
         unsafe {
-            self.get_module_types(MutSpan::from(buffer.as_mut()))
+            self.get_module_types(SpanMut::from(buffer.as_mut()))
                 .into_rust()
                 .map_err(Error::from)
         }
@@ -679,10 +674,13 @@ where
     #[inline]
     fn get_exported_interfaces(
         &self,
-        mut buffer: impl AsMut<[InterfaceDescriptor]>,
+        mut buffer: impl AsMut<[InterfaceDescriptor<'interface>]>,
     ) -> Result<usize, Error<Owned>> {
+        let buffer = buffer.as_mut();
         unsafe {
-            self.get_exported_interfaces(MutSpan::from(buffer.as_mut()))
+            // SAFETY: The written descriptors are borrowed from the interface.
+            let buffer = SpanMut::from_raw_parts_mut(buffer.as_mut_ptr().cast(), buffer.len());
+            self.get_exported_interfaces(buffer)
                 .into_rust()
                 .map_err(Error::from)
         }
@@ -691,7 +689,7 @@ where
     #[inline]
     fn get_exported_interface_handle(
         &self,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
     ) -> Result<Module<'interface, BorrowImmutable<'_>>, Error<Owned>> {
         unsafe {
             self.get_exported_interface_handle(NonNullConst::from(interface))
@@ -826,7 +824,7 @@ where
     fn add_dependency<O>(
         &mut self,
         module: &mut Module<'_, O>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
     ) -> Result<(), Error<Owned>>
     where
         O: MutableAccessIdentifier,
@@ -842,7 +840,7 @@ where
     fn remove_dependency<O>(
         &mut self,
         module: &mut Module<'_, O>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
     ) -> Result<(), Error<Owned>>
     where
         O: MutableAccessIdentifier,
@@ -858,7 +856,7 @@ where
     fn export_interface<O>(
         &mut self,
         module: &Module<'_, O>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
     ) -> Result<(), Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
@@ -874,7 +872,7 @@ where
     fn get_load_dependencies<'module, O>(
         &self,
         module: &Module<'module, O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -898,7 +896,7 @@ where
     fn get_runtime_dependencies<'module, O>(
         &self,
         module: &Module<'module, O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -922,7 +920,7 @@ where
     fn get_exportable_interfaces<'module, O>(
         &self,
         module: &Module<'module, O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -958,7 +956,7 @@ where
     fn get_module_path<'module, O>(
         &self,
         module: &Module<'module, O>,
-    ) -> Result<&'module [OSPathChar], Error<Owned>>
+    ) -> Result<OsPath<'module>, Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -967,10 +965,7 @@ where
                 .into_rust()
                 .map_or_else(
                     |e| Err(Error::from(e)),
-                    |v| {
-                        let slice = v.as_ref();
-                        Ok(std::slice::from_raw_parts(slice.as_ptr(), slice.len()))
-                    },
+                    |v| Ok(OsPath::from_os_path_string(v)),
                 )
         }
     }
@@ -994,7 +989,7 @@ where
     fn get_interface<'module, O, IT>(
         &self,
         module: &'module Module<'_, O>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
         caster: impl FnOnce(crate::ffi::module::Interface) -> IT,
     ) -> Result<Interface<'module, IT>, Error<Owned>>
     where
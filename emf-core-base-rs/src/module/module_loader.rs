@@ -1,7 +1,7 @@
 //! API of a module loader.
 use crate::fat_ptr::FatPtr;
 use crate::ffi::collections::NonNullConst;
-use crate::ffi::library::{OSPathChar, OSPathString};
+use crate::ffi::library::OSPathString;
 use crate::ffi::module::module_loader::{
     ModuleLoader as ModuleLoaderFFI, ModuleLoaderBinding, ModuleLoaderInterface,
     NativeModuleLoaderBinding, NativeModuleLoaderInterface,
@@ -11,8 +11,8 @@ use crate::module::{Interface, InterfaceDescriptor, InternalModule, ModuleInfo,
 use crate::ownership::{
     AccessIdentifier, ImmutableAccessIdentifier, MutableAccessIdentifier, Owned,
 };
-use crate::Error;
 use crate::ToOsPathBuff;
+use crate::{Error, OsPath};
 use std::ffi::c_void;
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
@@ -201,7 +201,7 @@ pub trait ModuleLoaderAPI<'a> {
     unsafe fn get_interface<'module, O, T>(
         &self,
         module: &'module InternalModule<O>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
         caster: impl FnOnce(crate::ffi::module::Interface) -> T,
     ) -> Result<Interface<'module, T>, Error<Owned>>
     where
@@ -249,7 +249,7 @@ pub trait ModuleLoaderAPI<'a> {
     unsafe fn get_module_path<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [OSPathChar], Error<Owned>>
+    ) -> Result<OsPath<'module>, Error<Owned>>
     where
         O: ImmutableAccessIdentifier;
 
@@ -271,7 +271,7 @@ pub trait ModuleLoaderAPI<'a> {
     unsafe fn get_load_dependencies<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier;
 
@@ -293,7 +293,7 @@ pub trait ModuleLoaderAPI<'a> {
     unsafe fn get_runtime_dependencies<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier;
 
@@ -315,7 +315,7 @@ pub trait ModuleLoaderAPI<'a> {
     unsafe fn get_exportable_interfaces<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier;
 
@@ -610,7 +610,7 @@ where
     pub unsafe fn get_interface<'module, MO, IT>(
         &self,
         module: &'module InternalModule<MO>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
         caster: impl FnOnce(crate::ffi::module::Interface) -> IT,
     ) -> Result<Interface<'module, IT>, Error<Owned>>
     where
@@ -666,7 +666,7 @@ where
     pub unsafe fn get_module_path<'module, MO>(
         &self,
         module: &'module InternalModule<MO>,
-    ) -> Result<&'module [OSPathChar], Error<Owned>>
+    ) -> Result<OsPath<'module>, Error<Owned>>
     where
         MO: ImmutableAccessIdentifier,
     {
@@ -692,7 +692,7 @@ where
     pub unsafe fn get_load_dependencies<'module, MO>(
         &self,
         module: &'module InternalModule<MO>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         MO: ImmutableAccessIdentifier,
     {
@@ -718,7 +718,7 @@ where
     pub unsafe fn get_runtime_dependencies<'module, MO>(
         &self,
         module: &'module InternalModule<MO>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         MO: ImmutableAccessIdentifier,
     {
@@ -744,7 +744,7 @@ where
     pub unsafe fn get_exportable_interfaces<'module, MO>(
         &self,
         module: &'module InternalModule<MO>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         MO: ImmutableAccessIdentifier,
     {
@@ -949,7 +949,7 @@ impl<'a> ModuleLoaderAPI<'a> for UnknownLoader<'a> {
     unsafe fn get_interface<'module, O, T>(
         &self,
         module: &'module InternalModule<O>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
         caster: impl FnOnce(crate::ffi::module::Interface) -> T,
     ) -> Result<Interface<'module, T>, Error<Owned>>
     where
@@ -979,7 +979,7 @@ impl<'a> ModuleLoaderAPI<'a> for UnknownLoader<'a> {
     unsafe fn get_module_path<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [OSPathChar], Error<Owned>>
+    ) -> Result<OsPath<'module>, Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -988,10 +988,7 @@ impl<'a> ModuleLoaderAPI<'a> for UnknownLoader<'a> {
             .into_rust()
             .map_or_else(
                 |e| Err(Error::from(e)),
-                |v| {
-                    let slice = v.as_ref();
-                    Ok(std::slice::from_raw_parts(slice.as_ptr(), slice.len()))
-                },
+                |v| Ok(OsPath::from_os_path_string(v)),
             )
     }
 
@@ -999,7 +996,7 @@ impl<'a> ModuleLoaderAPI<'a> for UnknownLoader<'a> {
     unsafe fn get_load_dependencies<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -1019,7 +1016,7 @@ impl<'a> ModuleLoaderAPI<'a> for UnknownLoader<'a> {
     unsafe fn get_runtime_dependencies<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -1039,7 +1036,7 @@ impl<'a> ModuleLoaderAPI<'a> for UnknownLoader<'a> {
     unsafe fn get_exportable_interfaces<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -1176,7 +1173,7 @@ impl<'a> ModuleLoaderAPI<'a> for NativeLoader<'a> {
     unsafe fn get_interface<'module, O, T>(
         &self,
         module: &'module InternalModule<O>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
         caster: impl FnOnce(crate::ffi::module::Interface) -> T,
     ) -> Result<Interface<'module, T>, Error<Owned>>
     where
@@ -1200,7 +1197,7 @@ impl<'a> ModuleLoaderAPI<'a> for NativeLoader<'a> {
     unsafe fn get_module_path<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [OSPathChar], Error<Owned>>
+    ) -> Result<OsPath<'module>, Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -1211,7 +1208,7 @@ impl<'a> ModuleLoaderAPI<'a> for NativeLoader<'a> {
     unsafe fn get_load_dependencies<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -1222,7 +1219,7 @@ impl<'a> ModuleLoaderAPI<'a> for NativeLoader<'a> {
     unsafe fn get_runtime_dependencies<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -1233,7 +1230,7 @@ impl<'a> ModuleLoaderAPI<'a> for NativeLoader<'a> {
     unsafe fn get_exportable_interfaces<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -1381,7 +1378,7 @@ impl<'a> ModuleLoaderAPI<'a> for NativeLoaderInternal<'a> {
     unsafe fn get_interface<'module, O, T>(
         &self,
         module: &'module InternalModule<O>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
         caster: impl FnOnce(crate::ffi::module::Interface) -> T,
     ) -> Result<Interface<'module, T>, Error<Owned>>
     where
@@ -1413,7 +1410,7 @@ impl<'a> ModuleLoaderAPI<'a> for NativeLoaderInternal<'a> {
     unsafe fn get_module_path<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [OSPathChar], Error<Owned>>
+    ) -> Result<OsPath<'module>, Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -1428,7 +1425,7 @@ impl<'a> ModuleLoaderAPI<'a> for NativeLoaderInternal<'a> {
     unsafe fn get_load_dependencies<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -1443,7 +1440,7 @@ impl<'a> ModuleLoaderAPI<'a> for NativeLoaderInternal<'a> {
     unsafe fn get_runtime_dependencies<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -1458,7 +1455,7 @@ impl<'a> ModuleLoaderAPI<'a> for NativeLoaderInternal<'a> {
     unsafe fn get_exportable_interfaces<'module, O>(
         &self,
         module: &'module InternalModule<O>,
-    ) -> Result<&'module [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'module [InterfaceDescriptor<'module>], Error<Owned>>
     where
         O: ImmutableAccessIdentifier,
     {
@@ -5,6 +5,8 @@ use crate::ffi::module::native_module::{
     NativeModuleInterface as NativeModuleInterfaceFFI,
 };
 use crate::ffi::CBaseBinding;
+#[cfg(feature = "init")]
+use crate::module::policy::{self, ModuleMetadata};
 use crate::module::{Interface, InterfaceDescriptor, Module, ModuleInfo};
 use crate::ownership::{
     AccessIdentifier, BorrowImmutable, BorrowMutable, ImmutableAccessIdentifier,
@@ -132,6 +134,10 @@ where
 {
     /// Loads the module.
     ///
+    /// If a [ModulePolicy](crate::module::policy::ModulePolicy) is installed, the module
+    /// receives a view of the interface restricted to the functions permitted by the policy.
+    /// The view is kept alive until the instance is unloaded.
+    ///
     /// # Failure
     ///
     /// The function can fail if some module invariant is not met.
@@ -155,18 +161,38 @@ where
         MO: AccessIdentifier,
     {
         let internal = interface.internal_interface();
-        let interface_handle = internal.base_module();
-        let has_fn_fn = internal.fetch_has_function_fn();
-        let get_fn_fn = internal.fetch_get_function_fn();
+        let mut interface_handle = internal.base_module();
+        let mut has_fn_fn = internal.fetch_has_function_fn();
+        let mut get_fn_fn = internal.fetch_get_function_fn();
 
-        self._interface
+        #[cfg(feature = "init")]
+        let view = {
+            let metadata = ModuleMetadata {
+                handle: module.as_handle(),
+                load_dependencies: self.get_load_dependencies(),
+            };
+            policy::restrict(&metadata, internal)?
+        };
+        #[cfg(feature = "init")]
+        if let Some(view) = &view {
+            interface_handle = view.base_module();
+            has_fn_fn = view.has_function_fn();
+            get_fn_fn = view.get_function_fn();
+        }
+
+        let instance = self
+            ._interface
             .into_mut()
             .as_mut()
             .load(module.as_handle(), interface_handle, has_fn_fn, get_fn_fn)
-            .map_or_else(
-                |e| Err(Error::from(e)),
-                |v| Ok(NativeModuleInstance::new(v)),
-            )
+            .into_rust()?;
+
+        #[cfg(feature = "init")]
+        if let Some(view) = view {
+            policy::retain(self._interface, instance, view);
+        }
+
+        Ok(NativeModuleInstance::new(instance))
     }
 
     /// Unloads the module.
@@ -193,8 +219,11 @@ where
             .into_mut()
             .as_mut()
             .unload(instance.as_handle())
-            .into_rust()
-            .map_or_else(|e| Err(Error::from(e)), |_v| Ok(()))
+            .into_rust()?;
+
+        #[cfg(feature = "init")]
+        policy::release(self._interface, instance.as_handle());
+        Ok(())
     }
 
     /// Initializes the module.
@@ -277,7 +306,7 @@ where
     pub unsafe fn get_interface<'instance, IO, T>(
         &self,
         instance: &'instance NativeModuleInstance<'instance, IO>,
-        interface: &InterfaceDescriptor,
+        interface: &InterfaceDescriptor<'_>,
         caster: impl FnOnce(crate::ffi::module::Interface) -> T,
     ) -> Result<Interface<'instance, T>, Error<Owned>>
     where
@@ -332,7 +361,7 @@ where
     /// Direct usage of a [NativeModule] may break some invariants
     /// of the module api, if not handled with care.
     #[inline]
-    pub unsafe fn get_load_dependencies(&self) -> &'a [InterfaceDescriptor] {
+    pub unsafe fn get_load_dependencies(&self) -> &'a [InterfaceDescriptor<'a>] {
         let span = self._interface.as_ref().get_load_dependencies();
         if span.is_empty() {
             <&[_]>::default()
@@ -360,7 +389,7 @@ where
     pub unsafe fn get_runtime_dependencies<'instance, IO>(
         &self,
         instance: &'instance NativeModuleInstance<'instance, IO>,
-    ) -> Result<&'instance [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'instance [InterfaceDescriptor<'instance>], Error<Owned>>
     where
         IO: ImmutableAccessIdentifier,
     {
@@ -399,7 +428,7 @@ where
     pub unsafe fn get_exportable_interfaces<'instance, IO>(
         &self,
         instance: &'instance NativeModuleInstance<'instance, IO>,
-    ) -> Result<&'instance [InterfaceDescriptor], Error<Owned>>
+    ) -> Result<&'instance [InterfaceDescriptor<'instance>], Error<Owned>>
     where
         IO: ImmutableAccessIdentifier,
     {
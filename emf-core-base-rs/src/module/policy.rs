@@ -0,0 +1,496 @@
+//! Capability-restricted interfaces for native modules.
+//!
+//! A [ModulePolicy] decides which functions of the interface a module may use, based on
+//! the metadata of the module. Once a policy is installed with [set_policy],
+//! [NativeModule::load](crate::module::native_module::NativeModule::load) hands every module
+//! a [RestrictedInterface] instead of the full interface. Denied functions are reported as
+//! missing by `has_function` and `get_function` and panic the interface if called anyway.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use emf_core_base_rs::module::policy::{self, ModuleMetadata};
+//! use emf_core_base_rs::sys::capabilities::Capabilities;
+//!
+//! policy::set_policy(|metadata: &ModuleMetadata<'_>| {
+//!     if metadata.depends_on("my_trusted_interface") {
+//!         Capabilities::all()
+//!     } else {
+//!         &policy::no_shutdown() & &policy::module_read_only()
+//!     }
+//! });
+//! ```
+use crate::ffi::collections::NonNullConst;
+use crate::ffi::intercept::{InterceptHooks, InterceptedInterface};
+use crate::ffi::module::native_module::{
+    NativeModule as NativeModuleFFI, NativeModuleInterface as NativeModuleInterfaceFFI,
+};
+use crate::ffi::module::{InterfaceDescriptor, ModuleHandle};
+use crate::ffi::sys::api::{GetFunctionFn, HasFunctionFn};
+use crate::ffi::{CBase, CBaseBinding, CBaseInterface, CBaseLoader, FnId};
+use crate::ownership::Owned;
+use crate::sys::capabilities::Capabilities;
+use crate::Error;
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, RwLock};
+
+/// Functions of the module api which modify the state of the modules.
+const MODULE_MUTATORS: &[FnId] = &[
+    FnId::ModuleRegisterLoader,
+    FnId::ModuleUnregisterLoader,
+    FnId::ModuleCreateModuleHandle,
+    FnId::ModuleRemoveModuleHandle,
+    FnId::ModuleLinkModule,
+    FnId::ModuleAddModule,
+    FnId::ModuleRemoveModule,
+    FnId::ModuleLoad,
+    FnId::ModuleUnload,
+    FnId::ModuleInitialize,
+    FnId::ModuleTerminate,
+    FnId::ModuleAddDependency,
+    FnId::ModuleRemoveDependency,
+    FnId::ModuleExportInterface,
+];
+
+/// Every function, except for [FnId::SysShutdown].
+pub fn no_shutdown() -> Capabilities {
+    let mut capabilities = Capabilities::all();
+    capabilities.remove(FnId::SysShutdown);
+    capabilities
+}
+
+/// Every function, except for the functions modifying the modules and module loaders.
+pub fn module_read_only() -> Capabilities {
+    let mut capabilities = Capabilities::all();
+    for &id in MODULE_MUTATORS {
+        capabilities.remove(id);
+    }
+    capabilities
+}
+
+/// Metadata of a module, which is available before loading it.
+#[derive(Debug, Copy, Clone)]
+pub struct ModuleMetadata<'a> {
+    /// Handle of the module.
+    pub handle: ModuleHandle,
+    /// Load dependencies of the module.
+    pub load_dependencies: &'a [InterfaceDescriptor<'a>],
+}
+
+impl ModuleMetadata<'_> {
+    /// Checks whether the module declares a load dependency to the interface `name`.
+    #[inline]
+    pub fn depends_on(&self, name: &str) -> bool {
+        self.load_dependencies
+            .iter()
+            .any(|dep| dep.name.as_str() == Ok(name))
+    }
+}
+
+/// A policy restricting the interface available to the modules.
+pub trait ModulePolicy: Send + Sync {
+    /// Computes the functions a module may use.
+    fn capabilities(&self, metadata: &ModuleMetadata<'_>) -> Capabilities;
+}
+
+impl ModulePolicy for Capabilities {
+    #[inline]
+    fn capabilities(&self, _metadata: &ModuleMetadata<'_>) -> Capabilities {
+        self.clone()
+    }
+}
+
+impl<F> ModulePolicy for F
+where
+    F: Fn(&ModuleMetadata<'_>) -> Capabilities + Send + Sync,
+{
+    #[inline]
+    fn capabilities(&self, metadata: &ModuleMetadata<'_>) -> Capabilities {
+        self(metadata)
+    }
+}
+
+/// Hooks denying every function outside of the capabilities.
+#[derive(Debug)]
+struct Restriction(Capabilities);
+
+impl InterceptHooks for Restriction {
+    #[inline]
+    fn permits(&self, id: FnId) -> bool {
+        self.0.contains(id)
+    }
+}
+
+/// A view of the interface, which only exposes a subset of its functions.
+#[derive(Debug)]
+pub struct RestrictedInterface {
+    interface: Pin<Box<InterceptedInterface<Restriction>>>,
+}
+
+impl RestrictedInterface {
+    /// Restricts `interface` to `capabilities`.
+    ///
+    /// # Safety
+    ///
+    /// The interface must outlive the view.
+    pub unsafe fn new(interface: CBaseInterface, capabilities: Capabilities) -> Self {
+        Self {
+            interface: InterceptedInterface::new(interface, Restriction(capabilities)),
+        }
+    }
+
+    /// Fetches the functions exposed by the view.
+    #[inline]
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.interface.hooks().0
+    }
+
+    /// Fetches the interface exposed by the view.
+    #[inline]
+    pub fn interface(&self) -> CBaseInterface {
+        self.interface.interface()
+    }
+
+    /// Fetches the handle to pass to the module together with the filtered functions.
+    #[inline]
+    pub fn base_module(&self) -> Option<NonNull<CBase>> {
+        self.interface.base_module()
+    }
+
+    /// Fetches the filtered `has_function` function.
+    #[inline]
+    pub fn has_function_fn(&self) -> HasFunctionFn {
+        self.interface.fetch_has_function_fn()
+    }
+
+    /// Fetches the filtered `get_function` function.
+    #[inline]
+    pub fn get_function_fn(&self) -> GetFunctionFn {
+        self.interface.get_function_fn()
+    }
+}
+
+/// Key of a loaded native module instance.
+type InstanceKey = (usize, usize);
+
+#[derive(Default)]
+struct PolicyState {
+    policy: RwLock<Option<Arc<dyn ModulePolicy>>>,
+    views: Mutex<HashMap<InstanceKey, Vec<RestrictedInterface>>>,
+    interface: Mutex<Option<CBaseInterface>>,
+}
+
+fn state() -> &'static PolicyState {
+    static STATE: OnceLock<PolicyState> = OnceLock::new();
+    STATE.get_or_init(Default::default)
+}
+
+/// Installs the policy applied to the native modules loaded from now on.
+///
+/// # Return
+///
+/// The previous policy.
+pub fn set_policy(policy: impl ModulePolicy + 'static) -> Option<Arc<dyn ModulePolicy>> {
+    let mut current = state()
+        .policy
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    current.replace(Arc::new(policy))
+}
+
+/// Removes the installed policy, granting the full interface to the modules loaded from now on.
+///
+/// # Return
+///
+/// The previous policy.
+pub fn reset_policy() -> Option<Arc<dyn ModulePolicy>> {
+    let mut current = state()
+        .policy
+        .write()
+        .unwrap_or_else(PoisonError::into_inner);
+    current.take()
+}
+
+/// Fetches the installed policy.
+pub fn policy() -> Option<Arc<dyn ModulePolicy>> {
+    state()
+        .policy
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone()
+}
+
+/// Applies the installed policy to a module, which is about to be loaded.
+///
+/// # Return
+///
+/// [Option::None] if the module may use the full interface, the view otherwise.
+pub(crate) unsafe fn restrict(
+    metadata: &ModuleMetadata<'_>,
+    interface: &impl CBaseBinding,
+) -> Result<Option<RestrictedInterface>, Error<Owned>> {
+    let policy = match policy() {
+        Some(policy) => policy,
+        None => return Ok(None),
+    };
+
+    let capabilities = policy.capabilities(metadata);
+    if capabilities.missing().is_empty() {
+        return Ok(None);
+    }
+
+    let inner = fetch_interface(interface)?;
+    Ok(Some(RestrictedInterface::new(inner, capabilities)))
+}
+
+/// Fetches the interface wrapped by the views, which is cached per `base_module`.
+unsafe fn fetch_interface(interface: &impl CBaseBinding) -> Result<CBaseInterface, Error<Owned>> {
+    let mut cached = state()
+        .interface
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    match *cached {
+        Some(inner) if inner.base_module == interface.base_module() => Ok(inner),
+        _ => {
+            let inner = <CBaseInterface as CBaseLoader>::try_fetch_interface(
+                interface.base_module(),
+                interface.fetch_get_function_fn(),
+            )
+            .into_rust()
+            .map_err(Error::from)?;
+            *cached = Some(inner);
+            Ok(inner)
+        }
+    }
+}
+
+fn instance_key(
+    module: NonNullConst<NativeModuleInterfaceFFI>,
+    instance: Option<NonNull<NativeModuleFFI>>,
+) -> InstanceKey {
+    (
+        module.as_ptr() as usize,
+        instance.map_or(0, |instance| instance.as_ptr() as usize),
+    )
+}
+
+/// Keeps the view of a loaded instance alive until the instance is unloaded.
+pub(crate) fn retain(
+    module: NonNullConst<NativeModuleInterfaceFFI>,
+    instance: Option<NonNull<NativeModuleFFI>>,
+    view: RestrictedInterface,
+) {
+    state()
+        .views
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .entry(instance_key(module, instance))
+        .or_default()
+        .push(view)
+}
+
+/// Drops the view of an unloaded instance.
+pub(crate) fn release(
+    module: NonNullConst<NativeModuleInterfaceFFI>,
+    instance: Option<NonNull<NativeModuleFFI>>,
+) {
+    let mut views = state().views.lock().unwrap_or_else(PoisonError::into_inner);
+    let key = instance_key(module, instance);
+    if let Some(instance_views) = views.get_mut(&key) {
+        instance_views.pop();
+        if instance_views.is_empty() {
+            views.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{module_read_only, no_shutdown, ModuleMetadata, RestrictedInterface};
+    use crate::ffi::collections::Optional;
+    use crate::ffi::module::api::ModuleBinding;
+    use crate::ffi::module::ModuleHandle;
+    use crate::ffi::record::{Call, Recording, ReplayInterface, Value};
+    use crate::ffi::sys::api::SysBinding;
+    use crate::ffi::version::{Version, VERSION};
+    use crate::ffi::{Bool, FnId, CBASE_INTERFACE_NAME};
+    use std::panic::AssertUnwindSafe;
+
+    /// Calls `f`, returning the message it panicked with.
+    fn denied(f: impl FnOnce()) -> String {
+        let saved_panic_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let payload = std::panic::catch_unwind(AssertUnwindSafe(f)).unwrap_err();
+        std::panic::set_hook(saved_panic_hook);
+        *payload.downcast::<String>().unwrap()
+    }
+
+    fn num_modules_call() -> Call {
+        Call {
+            id: FnId::ModuleGetNumModules,
+            args: vec![],
+            result: Value::Int(2),
+        }
+    }
+
+    #[test]
+    fn restricted_functions() {
+        let replay = ReplayInterface::new(Recording::new());
+        let capabilities = &no_shutdown() & &module_read_only();
+        let view = unsafe { RestrictedInterface::new(replay.interface(), capabilities) };
+        let interface = view.interface();
+
+        assert!(!view.capabilities().contains(FnId::ModuleUnload));
+        unsafe {
+            assert_eq!(interface.has_function(FnId::SysLock), Bool::True);
+            assert_eq!(interface.has_function(FnId::SysPanic), Bool::True);
+            assert_eq!(interface.has_function(FnId::SysShutdown), Bool::False);
+            assert_eq!(interface.has_function(FnId::ModuleUnload), Bool::False);
+            assert!(matches!(
+                interface.get_function(FnId::ModuleRemoveModule),
+                Optional::None
+            ));
+            assert!(matches!(
+                interface.get_function(FnId::ModuleGetModules),
+                Optional::Some(_)
+            ));
+
+            let has_function_fn = view.has_function_fn();
+            assert_eq!(
+                has_function_fn(view.base_module(), FnId::SysShutdown),
+                Bool::False
+            );
+        }
+    }
+
+    #[test]
+    fn no_shutdown_denied() {
+        let replay = ReplayInterface::new(Recording::from(vec![num_modules_call()]));
+        let view = unsafe { RestrictedInterface::new(replay.interface(), no_shutdown()) };
+        let mut interface = view.interface();
+
+        let message = denied(|| unsafe { interface.shutdown() });
+        assert_eq!(message, "The function `SysShutdown` is not permitted");
+
+        // The other functions are forwarded.
+        assert_eq!(unsafe { interface.get_num_modules() }, 2);
+        assert!(replay.divergences().is_empty());
+    }
+
+    #[test]
+    fn module_read_only_denied() {
+        let replay = ReplayInterface::new(Recording::from(vec![num_modules_call()]));
+        let view = unsafe { RestrictedInterface::new(replay.interface(), module_read_only()) };
+        let mut interface = view.interface();
+
+        for &id in super::MODULE_MUTATORS {
+            assert_eq!(unsafe { interface.has_function(id) }, Bool::False);
+        }
+
+        let handle = ModuleHandle { id: 1 };
+        let denied_fn = |id: FnId, message: String| {
+            assert_eq!(message, format!("The function `{:?}` is not permitted", id))
+        };
+        denied_fn(
+            FnId::ModuleCreateModuleHandle,
+            denied(|| {
+                unsafe { interface.create_module_handle() };
+            }),
+        );
+        denied_fn(
+            FnId::ModuleRemoveModuleHandle,
+            denied(|| {
+                let _ = unsafe { interface.remove_module_handle(handle) };
+            }),
+        );
+        denied_fn(
+            FnId::ModuleLoad,
+            denied(|| {
+                let _ = unsafe { interface.load(handle) };
+            }),
+        );
+        denied_fn(
+            FnId::ModuleInitialize,
+            denied(|| {
+                let _ = unsafe { interface.initialize(handle) };
+            }),
+        );
+        denied_fn(
+            FnId::ModuleTerminate,
+            denied(|| {
+                let _ = unsafe { interface.terminate(handle) };
+            }),
+        );
+        denied_fn(
+            FnId::ModuleUnload,
+            denied(|| {
+                let _ = unsafe { interface.unload(handle) };
+            }),
+        );
+
+        // The denied calls did not reach the wrapped interface.
+        assert_eq!(unsafe { interface.get_num_modules() }, 2);
+        assert!(replay.divergences().is_empty());
+    }
+
+    fn version(version: &Version) -> Value {
+        Value::List(vec![
+            Value::Int(version.major as i64),
+            Value::Int(version.minor as i64),
+            Value::Int(version.patch as i64),
+            Value::Int(version.build),
+            Value::Int(version.release_number as i64),
+            Value::Int(version.release_type as i64),
+        ])
+    }
+
+    #[test]
+    fn cached_interface() {
+        let descriptor = Value::List(vec![
+            Value::Bytes(CBASE_INTERFACE_NAME.as_bytes().to_vec()),
+            version(&VERSION),
+            Value::List(vec![]),
+        ]);
+        let replay = ReplayInterface::new(Recording::from(vec![
+            Call {
+                id: FnId::ModuleGetExportedInterfaceHandle,
+                args: vec![descriptor.clone()],
+                result: Value::Int(0),
+            },
+            Call {
+                id: FnId::ModuleGetInterface,
+                args: vec![Value::Int(0), descriptor],
+                result: Value::Ptr(1),
+            },
+            Call {
+                id: FnId::VersionIsCompatible,
+                args: vec![version(&VERSION), version(&VERSION)],
+                result: Value::Int(1),
+            },
+        ]));
+
+        let metadata = ModuleMetadata {
+            handle: ModuleHandle { id: 0 },
+            load_dependencies: &[],
+        };
+        let previous = super::set_policy(no_shutdown());
+        let views = unsafe {
+            (
+                super::restrict(&metadata, &replay.interface()),
+                super::restrict(&metadata, &replay.interface()),
+            )
+        };
+        match previous {
+            Some(policy) => drop(super::set_policy(move |metadata: &ModuleMetadata<'_>| {
+                policy.capabilities(metadata)
+            })),
+            None => drop(super::reset_policy()),
+        }
+
+        // The interface is only fetched once.
+        assert!(matches!(views, (Ok(Some(_)), Ok(Some(_)))));
+        assert!(replay.divergences().is_empty());
+    }
+}
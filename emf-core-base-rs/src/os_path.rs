@@ -0,0 +1,150 @@
+use crate::ffi::library::{OSPathChar, OSPathString};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+#[cfg(unix)]
+use std::ffi::OsStr;
+#[cfg(unix)]
+use std::os::unix::ffi::OsStrExt;
+#[cfg(unix)]
+use std::path::Path;
+
+/// A borrowed path in the platforms preferred encoding.
+///
+/// Unlike a [String], the path is not required to be valid `UTF-8`
+/// and is therefore converted without any loss.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct OsPath<'a> {
+    chars: &'a [OSPathChar],
+}
+
+impl<'a> OsPath<'a> {
+    /// Constructs a path from its characters.
+    #[inline]
+    pub const fn new(chars: &'a [OSPathChar]) -> Self {
+        Self { chars }
+    }
+
+    /// Constructs a path from an [OSPathString].
+    ///
+    /// # Safety
+    ///
+    /// The characters of `path` must remain valid for the lifetime `'a`.
+    #[inline]
+    pub unsafe fn from_os_path_string(path: OSPathString) -> Self {
+        let chars = path.as_ref();
+        Self::new(std::slice::from_raw_parts(chars.as_ptr(), chars.len()))
+    }
+
+    /// Fetches the characters of the path.
+    #[inline]
+    pub const fn as_chars(&self) -> &'a [OSPathChar] {
+        self.chars
+    }
+
+    /// Converts the path to an [OSPathString] without copying it.
+    #[inline]
+    pub fn to_os_path_string(&self) -> OSPathString {
+        OSPathString::from(self.chars)
+    }
+
+    /// Views the path as an [OsStr] without copying it.
+    #[inline]
+    #[cfg(unix)]
+    pub fn as_os_str(&self) -> &'a OsStr {
+        OsStr::from_bytes(self.chars)
+    }
+
+    /// Views the path as a [Path] without copying it.
+    #[inline]
+    #[cfg(unix)]
+    pub fn as_path(&self) -> &'a Path {
+        Path::new(self.as_os_str())
+    }
+
+    /// Copies the path into a [PathBuf].
+    #[inline]
+    #[cfg(unix)]
+    pub fn to_path_buf(&self) -> PathBuf {
+        self.as_path().to_path_buf()
+    }
+
+    /// Copies the path into a [PathBuf].
+    #[inline]
+    #[cfg(windows)]
+    pub fn to_path_buf(&self) -> PathBuf {
+        use std::os::windows::ffi::OsStringExt;
+        PathBuf::from(std::ffi::OsString::from_wide(self.chars))
+    }
+}
+
+#[cfg(unix)]
+impl<'a> From<&'a OsStr> for OsPath<'a> {
+    #[inline]
+    fn from(path: &'a OsStr) -> Self {
+        Self::new(path.as_bytes())
+    }
+}
+
+#[cfg(unix)]
+impl<'a> From<&'a Path> for OsPath<'a> {
+    #[inline]
+    fn from(path: &'a Path) -> Self {
+        Self::from(path.as_os_str())
+    }
+}
+
+impl From<OsPath<'_>> for PathBuf {
+    #[inline]
+    fn from(path: OsPath<'_>) -> Self {
+        path.to_path_buf()
+    }
+}
+
+/// Displays the path, replacing invalid characters.
+impl Display for OsPath<'_> {
+    #[cfg(unix)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.as_path().display(), f)
+    }
+
+    #[cfg(windows)]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&String::from_utf16_lossy(self.chars), f)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use crate::{OsPath, ToOsPathBuff};
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::{Path, PathBuf};
+
+    const INVALID: &[u8] = b"/modules/\xFF\xFEmodule.so";
+
+    #[test]
+    fn invalid_utf8() {
+        let path = Path::new(OsStr::from_bytes(INVALID));
+        assert!(path.to_str().is_none());
+
+        let buff = path.to_os_path_buff();
+        assert_eq!(buff, INVALID);
+
+        let os_path = OsPath::from(path);
+        assert_eq!(os_path.as_chars(), INVALID);
+        assert_eq!(os_path.as_path(), path);
+        assert_eq!(PathBuf::from(OsPath::new(&buff)), path);
+        assert_eq!(os_path.to_string(), "/modules/\u{FFFD}\u{FFFD}module.so");
+    }
+
+    #[test]
+    fn os_path_string() {
+        let path = OsPath::new(INVALID);
+        let string = path.to_os_path_string();
+        assert_eq!(string.as_ref().as_ptr(), INVALID.as_ptr());
+
+        let path = unsafe { OsPath::from_os_path_string(string) };
+        assert_eq!(path.as_os_str().as_bytes(), INVALID);
+    }
+}
@@ -8,6 +8,8 @@ use crate::ownership::Owned;
 use crate::sys::sync_handler::SyncHandlerAPI;
 use crate::Error;
 
+pub mod capabilities;
+pub mod shutdown;
 pub mod sync_handler;
 
 /// Minimal sys api.
@@ -0,0 +1,453 @@
+//! Snapshot of the functions implemented by the interface.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use emf_core_base_rs::CBaseRef;
+//! # let base_interface: &CBaseRef<'static> = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+//! use emf_core_base_rs::ffi::FnId;
+//! use emf_core_base_rs::sys::capabilities::Capabilities;
+//!
+//! let capabilities = Capabilities::probe(base_interface);
+//! println!("{}", capabilities);
+//!
+//! if let Err(e) = capabilities.require(&[FnId::ModuleLoad, FnId::ModuleInitialize]) {
+//!     panic!("{}", e);
+//! }
+//! ```
+use crate::ffi::FnId;
+use crate::fn_caster::{library, module, sys, version, FnCaster};
+use crate::ownership::Owned;
+use crate::sys::SysAPIMin;
+use crate::Error;
+use std::collections::btree_set::{BTreeSet, Iter};
+use std::fmt::{Display, Formatter};
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, Sub};
+
+#[cfg(feature = "unwind_internal")]
+use crate::ffi::extensions::unwind_internal::UnwindInternalInterface;
+#[cfg(feature = "unwind_internal")]
+use crate::fn_caster::extensions::ExtensionCaster;
+
+macro_rules! all_functions {
+    ($($caster:ty),* $(,)?) => {
+        /// Every function known to this crate.
+        const ALL_FUNCTIONS: &[FnId] = &[$(<$caster as FnCaster>::ID),*];
+
+        fn probe_functions<'interface, T>(interface: &T) -> BTreeSet<FnId>
+        where
+            T: SysAPIMin<'interface> + ?Sized,
+        {
+            let mut functions = BTreeSet::new();
+            $(
+                if interface.has_function::<$caster>() {
+                    functions.insert(<$caster as FnCaster>::ID);
+                }
+            )*
+            functions
+        }
+    };
+}
+
+#[cfg(not(feature = "unwind_internal"))]
+macro_rules! probe_list {
+    ($callback:ident, $($caster:ty),* $(,)?) => {
+        $callback!($($caster),*);
+    };
+}
+
+#[cfg(feature = "unwind_internal")]
+macro_rules! probe_list {
+    ($callback:ident, $($caster:ty),* $(,)?) => {
+        $callback!($($caster),*, ExtensionCaster<UnwindInternalInterface>);
+    };
+}
+
+probe_list!(
+    all_functions,
+    sys::ShutdownCaster,
+    sys::PanicCaster,
+    sys::HasFunctionCaster,
+    sys::GetFunctionCaster,
+    sys::LockCaster,
+    sys::TryLockCaster,
+    sys::UnlockCaster,
+    sys::GetSyncHandlerCaster,
+    sys::SetSyncHandlerCaster,
+    version::NewShortCaster,
+    version::NewLongCaster,
+    version::NewFullCaster,
+    version::FromStringCaster,
+    version::StringLengthShortCaster,
+    version::StringLengthLongCaster,
+    version::StringLengthFullCaster,
+    version::AsStringShortCaster,
+    version::AsStringLongCaster,
+    version::AsStringFullCaster,
+    version::StringIsValidCaster,
+    version::CompareCaster,
+    version::CompareWeakCaster,
+    version::CompareStrongCaster,
+    version::IsCompatibleCaster,
+    library::RegisterLoaderCaster,
+    library::UnregisterLoaderCaster,
+    library::GetLoaderInterfaceCaster,
+    library::GetLoaderHandleFromTypeCaster,
+    library::GetLoaderHandleFromLibraryCaster,
+    library::GetNumLoadersCaster,
+    library::LibraryExistsCaster,
+    library::TypeExistsCaster,
+    library::GetLibraryTypesCaster,
+    library::CreateLibraryHandleCaster,
+    library::RemoveLibraryHandleCaster,
+    library::LinkLibraryCaster,
+    library::GetInternalLibraryHandleCaster,
+    library::LoadCaster,
+    library::UnloadCaster,
+    library::GetDataSymbolCaster,
+    library::GetFunctionSymbolCaster,
+    module::RegisterLoaderCaster,
+    module::UnregisterLoaderCaster,
+    module::GetLoaderInterfaceCaster,
+    module::GetLoaderHandleFromTypeCaster,
+    module::GetLoaderHandleFromModuleCaster,
+    module::GetNumModulesCaster,
+    module::GetNumLoadersCaster,
+    module::GetNumExportedInterfacesCaster,
+    module::ModuleExistsCaster,
+    module::TypeExistsCaster,
+    module::ExportedInterfaceExistsCaster,
+    module::GetModulesCaster,
+    module::GetModuleTypesCaster,
+    module::GetExportedInterfacesCaster,
+    module::GetExportedInterfaceHandleCaster,
+    module::CreateModuleHandleCaster,
+    module::RemoveModuleHandleCaster,
+    module::LinkModuleCaster,
+    module::GetInternalModuleHandleCaster,
+    module::AddModuleCaster,
+    module::RemoveModuleCaster,
+    module::LoadCaster,
+    module::UnloadCaster,
+    module::InitializeCaster,
+    module::TerminateCaster,
+    module::AddDependencyCaster,
+    module::RemoveDependencyCaster,
+    module::ExportInterfaceCaster,
+    module::GetLoadDependenciesCaster,
+    module::GetRuntimeDependenciesCaster,
+    module::GetExportableInterfacesCaster,
+    module::FetchStatusCaster,
+    module::GetModulePathCaster,
+    module::GetModuleInfoCaster,
+    module::GetInterfaceCaster,
+);
+
+/// Error listing the functions missing from the interface.
+#[derive(Debug)]
+struct MissingFunctions {
+    functions: Vec<FnId>,
+}
+
+impl Display for MissingFunctions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The interface is missing the required functions: ")?;
+        for (i, id) in self.functions.iter().enumerate() {
+            if i != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?}", id)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MissingFunctions {}
+
+/// A set of the functions implemented by the interface.
+#[derive(Debug, Clone, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Capabilities {
+    functions: BTreeSet<FnId>,
+}
+
+impl Capabilities {
+    /// Constructs an empty set.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Constructs a set containing every function known to this crate.
+    #[inline]
+    pub fn all() -> Self {
+        ALL_FUNCTIONS.iter().copied().collect()
+    }
+
+    /// Probes every function with [SysAPIMin::has_function].
+    ///
+    /// # Return
+    ///
+    /// Set of the implemented functions.
+    #[inline]
+    pub fn probe<'interface, T>(interface: &T) -> Self
+    where
+        T: SysAPIMin<'interface> + ?Sized,
+    {
+        Self {
+            functions: probe_functions(interface),
+        }
+    }
+
+    /// Checks whether a function is contained in the set.
+    #[inline]
+    pub fn contains(&self, id: FnId) -> bool {
+        self.functions.contains(&id)
+    }
+
+    /// Inserts a function into the set.
+    ///
+    /// # Return
+    ///
+    /// [true] if the function was not present, [false] otherwise.
+    #[inline]
+    pub fn insert(&mut self, id: FnId) -> bool {
+        self.functions.insert(id)
+    }
+
+    /// Removes a function from the set.
+    ///
+    /// # Return
+    ///
+    /// [true] if the function was present, [false] otherwise.
+    #[inline]
+    pub fn remove(&mut self, id: FnId) -> bool {
+        self.functions.remove(&id)
+    }
+
+    /// Number of functions in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Checks whether the set is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    /// Iterates over the functions in ascending order of their ids.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, FnId> {
+        self.functions.iter()
+    }
+
+    /// Constructs the union of two sets.
+    #[inline]
+    pub fn union(&self, other: &Self) -> Self {
+        self.functions.union(&other.functions).copied().collect()
+    }
+
+    /// Constructs the intersection of two sets.
+    #[inline]
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.functions
+            .intersection(&other.functions)
+            .copied()
+            .collect()
+    }
+
+    /// Constructs the set of the functions in `self` but not in `other`.
+    #[inline]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.functions
+            .difference(&other.functions)
+            .copied()
+            .collect()
+    }
+
+    /// Checks whether all functions of `self` are contained in `other`.
+    #[inline]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.functions.is_subset(&other.functions)
+    }
+
+    /// Checks whether all functions of `other` are contained in `self`.
+    #[inline]
+    pub fn is_superset(&self, other: &Self) -> bool {
+        self.functions.is_superset(&other.functions)
+    }
+
+    /// Constructs the set of the known functions missing from `self`.
+    #[inline]
+    pub fn missing(&self) -> Self {
+        Self::all().difference(self)
+    }
+
+    /// Checks that all `required` functions are contained in the set.
+    ///
+    /// # Failure
+    ///
+    /// Fails if at least one function is missing.
+    ///
+    /// # Return
+    ///
+    /// Error listing every missing function on failure.
+    pub fn require(&self, required: &[FnId]) -> Result<(), Error<Owned>> {
+        let mut functions: Vec<_> = required
+            .iter()
+            .copied()
+            .filter(|&id| !self.contains(id))
+            .collect();
+
+        if functions.is_empty() {
+            Ok(())
+        } else {
+            functions.sort_unstable();
+            functions.dedup();
+            Err(Error::from(MissingFunctions { functions }))
+        }
+    }
+}
+
+impl Display for Capabilities {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let all = Self::all().union(self);
+        write!(f, "Capabilities ({}/{}):", self.len(), all.len())?;
+        for id in all.iter() {
+            let mark = if self.contains(*id) { 'x' } else { ' ' };
+            write!(f, "\n  [{}] {:?} ({})", mark, id, *id as i32)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromIterator<FnId> for Capabilities {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = FnId>>(iter: I) -> Self {
+        Self {
+            functions: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<FnId> for Capabilities {
+    #[inline]
+    fn extend<I: IntoIterator<Item = FnId>>(&mut self, iter: I) {
+        self.functions.extend(iter)
+    }
+}
+
+impl<'a> IntoIterator for &'a Capabilities {
+    type Item = &'a FnId;
+    type IntoIter = Iter<'a, FnId>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl BitOr for &Capabilities {
+    type Output = Capabilities;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl BitAnd for &Capabilities {
+    type Output = Capabilities;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl Sub for &Capabilities {
+    type Output = Capabilities;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Capabilities, ALL_FUNCTIONS};
+    use crate::ffi::intercept::{InterceptHooks, InterceptedInterface};
+    use crate::ffi::record::{Recording, ReplayInterface};
+    use crate::ffi::FnId;
+    use crate::CBaseRef;
+
+    struct Deny(Capabilities);
+
+    impl InterceptHooks for Deny {
+        fn permits(&self, id: FnId) -> bool {
+            !self.0.contains(id)
+        }
+    }
+
+    #[test]
+    fn probe() {
+        let replay = ReplayInterface::new(Recording::new());
+        let interface = unsafe { CBaseRef::new(replay.interface()) };
+        let available = Capabilities::probe(&interface);
+        assert!(available.contains(FnId::SysShutdown));
+        assert!(available.contains(FnId::ModuleLoad));
+
+        // `SysPanic` can not be denied.
+        let denied: Capabilities = [FnId::SysShutdown, FnId::SysPanic, FnId::ModuleLoad]
+            .iter()
+            .copied()
+            .collect();
+        let intercepted = unsafe { InterceptedInterface::new(replay.interface(), Deny(denied)) };
+        let interface = unsafe { CBaseRef::new(intercepted.interface()) };
+
+        let missing: Capabilities = [FnId::SysShutdown, FnId::ModuleLoad]
+            .iter()
+            .copied()
+            .collect();
+        let probed = Capabilities::probe(&interface);
+        assert_eq!(probed, &available - &missing);
+        assert!(probed.contains(FnId::SysPanic));
+    }
+
+    #[test]
+    fn set_operations() {
+        let lhs: Capabilities = [FnId::SysPanic, FnId::SysLock].iter().copied().collect();
+        let rhs: Capabilities = [FnId::SysLock, FnId::ModuleLoad].iter().copied().collect();
+
+        assert_eq!(
+            &lhs | &rhs,
+            [FnId::SysPanic, FnId::SysLock, FnId::ModuleLoad]
+                .iter()
+                .copied()
+                .collect()
+        );
+        assert_eq!(&lhs & &rhs, [FnId::SysLock].iter().copied().collect());
+        assert_eq!(&lhs - &rhs, [FnId::SysPanic].iter().copied().collect());
+        assert!((&lhs & &rhs).is_subset(&lhs));
+        assert!(Capabilities::all().is_superset(&rhs));
+        assert_eq!(Capabilities::all().len(), ALL_FUNCTIONS.len());
+        assert_eq!(Capabilities::new().missing(), Capabilities::all());
+    }
+
+    #[test]
+    fn require() {
+        let capabilities: Capabilities = [FnId::SysPanic].iter().copied().collect();
+        assert!(capabilities.require(&[FnId::SysPanic]).is_ok());
+
+        let error = capabilities
+            .require(&[FnId::ModuleLoad, FnId::SysPanic, FnId::SysLock])
+            .unwrap_err();
+        assert_eq!(
+            format!("{}", error),
+            "The interface is missing the required functions: SysLock, ModuleLoad"
+        );
+    }
+}
@@ -0,0 +1,691 @@
+//! Graceful shutdown of the interface.
+//!
+//! [SysAPI::shutdown](crate::sys::SysAPI::shutdown) never returns and leaves the modules
+//! and libraries as they are. A [ShutdownCoordinator] keeps track of the resources owned by
+//! the host and releases them in order:
+//!
+//! 1. The shutdown hooks are called in reverse registration order.
+//! 2. The `Ready` modules are terminated, dependents before their dependencies.
+//! 3. The modules are unloaded and removed.
+//! 4. The module loaders and library loaders are unregistered.
+//! 5. The libraries are unloaded.
+//!
+//! A failure does not abort the shutdown. Every failure is collected into the
+//! [ShutdownReport], and the stages depending on the failed one are skipped for that
+//! resource.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use emf_core_base_rs::CBaseRef;
+//! # let base_interface: &mut CBaseRef<'static> = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+//! # let module = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+//! use emf_core_base_rs::sys::shutdown::ShutdownCoordinator;
+//!
+//! let mut coordinator = ShutdownCoordinator::new();
+//! coordinator.add_module(module);
+//! coordinator.add_hook(|| {
+//!     println!("Shutting down");
+//!     Ok(())
+//! });
+//!
+//! let report = coordinator.shutdown(base_interface);
+//! if !report.is_success() {
+//!     eprintln!("{}", report);
+//! }
+//! ```
+use crate::ffi::library::{LibraryHandle, LoaderHandle as LibraryLoaderHandle};
+use crate::ffi::module::{LoaderHandle as ModuleLoaderHandle, ModuleHandle, ModuleStatus};
+use crate::library::{Library, LibraryAPI};
+use crate::module::{Module, ModuleAPI};
+use crate::ownership::Owned;
+use crate::{library, module, Error};
+use std::fmt::{Debug, Display, Formatter};
+
+#[cfg(feature = "unwind_internal")]
+use crate::extensions::unwind_internal::{Signal, UnwindInternalAPI, UnwindInternalContextAPI};
+#[cfg(feature = "unwind_internal")]
+use crate::CBaseAPI;
+#[cfg(feature = "unwind_internal")]
+use std::panic::UnwindSafe;
+
+/// A function called at the start of the shutdown.
+pub type ShutdownHook<'a> = Box<dyn FnOnce() -> Result<(), Error<Owned>> + 'a>;
+
+/// Stages of the shutdown.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ShutdownStage {
+    /// Calling of the shutdown hooks.
+    Hooks,
+    /// Termination of the modules.
+    Terminate,
+    /// Unloading of the modules.
+    Unload,
+    /// Removal of the modules.
+    Remove,
+    /// Unregistration of the module loaders.
+    UnregisterModuleLoaders,
+    /// Unregistration of the library loaders.
+    UnregisterLibraryLoaders,
+    /// Unloading of the libraries.
+    UnloadLibraries,
+}
+
+impl Display for ShutdownStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShutdownStage::Hooks => write!(f, "Hooks"),
+            ShutdownStage::Terminate => write!(f, "Terminate"),
+            ShutdownStage::Unload => write!(f, "Unload"),
+            ShutdownStage::Remove => write!(f, "Remove"),
+            ShutdownStage::UnregisterModuleLoaders => write!(f, "UnregisterModuleLoaders"),
+            ShutdownStage::UnregisterLibraryLoaders => write!(f, "UnregisterLibraryLoaders"),
+            ShutdownStage::UnloadLibraries => write!(f, "UnloadLibraries"),
+        }
+    }
+}
+
+/// Resource affected by a failure.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ShutdownTarget {
+    /// A shutdown hook, identified by its registration index.
+    Hook(usize),
+    /// A module.
+    Module(ModuleHandle),
+    /// A module loader.
+    ModuleLoader(ModuleLoaderHandle),
+    /// A library loader.
+    LibraryLoader(LibraryLoaderHandle),
+    /// A library.
+    Library(LibraryHandle),
+}
+
+impl Display for ShutdownTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShutdownTarget::Hook(index) => write!(f, "hook {}", index),
+            ShutdownTarget::Module(handle) => write!(f, "module {}", handle),
+            ShutdownTarget::ModuleLoader(handle) => write!(f, "module loader {}", handle),
+            ShutdownTarget::LibraryLoader(handle) => write!(f, "library loader {}", handle),
+            ShutdownTarget::Library(handle) => write!(f, "library {}", handle),
+        }
+    }
+}
+
+/// A failure, which occurred during the shutdown.
+#[derive(Debug)]
+pub struct ShutdownFailure {
+    /// Stage of the failure.
+    pub stage: ShutdownStage,
+    /// Affected resource.
+    pub target: ShutdownTarget,
+    /// Error returned by the interface.
+    pub error: Error<Owned>,
+}
+
+impl Display for ShutdownFailure {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", self.stage, self.target, self.error)
+    }
+}
+
+/// Outcome of a shutdown.
+#[derive(Debug, Default)]
+pub struct ShutdownReport {
+    terminated: Vec<ModuleHandle>,
+    failures: Vec<ShutdownFailure>,
+}
+
+impl ShutdownReport {
+    /// Checks whether the shutdown completed without failures.
+    #[inline]
+    pub fn is_success(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Fetches the modules in the order they were terminated.
+    #[inline]
+    pub fn terminated(&self) -> &[ModuleHandle] {
+        &self.terminated
+    }
+
+    /// Fetches the failures in the order they occurred.
+    #[inline]
+    pub fn failures(&self) -> &[ShutdownFailure] {
+        &self.failures
+    }
+
+    /// Converts the report to a [Result].
+    ///
+    /// # Return
+    ///
+    /// [Result::Ok] if the shutdown completed without failures, the report otherwise.
+    #[inline]
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_success() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+
+    fn check<T>(
+        &mut self,
+        stage: ShutdownStage,
+        target: ShutdownTarget,
+        result: Result<T, Error<Owned>>,
+    ) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.failures.push(ShutdownFailure {
+                    stage,
+                    target,
+                    error,
+                });
+                None
+            }
+        }
+    }
+}
+
+impl Display for ShutdownReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_success() {
+            return write!(f, "Shutdown completed");
+        }
+
+        write!(
+            f,
+            "Shutdown completed with {} failure(s)",
+            self.failures.len()
+        )?;
+        for failure in &self.failures {
+            write!(f, "\n  {}", failure)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reason for the interruption of a closure run by [ShutdownCoordinator::catch_shutdown].
+#[cfg(feature = "unwind_internal")]
+#[derive(Debug)]
+pub enum Interruption {
+    /// A shutdown signal was caught and the shutdown was performed.
+    Shutdown(ShutdownReport),
+    /// Any other signal.
+    Signal(Signal),
+}
+
+/// Coordinator of the shutdown of the interface.
+pub struct ShutdownCoordinator<'interface> {
+    hooks: Vec<ShutdownHook<'interface>>,
+    modules: Vec<Module<'interface, Owned>>,
+    module_loaders: Vec<module::Loader<'interface, Owned>>,
+    library_loaders: Vec<library::Loader<'interface, Owned>>,
+    libraries: Vec<Library<'interface, Owned>>,
+}
+
+impl<'interface> ShutdownCoordinator<'interface> {
+    /// Constructs a coordinator without any resources.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            hooks: Vec::new(),
+            modules: Vec::new(),
+            module_loaders: Vec::new(),
+            library_loaders: Vec::new(),
+            libraries: Vec::new(),
+        }
+    }
+
+    /// Registers a hook, which is called at the start of the shutdown.
+    ///
+    /// Hooks are called in reverse registration order.
+    #[inline]
+    pub fn add_hook(&mut self, hook: impl FnOnce() -> Result<(), Error<Owned>> + 'interface) {
+        self.hooks.push(Box::new(hook))
+    }
+
+    /// Hands a module over to the coordinator.
+    #[inline]
+    pub fn add_module(&mut self, module: Module<'interface, Owned>) {
+        self.modules.push(module)
+    }
+
+    /// Hands a custom module loader over to the coordinator.
+    #[inline]
+    pub fn add_module_loader(&mut self, loader: module::Loader<'interface, Owned>) {
+        self.module_loaders.push(loader)
+    }
+
+    /// Hands a custom library loader over to the coordinator.
+    #[inline]
+    pub fn add_library_loader(&mut self, loader: library::Loader<'interface, Owned>) {
+        self.library_loaders.push(loader)
+    }
+
+    /// Hands a library over to the coordinator.
+    #[inline]
+    pub fn add_library(&mut self, library: Library<'interface, Owned>) {
+        self.libraries.push(library)
+    }
+
+    /// Releases every resource held by the coordinator.
+    ///
+    /// The coordinator is empty afterwards and can be reused.
+    ///
+    /// # Return
+    ///
+    /// Report of the shutdown.
+    pub fn shutdown<T>(&mut self, interface: &mut T) -> ShutdownReport
+    where
+        T: ModuleAPI<'interface> + LibraryAPI<'interface>,
+    {
+        let mut report = ShutdownReport::default();
+
+        let hooks = std::mem::take(&mut self.hooks);
+        for (index, hook) in hooks.into_iter().enumerate().rev() {
+            report.check(ShutdownStage::Hooks, ShutdownTarget::Hook(index), hook());
+        }
+
+        let modules = std::mem::take(&mut self.modules);
+        let dependencies: Vec<_> = modules
+            .iter()
+            .map(|module| dependency_indices(interface, &modules, module))
+            .collect();
+
+        let order = termination_order(&dependencies);
+        let mut modules: Vec<_> = modules.into_iter().map(Some).collect();
+        let mut terminated = Vec::with_capacity(modules.len());
+        for index in order {
+            let mut module = modules[index].take().unwrap();
+            let target = ShutdownTarget::Module(module.as_handle());
+            let status = interface.fetch_status(&module);
+            let status = match report.check(ShutdownStage::Terminate, target, status) {
+                Some(status) => status,
+                None => continue,
+            };
+
+            if status == ModuleStatus::Ready {
+                let result = interface.terminate(&mut module);
+                if report
+                    .check(ShutdownStage::Terminate, target, result)
+                    .is_none()
+                {
+                    continue;
+                }
+                report.terminated.push(module.as_handle());
+            }
+            terminated.push((module, status));
+        }
+
+        for (mut module, status) in terminated {
+            let target = ShutdownTarget::Module(module.as_handle());
+            if status != ModuleStatus::Unloaded {
+                let result = ModuleAPI::unload(interface, &mut module);
+                if report
+                    .check(ShutdownStage::Unload, target, result)
+                    .is_none()
+                {
+                    continue;
+                }
+            }
+
+            let result = interface.remove_module(module);
+            report.check(ShutdownStage::Remove, target, result);
+        }
+
+        for loader in std::mem::take(&mut self.module_loaders).into_iter().rev() {
+            let target = ShutdownTarget::ModuleLoader(loader.as_handle());
+            let result = ModuleAPI::unregister_loader(interface, loader);
+            report.check(ShutdownStage::UnregisterModuleLoaders, target, result);
+        }
+
+        for loader in std::mem::take(&mut self.library_loaders).into_iter().rev() {
+            let target = ShutdownTarget::LibraryLoader(loader.as_handle());
+            let result = LibraryAPI::unregister_loader(interface, loader);
+            report.check(ShutdownStage::UnregisterLibraryLoaders, target, result);
+        }
+
+        for library in std::mem::take(&mut self.libraries).into_iter().rev() {
+            let target = ShutdownTarget::Library(library.as_handle());
+            let result = LibraryAPI::unload(interface, library);
+            report.check(ShutdownStage::UnloadLibraries, target, result);
+        }
+
+        report
+    }
+
+    /// Calls `f`, performing the shutdown if it raises a shutdown signal.
+    ///
+    /// # Return
+    ///
+    /// Return value from `f`, the report of the shutdown or the caught signal.
+    #[cfg(feature = "unwind_internal")]
+    pub fn catch_shutdown<T, U>(
+        &mut self,
+        context: &impl UnwindInternalContextAPI<'interface>,
+        extension: &mut impl UnwindInternalAPI<'interface>,
+        interface: &mut T,
+        f: impl FnOnce(&mut T) -> U + UnwindSafe,
+    ) -> Result<U, Interruption>
+    where
+        T: CBaseAPI<'interface>,
+    {
+        match context.catch_unwind(extension, interface, f) {
+            Ok(value) => Ok(value),
+            Err(Signal::Shutdown) => Err(Interruption::Shutdown(self.shutdown(interface))),
+            Err(signal) => Err(Interruption::Signal(signal)),
+        }
+    }
+}
+
+impl Default for ShutdownCoordinator<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for ShutdownCoordinator<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShutdownCoordinator")
+            .field("hooks", &self.hooks.len())
+            .field("modules", &self.modules)
+            .field("module_loaders", &self.module_loaders)
+            .field("library_loaders", &self.library_loaders)
+            .field("libraries", &self.libraries)
+            .finish()
+    }
+}
+
+/// Computes the indices of the modules exporting the dependencies of `module`.
+fn dependency_indices<'interface, T>(
+    interface: &T,
+    modules: &[Module<'interface, Owned>],
+    module: &Module<'interface, Owned>,
+) -> Vec<usize>
+where
+    T: ModuleAPI<'interface>,
+{
+    let load = interface.get_load_dependencies(module).unwrap_or_default();
+    let runtime = interface
+        .get_runtime_dependencies(module)
+        .unwrap_or_default();
+
+    let mut indices: Vec<usize> = load
+        .iter()
+        .chain(runtime)
+        .filter_map(|dependency| {
+            let exporter = interface
+                .get_exported_interface_handle(dependency)
+                .ok()?
+                .as_handle();
+            modules.iter().position(|m| m.as_handle() == exporter)
+        })
+        .collect();
+    indices.sort_unstable();
+    indices.dedup();
+    indices
+}
+
+/// Orders the modules such that every module precedes its dependencies.
+///
+/// `dependencies[i]` lists the modules on which the `i`-th module depends. Independent
+/// modules are ordered in reverse registration order. Cycles are broken by the same rule.
+fn termination_order(dependencies: &[Vec<usize>]) -> Vec<usize> {
+    let mut remaining: Vec<usize> = (0..dependencies.len()).rev().collect();
+    let mut order = Vec::with_capacity(remaining.len());
+
+    while !remaining.is_empty() {
+        let position = remaining
+            .iter()
+            .position(|&i| {
+                !remaining
+                    .iter()
+                    .any(|&j| j != i && dependencies[j].contains(&i))
+            })
+            .unwrap_or(0);
+        order.push(remaining.remove(position));
+    }
+
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        termination_order, ShutdownCoordinator, ShutdownFailure, ShutdownReport, ShutdownStage,
+        ShutdownTarget,
+    };
+    use crate::ffi::errors::SimpleError;
+    use crate::ffi::library::{LibraryHandle, LoaderHandle as LibraryLoaderHandle};
+    use crate::ffi::module::{LoaderHandle as ModuleLoaderHandle, ModuleHandle};
+    use crate::ffi::record::{Call, Recording, ReplayInterface, Value};
+    use crate::ffi::FnId;
+    use crate::library::{self, Library};
+    use crate::module::{self, Module};
+    use crate::ownership::Owned;
+    use crate::{CBaseRef, Error};
+    use std::cell::RefCell;
+
+    fn call(id: FnId, handle: i32, result: Value) -> Call {
+        Call {
+            id,
+            args: vec![Value::Int(handle as i64)],
+            result,
+        }
+    }
+
+    fn ok() -> Value {
+        Value::Int(0)
+    }
+
+    fn dependency() -> Value {
+        let version = (0..6).map(|_| Value::Int(0)).collect();
+        Value::List(vec![
+            Value::Bytes(b"dependency".to_vec()),
+            Value::List(version),
+            Value::List(vec![]),
+        ])
+    }
+
+    #[test]
+    fn shutdown_order() {
+        // Module 2 depends on module 1, and module 3 fails to terminate.
+        let replay = ReplayInterface::new(Recording::from(vec![
+            call(FnId::ModuleGetLoadDependencies, 1, Value::List(vec![])),
+            call(FnId::ModuleGetRuntimeDependencies, 1, Value::List(vec![])),
+            call(
+                FnId::ModuleGetLoadDependencies,
+                2,
+                Value::List(vec![dependency()]),
+            ),
+            call(FnId::ModuleGetRuntimeDependencies, 2, Value::List(vec![])),
+            Call {
+                id: FnId::ModuleGetExportedInterfaceHandle,
+                args: vec![dependency()],
+                result: Value::Int(1),
+            },
+            call(FnId::ModuleGetLoadDependencies, 3, Value::List(vec![])),
+            call(FnId::ModuleGetRuntimeDependencies, 3, Value::List(vec![])),
+            call(FnId::ModuleFetchStatus, 3, Value::Int(2)),
+            call(
+                FnId::ModuleTerminate,
+                3,
+                Value::Err(String::from("Module busy")),
+            ),
+            call(FnId::ModuleFetchStatus, 2, Value::Int(2)),
+            call(FnId::ModuleTerminate, 2, ok()),
+            call(FnId::ModuleFetchStatus, 1, Value::Int(1)),
+            call(FnId::ModuleUnload, 2, ok()),
+            call(FnId::ModuleRemoveModule, 2, ok()),
+            call(
+                FnId::ModuleUnload,
+                1,
+                Value::Err(String::from("Unload failed")),
+            ),
+            call(FnId::ModuleUnregisterLoader, 5, ok()),
+            call(FnId::ModuleUnregisterLoader, 4, ok()),
+            call(FnId::LibraryUnregisterLoader, 6, ok()),
+            call(FnId::LibraryUnload, 7, ok()),
+        ]));
+        let mut interface = unsafe { CBaseRef::new(replay.interface()) };
+
+        let hooks = RefCell::new(Vec::new());
+        let mut coordinator = ShutdownCoordinator::new();
+        coordinator.add_hook(|| {
+            hooks.borrow_mut().push(0);
+            Ok(())
+        });
+        coordinator.add_hook(|| {
+            hooks.borrow_mut().push(1);
+            Err(Error::from(crate::ffi::errors::Error::from(
+                SimpleError::new("Hook failed"),
+            )))
+        });
+        unsafe {
+            for id in 1..=3 {
+                coordinator.add_module(Module::<Owned>::new(ModuleHandle { id }));
+            }
+            coordinator
+                .add_module_loader(module::Loader::<Owned>::new(ModuleLoaderHandle { id: 4 }));
+            coordinator
+                .add_module_loader(module::Loader::<Owned>::new(ModuleLoaderHandle { id: 5 }));
+            coordinator
+                .add_library_loader(library::Loader::<Owned>::new(LibraryLoaderHandle { id: 6 }));
+            coordinator.add_library(Library::<Owned>::new(LibraryHandle { id: 7 }));
+        }
+
+        let report = coordinator.shutdown(&mut interface);
+        assert_eq!(replay.finish(), Ok(()));
+        assert_eq!(*hooks.borrow(), vec![1, 0]);
+        assert_eq!(report.terminated(), [ModuleHandle { id: 2 }]);
+        assert_eq!(
+            report.to_string(),
+            "Shutdown completed with 3 failure(s)\n  \
+             Hooks: hook 1: Hook failed\n  \
+             Terminate: module 3: Module busy\n  \
+             Unload: module 1: Unload failed"
+        );
+    }
+
+    #[test]
+    fn dependents_first() {
+        // 0 <- 1 <- 2, 3 independent.
+        let dependencies = vec![vec![], vec![0], vec![1], vec![]];
+        assert_eq!(termination_order(&dependencies), vec![3, 2, 1, 0]);
+
+        // 2 depends on 0 and 1, 1 depends on 0.
+        let dependencies = vec![vec![], vec![0], vec![0, 1]];
+        assert_eq!(termination_order(&dependencies), vec![2, 1, 0]);
+
+        // Registered before its dependent.
+        let dependencies = vec![vec![1], vec![]];
+        assert_eq!(termination_order(&dependencies), vec![0, 1]);
+
+        // Cycle.
+        let dependencies = vec![vec![1], vec![0]];
+        assert_eq!(termination_order(&dependencies), vec![1, 0]);
+    }
+
+    #[test]
+    #[cfg(feature = "unwind_internal")]
+    fn catch_shutdown() {
+        use super::Interruption;
+        use crate::extensions::unwind_internal::default_context::{
+            DefaultContext, PanicSignal, ShutdownSignal,
+        };
+        use crate::extensions::unwind_internal::{
+            Signal, UnwindInternalAPI, UnwindInternalContextRef,
+        };
+        use crate::CBaseAPI;
+
+        #[derive(Default)]
+        struct Extension {
+            context: Option<UnwindInternalContextRef>,
+        }
+
+        impl<'interface> UnwindInternalAPI<'interface> for Extension {
+            fn get_context(
+                &self,
+                _interface: &impl CBaseAPI<'interface>,
+            ) -> Option<UnwindInternalContextRef> {
+                self.context
+            }
+
+            fn set_context(
+                &mut self,
+                _interface: &mut impl CBaseAPI<'interface>,
+                context: Option<UnwindInternalContextRef>,
+            ) {
+                self.context = context
+            }
+        }
+
+        let replay =
+            ReplayInterface::new(Recording::from(vec![call(FnId::LibraryUnload, 1, ok())]));
+        let mut interface = unsafe { CBaseRef::new(replay.interface()) };
+        let context = DefaultContext::default();
+        let mut extension = Extension::default();
+        let mut coordinator = ShutdownCoordinator::new();
+        coordinator.add_library(unsafe { Library::<Owned>::new(LibraryHandle { id: 1 }) });
+
+        let result = coordinator.catch_shutdown(&context, &mut extension, &mut interface, |_| 5);
+        assert!(matches!(result, Ok(5)));
+
+        let result = coordinator.catch_shutdown(&context, &mut extension, &mut interface, |_| {
+            std::panic::panic_any(PanicSignal { error: None })
+        });
+        assert!(matches!(
+            result,
+            Err(Interruption::Signal(Signal::Panic(None)))
+        ));
+        assert_eq!(replay.remaining(), 1);
+
+        // Only a shutdown signal releases the resources.
+        let result = coordinator.catch_shutdown(&context, &mut extension, &mut interface, |_| {
+            std::panic::panic_any(ShutdownSignal {})
+        });
+        match result {
+            Err(Interruption::Shutdown(report)) => assert!(report.is_success()),
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert_eq!(replay.finish(), Ok(()));
+    }
+
+    #[test]
+    fn report() {
+        let mut report = ShutdownReport::default();
+        assert!(report.is_success());
+        assert_eq!(report.to_string(), "Shutdown completed");
+
+        let handle = ModuleHandle { id: 3 };
+        let error = Error::from(crate::ffi::errors::Error::from(SimpleError::new(
+            "Module busy",
+        )));
+        let result: Result<(), _> = Err(error);
+        assert!(report
+            .check(
+                ShutdownStage::Terminate,
+                ShutdownTarget::Module(handle),
+                result
+            )
+            .is_none());
+
+        assert!(matches!(
+            report.failures(),
+            [ShutdownFailure {
+                stage: ShutdownStage::Terminate,
+                target: ShutdownTarget::Module(ModuleHandle { id: 3 }),
+                ..
+            }]
+        ));
+        assert_eq!(
+            report.to_string(),
+            "Shutdown completed with 1 failure(s)\n  Terminate: module 3: Module busy"
+        );
+        assert!(report.into_result().is_err());
+    }
+}
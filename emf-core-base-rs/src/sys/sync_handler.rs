@@ -1,7 +1,49 @@
 //! API of a sync handler.
-use crate::ffi::sys::sync_handler::{SyncHandlerBinding, SyncHandlerInterface};
-use crate::ffi::Bool;
+//!
+//! Custom sync handlers can be built from any [RawMutex] with an [OwnedSyncHandler].
+use crate::ffi::collections::NonNullConst;
+use crate::ffi::sys::api::SysBinding;
+use crate::ffi::sys::sync_handler::{
+    SyncHandler as SyncHandlerFFI, SyncHandlerBinding, SyncHandlerInterface, SyncHandlerVTable,
+};
+use crate::ffi::{Bool, TypeWrapper};
+use crate::guard::ffi_guard;
 use std::marker::PhantomData;
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::time::{Duration, Instant};
+
+mod raw_mutex;
+mod reentrant_mutex;
+mod spin_lock;
+
+#[cfg(all(
+    target_os = "linux",
+    any(
+        target_arch = "x86_64",
+        target_arch = "x86",
+        target_arch = "arm",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    )
+))]
+mod futex_lock;
+
+pub use raw_mutex::RawMutex;
+pub use reentrant_mutex::ReentrantMutex;
+pub use spin_lock::SpinLock;
+
+#[cfg(all(
+    target_os = "linux",
+    any(
+        target_arch = "x86_64",
+        target_arch = "x86",
+        target_arch = "arm",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    )
+))]
+pub use futex_lock::FutexLock;
 
 /// A borrowed sync handler.
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
@@ -61,6 +103,25 @@ pub trait SyncHandlerAPI<'a> {
     /// Direct usage of a [SyncHandlerAPI] may break some invariants
     /// of the sys api, if not handled with care.
     unsafe fn unlock(&self);
+
+    /// Locks the synchronisation handler with a timeout.
+    ///
+    /// The calling thread is stalled until the lock can be acquired
+    /// or the `timeout` has elapsed. The default implementation calls
+    /// [SyncHandlerAPI::try_lock] with backoff.
+    ///
+    /// # Return
+    ///
+    /// [true] on success and [false] otherwise.
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    /// Direct usage of a [SyncHandlerAPI] may break some invariants
+    /// of the sys api, if not handled with care.
+    unsafe fn lock_timeout(&self, timeout: Duration) -> bool {
+        try_lock_until(Instant::now().checked_add(timeout), || self.try_lock())
+    }
 }
 
 impl SyncHandlerAPI<'_> for SyncHandler<'_> {
@@ -93,4 +154,486 @@ impl SyncHandlerAPI<'_> for SyncHandler<'_> {
     unsafe fn unlock(&self) {
         self.handler.unlock()
     }
+
+    /// Locks the synchronisation handler with a timeout.
+    ///
+    /// Waits on the mutex directly, if the handler has been created by an
+    /// [OwnedSyncHandler], and calls [SyncHandlerAPI::try_lock] with backoff otherwise.
+    #[inline]
+    unsafe fn lock_timeout(&self, timeout: Duration) -> bool {
+        match owned_header(&self.handler) {
+            Some(header) => (header.as_ref().vtable.try_lock_for)(header, timeout),
+            None => try_lock_until(Instant::now().checked_add(timeout), || self.try_lock()),
+        }
+    }
+}
+
+/// VTable shared by the handlers of all [OwnedSyncHandler]s.
+///
+/// Its functions dispatch through the [ExtendedVTable] in the [Header] of the handler.
+static OWNED_VTABLE: SyncHandlerVTable = SyncHandlerVTable {
+    lock_fn: TypeWrapper(owned_lock_fn),
+    try_lock_fn: TypeWrapper(owned_try_lock_fn),
+    unlock_fn: TypeWrapper(owned_unlock_fn),
+};
+
+/// Functions of an [OwnedSyncHandler], extending the [SyncHandlerVTable] with timed locking.
+#[derive(Debug)]
+struct ExtendedVTable {
+    lock: unsafe fn(NonNull<Header>),
+    try_lock: unsafe fn(NonNull<Header>) -> bool,
+    unlock: unsafe fn(NonNull<Header>),
+    try_lock_for: unsafe fn(NonNull<Header>, Duration) -> bool,
+}
+
+/// Start of the handler of an [OwnedSyncHandler].
+///
+/// The layout of the handler is only known for the handlers using the [OWNED_VTABLE]
+/// of this copy of the crate, so that the handlers of other modules are never read.
+#[repr(C)]
+#[derive(Debug)]
+struct Header {
+    vtable: &'static ExtendedVTable,
+}
+
+/// Handler of an [OwnedSyncHandler].
+#[repr(C)]
+#[derive(Debug)]
+struct Inner<M> {
+    header: Header,
+    mutex: M,
+}
+
+/// Fetches the header of a handler created by an [OwnedSyncHandler].
+#[inline]
+fn owned_header(handler: &SyncHandlerInterface) -> Option<NonNull<Header>> {
+    if handler.vtable == NonNullConst::from(&OWNED_VTABLE) {
+        handler.handler.map(NonNull::cast)
+    } else {
+        None
+    }
+}
+
+unsafe extern "C-unwind" fn owned_lock_fn(handler: Option<NonNull<SyncHandlerFFI>>) {
+    ffi_guard(|| {
+        let header = handler.unwrap().cast::<Header>();
+        (header.as_ref().vtable.lock)(header)
+    })
+}
+
+unsafe extern "C-unwind" fn owned_try_lock_fn(handler: Option<NonNull<SyncHandlerFFI>>) -> Bool {
+    ffi_guard(|| {
+        let header = handler.unwrap().cast::<Header>();
+        if (header.as_ref().vtable.try_lock)(header) {
+            Bool::True
+        } else {
+            Bool::False
+        }
+    })
+}
+
+unsafe extern "C-unwind" fn owned_unlock_fn(handler: Option<NonNull<SyncHandlerFFI>>) {
+    ffi_guard(|| {
+        let header = handler.unwrap().cast::<Header>();
+        (header.as_ref().vtable.unlock)(header)
+    })
+}
+
+/// A sync handler owning its [RawMutex].
+///
+/// The mutex is pinned on the heap, so that the interface returned by
+/// [SyncHandlerAPI::to_raw] stays valid for the lifetime of the handler.
+/// Unlike other handlers, the handler supports waiting for the lock with a timeout,
+/// e.g. through [CBaseAccess::lock_timeout](crate::CBaseAccess::lock_timeout).
+#[derive(Debug)]
+pub struct OwnedSyncHandler<M: RawMutex> {
+    inner: Pin<Box<Inner<M>>>,
+}
+
+impl<M: RawMutex> OwnedSyncHandler<M> {
+    const EXTENDED_VTABLE: ExtendedVTable = ExtendedVTable {
+        lock: Self::lock_fn,
+        try_lock: Self::try_lock_fn,
+        unlock: Self::unlock_fn,
+        try_lock_for: Self::try_lock_for_fn,
+    };
+
+    /// Constructs a new sync handler from a mutex.
+    #[inline]
+    pub fn new(mutex: M) -> Self {
+        Self {
+            inner: Box::pin(Inner {
+                header: Header {
+                    vtable: &Self::EXTENDED_VTABLE,
+                },
+                mutex,
+            }),
+        }
+    }
+
+    /// Fetches a reference to the mutex.
+    #[inline]
+    pub fn mutex(&self) -> &M {
+        &self.inner.mutex
+    }
+
+    /// Leaks the handler, returning a borrowed handler valid for the rest of the program.
+    ///
+    /// Useful in combination with [SysAPI::set_sync_handler](crate::sys::SysAPI::set_sync_handler),
+    /// where the handler must outlive the interface.
+    #[inline]
+    pub fn leak(self) -> SyncHandler<'static> {
+        let handler = unsafe { SyncHandler::from_raw(self.to_raw()) };
+        std::mem::forget(self);
+        handler
+    }
+
+    unsafe fn mutex_of<'a>(header: NonNull<Header>) -> &'a M {
+        &header.cast::<Inner<M>>().as_ref().mutex
+    }
+
+    unsafe fn lock_fn(header: NonNull<Header>) {
+        Self::mutex_of(header).lock()
+    }
+
+    unsafe fn try_lock_fn(header: NonNull<Header>) -> bool {
+        Self::mutex_of(header).try_lock()
+    }
+
+    unsafe fn unlock_fn(header: NonNull<Header>) {
+        Self::mutex_of(header).unlock()
+    }
+
+    unsafe fn try_lock_for_fn(header: NonNull<Header>, timeout: Duration) -> bool {
+        Self::mutex_of(header).try_lock_for(timeout)
+    }
+}
+
+impl<M: RawMutex> Default for OwnedSyncHandler<M> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(M::INIT)
+    }
+}
+
+impl<'a, M: RawMutex> SyncHandlerAPI<'a> for OwnedSyncHandler<M> {
+    type Handler = SyncHandler<'a>;
+
+    #[inline]
+    fn to_raw(&self) -> SyncHandlerInterface {
+        SyncHandlerInterface {
+            handler: Some(NonNull::from(&self.inner.header).cast()),
+            vtable: NonNullConst::from(&OWNED_VTABLE),
+        }
+    }
+
+    #[inline]
+    unsafe fn from_raw(handler: SyncHandlerInterface) -> Self::Handler {
+        SyncHandler::from_raw(handler)
+    }
+
+    #[inline]
+    unsafe fn lock(&self) {
+        self.inner.mutex.lock()
+    }
+
+    #[inline]
+    unsafe fn try_lock(&self) -> bool {
+        self.inner.mutex.try_lock()
+    }
+
+    #[inline]
+    unsafe fn unlock(&self) {
+        self.inner.mutex.unlock()
+    }
+
+    #[inline]
+    unsafe fn lock_timeout(&self, timeout: Duration) -> bool {
+        self.inner.mutex.try_lock_for(timeout)
+    }
+}
+
+/// Calls `try_lock` with exponential backoff until it succeeds or the `deadline` is reached.
+///
+/// A `deadline` of [Option::None] never expires.
+pub(crate) fn try_lock_until(
+    deadline: Option<Instant>,
+    mut try_lock: impl FnMut() -> bool,
+) -> bool {
+    const SPIN_LIMIT: u32 = 6;
+
+    let mut step = 0;
+    loop {
+        if try_lock() {
+            return true;
+        }
+
+        if matches!(deadline, Some(deadline) if Instant::now() >= deadline) {
+            return false;
+        }
+
+        if step < SPIN_LIMIT {
+            for _ in 0..(1 << step) {
+                std::hint::spin_loop();
+            }
+            step += 1;
+        } else {
+            std::thread::yield_now();
+        }
+    }
+}
+
+/// Locks the interface with a timeout.
+///
+/// Waits on the active sync handler directly, if it has been created by an
+/// [OwnedSyncHandler], and falls back to [SysBinding::try_lock] with backoff otherwise.
+///
+/// # Safety
+///
+/// The function crosses the ffi boundary.
+pub(crate) unsafe fn lock_interface_timeout(
+    interface: &impl SysBinding,
+    timeout: Duration,
+) -> bool {
+    let deadline = Instant::now().checked_add(timeout);
+    loop {
+        // The active handler is read without holding the lock and is checked again
+        // once it has been locked.
+        let handler = interface.get_sync_handler();
+        if owned_header(&handler).is_none() {
+            return try_lock_until(deadline, || interface.try_lock() == Bool::True);
+        }
+
+        let remaining = deadline.map_or(Duration::MAX, |deadline| {
+            deadline.saturating_duration_since(Instant::now())
+        });
+        if !SyncHandler::from_raw(handler).lock_timeout(remaining) {
+            return false;
+        }
+
+        // The handler may have been swapped while waiting.
+        if interface.get_sync_handler() == handler {
+            return true;
+        }
+        handler.unlock();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ffi::collections::NonNullConst;
+    use crate::ffi::sys::sync_handler::{
+        SyncHandlerBinding, SyncHandlerInterface, SyncHandlerVTable,
+    };
+    use crate::ffi::{Bool, TypeWrapper};
+    use crate::sys::sync_handler::{
+        OwnedSyncHandler, RawMutex, ReentrantMutex, SpinLock, SyncHandler, SyncHandlerAPI,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    fn test_exclusion<M: RawMutex + 'static>() {
+        let handler = Arc::new(OwnedSyncHandler::<M>::default());
+        let counter = Arc::new(AtomicUsize::new(0));
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let handler = handler.clone();
+                let counter = counter.clone();
+                std::thread::spawn(move || {
+                    let raw = handler.to_raw();
+                    for _ in 0..250 {
+                        unsafe { raw.lock() };
+                        let value = counter.load(Ordering::Relaxed);
+                        std::thread::yield_now();
+                        counter.store(value + 1, Ordering::Relaxed);
+                        unsafe { raw.unlock() };
+                    }
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(counter.load(Ordering::Relaxed), 1000);
+    }
+
+    fn test_try_lock<M: RawMutex>() {
+        let handler = OwnedSyncHandler::<M>::default();
+        let raw = handler.to_raw();
+
+        unsafe {
+            assert_eq!(raw.try_lock(), Bool::True);
+            assert_eq!(raw.try_lock(), Bool::False);
+            assert!(!handler.try_lock());
+            raw.unlock();
+            assert!(handler.try_lock());
+            handler.unlock();
+        }
+    }
+
+    fn test_lock_timeout<M: RawMutex + 'static>() {
+        let handler = Arc::new(OwnedSyncHandler::<M>::default());
+        let timeout = Duration::from_millis(10);
+
+        unsafe {
+            assert!(handler.lock_timeout(Duration::from_nanos(0)));
+
+            let other = handler.clone();
+            let locked = std::thread::spawn(move || {
+                let start = Instant::now();
+                let locked = SyncHandler::from_raw(other.to_raw()).lock_timeout(timeout);
+                (locked, start.elapsed())
+            });
+            let (locked, elapsed) = locked.join().unwrap();
+            assert!(!locked);
+            assert!(elapsed >= timeout);
+
+            handler.unlock();
+        }
+
+        let other = handler.clone();
+        let locked = std::thread::spawn(move || unsafe {
+            let locked = other.lock_timeout(timeout);
+            other.unlock();
+            locked
+        });
+        assert!(locked.join().unwrap());
+    }
+
+    #[derive(Debug, Default)]
+    struct TimedMutex {
+        lock: SpinLock,
+        timed_locks: AtomicUsize,
+    }
+
+    unsafe impl RawMutex for TimedMutex {
+        const INIT: Self = Self {
+            lock: SpinLock::INIT,
+            timed_locks: AtomicUsize::new(0),
+        };
+
+        fn lock(&self) {
+            self.lock.lock()
+        }
+
+        fn try_lock(&self) -> bool {
+            self.lock.try_lock()
+        }
+
+        fn try_lock_for(&self, timeout: Duration) -> bool {
+            self.timed_locks.fetch_add(1, Ordering::Relaxed);
+            self.lock.try_lock_for(timeout)
+        }
+
+        unsafe fn unlock(&self) {
+            self.lock.unlock()
+        }
+    }
+
+    #[test]
+    fn timed_lock() {
+        static FOREIGN_VTABLE: SyncHandlerVTable = SyncHandlerVTable {
+            lock_fn: TypeWrapper(super::owned_lock_fn),
+            try_lock_fn: TypeWrapper(super::owned_try_lock_fn),
+            unlock_fn: TypeWrapper(super::owned_unlock_fn),
+        };
+
+        let handler = OwnedSyncHandler::<TimedMutex>::default();
+        let timeout = Duration::from_millis(1);
+
+        unsafe {
+            let owned = SyncHandler::from_raw(handler.to_raw());
+            assert!(owned.lock_timeout(timeout));
+            assert!(!owned.lock_timeout(timeout));
+            owned.unlock();
+            assert_eq!(handler.mutex().timed_locks.load(Ordering::Relaxed), 2);
+
+            // Handlers with an unknown vtable are never waited on directly.
+            let foreign = SyncHandler::from_raw(SyncHandlerInterface {
+                vtable: NonNullConst::from(&FOREIGN_VTABLE),
+                ..handler.to_raw()
+            });
+            assert!(foreign.lock_timeout(timeout));
+            assert!(!foreign.lock_timeout(timeout));
+            foreign.unlock();
+            assert_eq!(handler.mutex().timed_locks.load(Ordering::Relaxed), 2);
+        }
+    }
+
+    #[test]
+    fn try_lock_until() {
+        let mut attempts = 0;
+        assert!(super::try_lock_until(None, || {
+            attempts += 1;
+            attempts == 100
+        }));
+
+        let deadline = Instant::now() + Duration::from_millis(10);
+        assert!(!super::try_lock_until(Some(deadline), || false));
+        assert!(Instant::now() >= deadline);
+    }
+
+    #[test]
+    fn spin_lock() {
+        test_exclusion::<SpinLock>();
+        test_try_lock::<SpinLock>();
+        test_lock_timeout::<SpinLock>();
+    }
+
+    #[test]
+    #[cfg(all(
+        target_os = "linux",
+        any(
+            target_arch = "x86_64",
+            target_arch = "x86",
+            target_arch = "arm",
+            target_arch = "aarch64",
+            target_arch = "riscv64"
+        )
+    ))]
+    fn futex_lock() {
+        use crate::sys::sync_handler::FutexLock;
+
+        test_exclusion::<FutexLock>();
+        test_try_lock::<FutexLock>();
+        test_lock_timeout::<FutexLock>();
+    }
+
+    #[test]
+    fn reentrant_mutex() {
+        test_exclusion::<ReentrantMutex<SpinLock>>();
+        test_lock_timeout::<ReentrantMutex<SpinLock>>();
+
+        let handler = Arc::new(OwnedSyncHandler::<ReentrantMutex<SpinLock>>::default());
+        let raw = handler.to_raw();
+
+        unsafe {
+            raw.lock();
+            raw.lock();
+            assert_eq!(raw.try_lock(), Bool::True);
+            assert!(handler.mutex().is_owned_by_current_thread());
+
+            let other = handler.clone();
+            let locked = std::thread::spawn(move || other.try_lock());
+            assert!(!locked.join().unwrap());
+
+            raw.unlock();
+            raw.unlock();
+            assert!(handler.mutex().is_owned_by_current_thread());
+            raw.unlock();
+            assert!(!handler.mutex().is_owned_by_current_thread());
+        }
+
+        let other = handler.clone();
+        let locked = std::thread::spawn(move || unsafe {
+            let locked = other.try_lock();
+            other.unlock();
+            locked
+        });
+        assert!(locked.join().unwrap());
+    }
 }
@@ -0,0 +1,152 @@
+//! Futex-based lock.
+use crate::sys::sync_handler::RawMutex;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+
+const UNLOCKED: u32 = 0;
+const LOCKED: u32 = 1;
+const CONTENDED: u32 = 2;
+
+/// A lock which puts waiting threads to sleep with the `futex` system call.
+///
+/// Uncontended locking and unlocking is performed in user space.
+/// The kernel is only involved if a thread has to wait for the lock.
+#[derive(Debug, Default)]
+pub struct FutexLock {
+    state: AtomicU32,
+}
+
+impl FutexLock {
+    /// Constructs a new unlocked futex lock.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU32::new(UNLOCKED),
+        }
+    }
+
+    #[cold]
+    fn lock_contended(&self) {
+        while self.state.swap(CONTENDED, Ordering::Acquire) != UNLOCKED {
+            futex::wait(&self.state, CONTENDED, None);
+        }
+    }
+}
+
+unsafe impl RawMutex for FutexLock {
+    const INIT: Self = Self::new();
+
+    #[inline]
+    fn lock(&self) {
+        if self
+            .state
+            .compare_exchange(UNLOCKED, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            self.lock_contended()
+        }
+    }
+
+    #[inline]
+    fn try_lock(&self) -> bool {
+        self.state
+            .compare_exchange(UNLOCKED, LOCKED, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    fn try_lock_for(&self, timeout: Duration) -> bool {
+        if self.try_lock() {
+            return true;
+        }
+
+        let deadline = Instant::now().checked_add(timeout);
+        loop {
+            if self.state.swap(CONTENDED, Ordering::Acquire) == UNLOCKED {
+                return true;
+            }
+
+            let remaining = match deadline {
+                None => None,
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return false;
+                    }
+                    Some(deadline - now)
+                }
+            };
+            futex::wait(&self.state, CONTENDED, remaining);
+        }
+    }
+
+    #[inline]
+    unsafe fn unlock(&self) {
+        if self.state.swap(UNLOCKED, Ordering::Release) == CONTENDED {
+            futex::wake_one(&self.state)
+        }
+    }
+}
+
+mod futex {
+    use std::convert::TryFrom;
+    use std::os::raw::{c_int, c_long};
+    use std::sync::atomic::AtomicU32;
+    use std::time::Duration;
+
+    #[cfg(target_arch = "x86_64")]
+    const SYS_FUTEX: c_long = 202;
+    #[cfg(any(target_arch = "x86", target_arch = "arm"))]
+    const SYS_FUTEX: c_long = 240;
+    #[cfg(any(target_arch = "aarch64", target_arch = "riscv64"))]
+    const SYS_FUTEX: c_long = 98;
+
+    const FUTEX_WAIT_PRIVATE: c_int = 128;
+    const FUTEX_WAKE_PRIVATE: c_int = 129;
+
+    #[repr(C)]
+    struct Timespec {
+        tv_sec: c_long,
+        tv_nsec: c_long,
+    }
+
+    extern "C" {
+        fn syscall(num: c_long, ...) -> c_long;
+    }
+
+    /// Blocks the thread while `futex` contains `expected`, or until the `timeout` elapses.
+    ///
+    /// Spurious wake-ups are possible.
+    #[inline]
+    pub fn wait(futex: &AtomicU32, expected: u32, timeout: Option<Duration>) {
+        let timeout = timeout.map(|timeout| Timespec {
+            tv_sec: c_long::try_from(timeout.as_secs()).unwrap_or(c_long::MAX),
+            tv_nsec: timeout.subsec_nanos() as c_long,
+        });
+        let timeout = timeout
+            .as_ref()
+            .map_or(std::ptr::null(), |timeout| timeout as *const Timespec);
+
+        unsafe {
+            syscall(
+                SYS_FUTEX,
+                futex as *const AtomicU32,
+                FUTEX_WAIT_PRIVATE,
+                expected,
+                timeout,
+            );
+        }
+    }
+
+    /// Wakes one thread waiting on `futex`.
+    #[inline]
+    pub fn wake_one(futex: &AtomicU32) {
+        unsafe {
+            syscall(
+                SYS_FUTEX,
+                futex as *const AtomicU32,
+                FUTEX_WAKE_PRIVATE,
+                1 as c_int,
+            );
+        }
+    }
+}
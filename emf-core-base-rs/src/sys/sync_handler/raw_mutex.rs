@@ -0,0 +1,49 @@
+//! Raw mutex abstraction.
+use crate::sys::sync_handler::try_lock_until;
+use std::time::{Duration, Instant};
+
+/// Basic mutual exclusion primitive.
+///
+/// Any type implementing this trait can be turned into a sync handler
+/// with an [OwnedSyncHandler](super::OwnedSyncHandler).
+///
+/// # Safety
+///
+/// Implementations must guarantee that at most one thread holds the lock
+/// at any given time. The mutex may be locked and unlocked from different
+/// threads, as long as every call to [RawMutex::unlock] is preceded by
+/// a successful lock operation.
+pub unsafe trait RawMutex: Send + Sync {
+    /// Value of an unlocked mutex.
+    const INIT: Self;
+
+    /// Locks the mutex.
+    ///
+    /// The calling thread is stalled until the lock can be acquired.
+    fn lock(&self);
+
+    /// Tries to lock the mutex.
+    ///
+    /// # Return
+    ///
+    /// [true] on success and [false] otherwise.
+    fn try_lock(&self) -> bool;
+
+    /// Tries to lock the mutex, waiting at most `timeout`.
+    ///
+    /// The default implementation calls [RawMutex::try_lock] with backoff.
+    ///
+    /// # Return
+    ///
+    /// [true] on success and [false] otherwise.
+    fn try_lock_for(&self, timeout: Duration) -> bool {
+        try_lock_until(Instant::now().checked_add(timeout), || self.try_lock())
+    }
+
+    /// Unlocks the mutex.
+    ///
+    /// # Safety
+    ///
+    /// The mutex must be locked.
+    unsafe fn unlock(&self);
+}
@@ -0,0 +1,116 @@
+//! Reentrant mutex.
+use crate::sys::sync_handler::RawMutex;
+use std::cell::UnsafeCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// A mutex which can be locked multiple times by the same thread.
+///
+/// Wraps any [RawMutex] and keeps track of the owning thread.
+/// The underlying mutex is released once every lock operation has been
+/// matched by a call to [RawMutex::unlock].
+///
+/// As a sync handler, it allows callbacks executed under the lock
+/// to lock the interface again, instead of deadlocking.
+#[derive(Debug, Default)]
+pub struct ReentrantMutex<M: RawMutex> {
+    mutex: M,
+    owner: AtomicUsize,
+    count: UnsafeCell<usize>,
+}
+
+unsafe impl<M: RawMutex> Sync for ReentrantMutex<M> {}
+
+impl<M: RawMutex> ReentrantMutex<M> {
+    /// Constructs a new reentrant mutex from an unlocked mutex.
+    #[inline]
+    pub const fn new(mutex: M) -> Self {
+        Self {
+            mutex,
+            owner: AtomicUsize::new(0),
+            count: UnsafeCell::new(0),
+        }
+    }
+
+    /// Checks whether the calling thread holds the lock.
+    #[inline]
+    pub fn is_owned_by_current_thread(&self) -> bool {
+        self.owner.load(Ordering::Relaxed) == current_thread_id()
+    }
+
+    #[inline]
+    fn increment_count(&self) {
+        unsafe {
+            let count = &mut *self.count.get();
+            *count = count
+                .checked_add(1)
+                .expect("ReentrantMutex lock count overflow");
+        }
+    }
+
+    #[inline]
+    fn acquire(&self, id: usize) {
+        self.owner.store(id, Ordering::Relaxed);
+        unsafe { *self.count.get() = 1 };
+    }
+}
+
+unsafe impl<M: RawMutex> RawMutex for ReentrantMutex<M> {
+    const INIT: Self = Self::new(M::INIT);
+
+    #[inline]
+    fn lock(&self) {
+        let id = current_thread_id();
+        if self.owner.load(Ordering::Relaxed) == id {
+            self.increment_count();
+        } else {
+            self.mutex.lock();
+            self.acquire(id);
+        }
+    }
+
+    #[inline]
+    fn try_lock(&self) -> bool {
+        let id = current_thread_id();
+        if self.owner.load(Ordering::Relaxed) == id {
+            self.increment_count();
+            true
+        } else if self.mutex.try_lock() {
+            self.acquire(id);
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn try_lock_for(&self, timeout: Duration) -> bool {
+        let id = current_thread_id();
+        if self.owner.load(Ordering::Relaxed) == id {
+            self.increment_count();
+            true
+        } else if self.mutex.try_lock_for(timeout) {
+            self.acquire(id);
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    unsafe fn unlock(&self) {
+        let count = &mut *self.count.get();
+        *count -= 1;
+        if *count == 0 {
+            self.owner.store(0, Ordering::Relaxed);
+            self.mutex.unlock();
+        }
+    }
+}
+
+/// Returns a non-zero value, unique among all running threads.
+#[inline]
+fn current_thread_id() -> usize {
+    thread_local!(static KEY: u8 = const { 0 });
+    KEY.with(|key| key as *const u8 as usize)
+}
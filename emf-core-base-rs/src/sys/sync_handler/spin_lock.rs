@@ -0,0 +1,51 @@
+//! Spin lock.
+use crate::sys::sync_handler::RawMutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A simple spin lock.
+///
+/// Waiting threads busy-wait until the lock is released,
+/// which makes it suitable only for short critical sections.
+#[derive(Debug, Default)]
+pub struct SpinLock {
+    locked: AtomicBool,
+}
+
+impl SpinLock {
+    /// Constructs a new unlocked spin lock.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+        }
+    }
+}
+
+unsafe impl RawMutex for SpinLock {
+    const INIT: Self = Self::new();
+
+    #[inline]
+    fn lock(&self) {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            while self.locked.load(Ordering::Relaxed) {
+                std::hint::spin_loop();
+            }
+        }
+    }
+
+    #[inline]
+    fn try_lock(&self) -> bool {
+        self.locked
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    #[inline]
+    unsafe fn unlock(&self) {
+        self.locked.store(false, Ordering::Release)
+    }
+}
@@ -0,0 +1,301 @@
+//! Instrumentation of the interface calls.
+//!
+//! With the `tracing` feature enabled, every call to the interface is executed inside of a
+//! span of the `emf_core_base_rs` target at the `TRACE` level. The spans record the
+//! arguments of the call, the elapsed time in `elapsed_us` and the message of a returned
+//! error in `error`. Acquiring and releasing the interface lock emit events recording the
+//! time spent waiting for the lock in `wait_us` and the time it was held in `hold_us`.
+//!
+//! Without the feature the instrumentation compiles down to the plain calls.
+
+/// Executes `$call` inside of a span named `$name` with the optional `[fields]`.
+macro_rules! traced {
+    ($name:literal, $([$($field:tt)+],)? $call:block) => {{
+        #[cfg(feature = "tracing")]
+        {
+            crate::trace::call(
+                tracing::trace_span!(
+                    target: crate::trace::TARGET,
+                    $name,
+                    $($($field)+,)?
+                    elapsed_us = tracing::field::Empty,
+                    error = tracing::field::Empty,
+                ),
+                || $call,
+            )
+        }
+        #[cfg(not(feature = "tracing"))]
+        $call
+    }};
+}
+
+#[cfg(feature = "tracing")]
+pub(crate) use tracing_impl::*;
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) use noop_impl::*;
+
+#[cfg(feature = "tracing")]
+mod tracing_impl {
+    use crate::ffi::collections::{NonNullConst, Result};
+    use crate::ffi::errors::Error;
+    use crate::ffi::library::OSPathString;
+    use std::cell::RefCell;
+    use std::fmt::{Display, Formatter};
+    use std::time::Instant;
+
+    /// Target of the spans and events.
+    pub(crate) const TARGET: &str = "emf_core_base_rs";
+
+    thread_local!(pub(super) static LOCKED_AT: RefCell<Vec<Instant>> = const { RefCell::new(Vec::new()) });
+
+    /// Point in time, at which an operation started.
+    pub(crate) type Timestamp = Instant;
+
+    /// Records the value returned by a call in its span.
+    pub(crate) trait Record {
+        /// Records the value in `span`.
+        fn record(&self, span: &tracing::Span);
+    }
+
+    impl<T> Record for T {
+        #[inline]
+        default fn record(&self, _span: &tracing::Span) {}
+    }
+
+    impl<T> Record for Result<T, Error> {
+        #[inline]
+        fn record(&self, span: &tracing::Span) {
+            if let Result::Err(e) = self {
+                span.record("error", tracing::field::display(e));
+            }
+        }
+    }
+
+    /// Executes `f` inside of `span`, recording the elapsed time and errors.
+    #[inline]
+    pub(crate) fn call<R>(span: tracing::Span, f: impl FnOnce() -> R) -> R {
+        if span.is_disabled() {
+            return f();
+        }
+
+        let _entered = span.enter();
+        let start = Instant::now();
+        let result = f();
+        span.record("elapsed_us", start.elapsed().as_micros() as u64);
+        result.record(&span);
+        result
+    }
+
+    /// Fetches the current point in time.
+    #[inline]
+    pub(crate) fn now() -> Timestamp {
+        Instant::now()
+    }
+
+    /// Records the acquisition of the interface lock, which was requested at `start`.
+    #[inline]
+    pub(crate) fn lock_acquired(start: Timestamp) {
+        let now = Instant::now();
+        LOCKED_AT.with(|locked_at| locked_at.borrow_mut().push(now));
+        tracing::trace!(
+            target: TARGET,
+            wait_us = (now - start).as_micros() as u64,
+            "interface locked"
+        );
+    }
+
+    /// Records the release of the interface lock.
+    #[inline]
+    pub(crate) fn lock_released() {
+        // The lock may have been acquired without the instrumented functions.
+        if let Some(locked_at) = LOCKED_AT.with(|locked_at| locked_at.borrow_mut().pop()) {
+            tracing::trace!(
+                target: TARGET,
+                hold_us = locked_at.elapsed().as_micros() as u64,
+                "interface unlocked"
+            );
+        }
+    }
+
+    /// Displays the value behind a pointer.
+    #[derive(Debug)]
+    pub(crate) struct Pointee<T>(NonNullConst<T>);
+
+    /// Displays the value behind `ptr`.
+    #[inline]
+    pub(crate) fn pointee<T>(ptr: NonNullConst<T>) -> Pointee<T> {
+        Pointee(ptr)
+    }
+
+    impl<T: Display> Display for Pointee<T> {
+        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+            unsafe { Display::fmt(self.0.as_ref(), f) }
+        }
+    }
+
+    /// Converts a path to a lossy string.
+    #[cfg(unix)]
+    pub(crate) fn path(path: &OSPathString) -> String {
+        String::from_utf8_lossy(path.as_ref()).into_owned()
+    }
+
+    /// Converts a path to a lossy string.
+    #[cfg(windows)]
+    pub(crate) fn path(path: &OSPathString) -> String {
+        String::from_utf16_lossy(path.as_ref())
+    }
+
+    /// Converts a byte string to a lossy string.
+    #[inline]
+    pub(crate) fn string(string: &impl AsRef<[u8]>) -> String {
+        String::from_utf8_lossy(string.as_ref()).into_owned()
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod noop_impl {
+    /// Point in time, at which an operation started.
+    #[derive(Debug, Copy, Clone)]
+    pub(crate) struct Timestamp;
+
+    /// Fetches the current point in time.
+    #[inline]
+    pub(crate) fn now() -> Timestamp {
+        Timestamp
+    }
+
+    /// Records the acquisition of the interface lock, which was requested at `start`.
+    #[inline]
+    pub(crate) fn lock_acquired(_start: Timestamp) {}
+
+    /// Records the release of the interface lock.
+    #[inline]
+    pub(crate) fn lock_released() {}
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use crate::ffi::collections::Result;
+    use crate::ffi::errors::{Error, StaticError};
+    use std::fmt::Debug;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    type Fields = Vec<(&'static str, String)>;
+
+    /// Subscriber recording the spans and events.
+    #[derive(Debug, Default, Clone)]
+    struct Recorder {
+        spans: Arc<Mutex<Vec<(&'static str, Fields)>>>,
+        events: Arc<Mutex<Vec<Fields>>>,
+    }
+
+    struct Visitor<'a>(&'a mut Fields);
+
+    impl Visit for Visitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0.push((field.name(), format!("{:?}", value)))
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut fields = Vec::new();
+            span.record(&mut Visitor(&mut fields));
+            let mut spans = self.spans.lock().unwrap();
+            spans.push((span.metadata().name(), fields));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            let (_, fields) = &mut spans[span.into_u64() as usize - 1];
+            values.record(&mut Visitor(fields));
+        }
+
+        fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut fields = Vec::new();
+            event.record(&mut Visitor(&mut fields));
+            self.events.lock().unwrap().push(fields);
+        }
+
+        fn enter(&self, _span: &Id) {}
+
+        fn exit(&self, _span: &Id) {}
+    }
+
+    fn field<'a>(fields: &'a Fields, name: &str) -> Option<&'a str> {
+        fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn spans() {
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            let value: i32 = traced!("test.ok", [handle = 5], { 1 });
+            assert_eq!(value, 1);
+            let result: Result<i32, Error> = traced!("test.err", {
+                Result::Err(Error::from(StaticError::new("My error message!")))
+            });
+            assert!(result.into_rust().is_err());
+        });
+
+        let spans = recorder.spans.lock().unwrap();
+        assert_eq!(spans.len(), 2);
+
+        let (name, fields) = &spans[0];
+        assert_eq!(*name, "test.ok");
+        assert_eq!(field(fields, "handle"), Some("5"));
+        assert!(field(fields, "elapsed_us").is_some());
+        assert_eq!(field(fields, "error"), None);
+
+        let (name, fields) = &spans[1];
+        assert_eq!(*name, "test.err");
+        assert_eq!(field(fields, "error"), Some("My error message!"));
+    }
+
+    #[test]
+    fn lock_holder() {
+        let recorder = Recorder::default();
+        tracing::subscriber::with_default(recorder.clone(), || {
+            // Releasing a lock acquired without the instrumentation is not reported.
+            super::lock_released();
+
+            super::lock_acquired(super::now());
+            super::lock_acquired(super::now());
+            super::lock_released();
+            super::lock_released();
+            super::lock_released();
+        });
+        assert!(super::tracing_impl::LOCKED_AT.with(|locked_at| locked_at.borrow().is_empty()));
+
+        let events = recorder.events.lock().unwrap();
+        let messages: Vec<_> = events
+            .iter()
+            .map(|fields| field(fields, "message").unwrap())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "interface locked",
+                "interface locked",
+                "interface unlocked",
+                "interface unlocked",
+            ]
+        );
+        assert!(field(&events[0], "wait_us").is_some());
+        assert!(field(&events[2], "hold_us").is_some());
+    }
+}
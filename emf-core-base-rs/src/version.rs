@@ -1,7 +1,7 @@
 //! Version api.
 //!
 //! The version api is exposed by the [VersionAPI] trait.
-use crate::ffi::collections::{ConstSpan, MutSpan, NonNullConst};
+use crate::ffi::collections::{NonNullConst, SpanMut, SpanRef};
 use crate::ffi::version::api::VersionBinding;
 use crate::ffi::Bool;
 use std::cmp::Ordering;
@@ -228,7 +228,7 @@ where
     #[inline]
     fn from_string(&self, buffer: impl AsRef<str>) -> Result<Version, Error<Owned>> {
         unsafe {
-            <T as VersionBinding>::from_string(self, ConstSpan::from(buffer.as_ref()))
+            <T as VersionBinding>::from_string(self, SpanRef::from(buffer.as_ref()))
                 .into_rust()
                 .map_err(From::from)
         }
@@ -259,7 +259,7 @@ where
             <T as VersionBinding>::as_string_short(
                 self,
                 NonNullConst::from(version),
-                MutSpan::from(buffer.as_mut()),
+                SpanMut::from(buffer.as_mut().as_bytes_mut()),
             )
             .into_rust()
             .map_err(From::from)
@@ -276,7 +276,7 @@ where
             <T as VersionBinding>::as_string_long(
                 self,
                 NonNullConst::from(version),
-                MutSpan::from(buffer.as_mut()),
+                SpanMut::from(buffer.as_mut().as_bytes_mut()),
             )
             .into_rust()
             .map_err(From::from)
@@ -293,7 +293,7 @@ where
             <T as VersionBinding>::as_string_full(
                 self,
                 NonNullConst::from(version),
-                MutSpan::from(buffer.as_mut()),
+                SpanMut::from(buffer.as_mut().as_bytes_mut()),
             )
             .into_rust()
             .map_err(From::from)
@@ -303,7 +303,7 @@ where
     #[inline]
     fn string_is_valid(&self, version_string: impl AsRef<str>) -> bool {
         unsafe {
-            <T as VersionBinding>::string_is_valid(self, ConstSpan::from(version_string.as_ref()))
+            <T as VersionBinding>::string_is_valid(self, SpanRef::from(version_string.as_ref()))
                 == Bool::True
         }
     }
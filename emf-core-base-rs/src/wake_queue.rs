@@ -0,0 +1,327 @@
+//! Wake-up queues for asynchronous locking.
+//!
+//! Futures waiting for the lock of an interface register their [Waker] in the queue
+//! of that interface. Every unlock performed by this crate wakes the first waiter in
+//! line of the unlocked interface, which then retries acquiring the lock with `try_lock`.
+//!
+//! Unlocks performed by the host or by other modules are not observed by the queues.
+//! As a fallback, a background thread periodically wakes the first waiter of every
+//! queue, so that those unlocks are noticed after at most [REPOLL_INTERVAL].
+use crate::sys::sync_handler::{RawMutex, SpinLock};
+use crate::{CBaseInterfaceInfo, CBaseRef};
+use std::cell::UnsafeCell;
+use std::collections::BTreeMap;
+use std::sync::atomic::{fence, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::task::{Context, Poll, Waker};
+use std::thread::Thread;
+use std::time::Duration;
+
+/// Queues of the futures waiting for an interface lock.
+static LOCK_QUEUE: WakeQueue = WakeQueue::new();
+
+/// Interval in which the waiting futures re-poll the lock.
+const REPOLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Identifies the queue of an interface.
+///
+/// Copies of the same interface share their `base_module` and therefore their queue.
+#[inline]
+pub(crate) fn queue_id(interface: &CBaseRef<'_>) -> usize {
+    let interface = interface.internal_interface();
+    match interface.base_module {
+        Some(base_module) => base_module.as_ptr() as usize,
+        None => interface.vtable.as_ptr() as usize,
+    }
+}
+
+#[derive(Debug)]
+struct QueueState {
+    next_key: usize,
+    queues: BTreeMap<usize, Vec<(usize, Waker)>>,
+}
+
+/// FIFO queues of wakers, one per interface.
+#[derive(Debug)]
+struct WakeQueue {
+    lock: SpinLock,
+    len: AtomicUsize,
+    state: UnsafeCell<QueueState>,
+}
+
+unsafe impl Sync for WakeQueue {}
+
+impl WakeQueue {
+    const fn new() -> Self {
+        Self {
+            lock: SpinLock::new(),
+            len: AtomicUsize::new(0),
+            state: UnsafeCell::new(QueueState {
+                next_key: 0,
+                queues: BTreeMap::new(),
+            }),
+        }
+    }
+
+    #[inline]
+    fn with_state<U>(&self, f: impl FnOnce(&mut QueueState) -> U) -> U {
+        self.lock.lock();
+        let state = unsafe { &mut *self.state.get() };
+        let result = f(state);
+        state.queues.retain(|_, waiters| !waiters.is_empty());
+        self.len
+            .store(state.queues.values().map(Vec::len).sum(), Ordering::Relaxed);
+        unsafe { self.lock.unlock() };
+        result
+    }
+
+    /// Registers the waker in the queue of `id`, or updates it if `key` is still queued.
+    fn register(&self, id: usize, key: &mut Option<usize>, waker: &Waker) {
+        self.with_state(|state| {
+            let waiters = state.queues.entry(id).or_default();
+            if let Some(key) = *key {
+                if let Some((_, queued)) = waiters.iter_mut().find(|(k, _)| *k == key) {
+                    if !queued.will_wake(waker) {
+                        *queued = waker.clone();
+                    }
+                    return;
+                }
+            }
+
+            let new_key = state.next_key;
+            state.next_key = state.next_key.wrapping_add(1);
+            waiters.push((new_key, waker.clone()));
+            *key = Some(new_key);
+        })
+    }
+
+    /// Removes the waker from the queue of `id`.
+    ///
+    /// # Return
+    ///
+    /// [true] if the waker was still queued, [false] if it has already been woken.
+    fn unregister(&self, id: usize, key: usize) -> bool {
+        self.with_state(|state| {
+            let waiters = match state.queues.get_mut(&id) {
+                Some(waiters) => waiters,
+                None => return false,
+            };
+            match waiters.iter().position(|(k, _)| *k == key) {
+                Some(pos) => {
+                    waiters.remove(pos);
+                    true
+                }
+                None => false,
+            }
+        })
+    }
+
+    /// Wakes the first waiter in the queue of `id`.
+    fn notify_one(&self, id: usize) {
+        // Pairs with the fence in `poll_lock`, so that either the unlocking thread
+        // observes the registered waker, or the waiting task observes the unlock.
+        fence(Ordering::SeqCst);
+        if self.len.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+
+        let waker = self.with_state(|state| match state.queues.get_mut(&id) {
+            Some(waiters) if !waiters.is_empty() => Some(waiters.remove(0).1),
+            _ => None,
+        });
+
+        if let Some(waker) = waker {
+            waker.wake()
+        }
+    }
+
+    /// Wakes the first waiter of every queue without removing it.
+    fn repoll(&self) {
+        let wakers: Vec<_> = self.with_state(|state| {
+            state
+                .queues
+                .values()
+                .filter_map(|waiters| waiters.first().map(|(_, waker)| waker.clone()))
+                .collect()
+        });
+
+        for waker in wakers {
+            waker.wake()
+        }
+    }
+}
+
+/// Fetches the thread re-polling the queues, spawning it on first use.
+fn repoll_thread() -> Option<&'static Thread> {
+    static THREAD: OnceLock<Option<Thread>> = OnceLock::new();
+    THREAD
+        .get_or_init(|| {
+            std::thread::Builder::new()
+                .name("emf-core-base-wake-queue".into())
+                .spawn(|| loop {
+                    if LOCK_QUEUE.len.load(Ordering::Relaxed) == 0 {
+                        std::thread::park();
+                    } else {
+                        std::thread::sleep(REPOLL_INTERVAL);
+                        LOCK_QUEUE.repoll();
+                    }
+                })
+                .ok()
+                .map(|handle| handle.thread().clone())
+        })
+        .as_ref()
+}
+
+/// Polls the lock of `interface` with `try_lock`, registering the task in the
+/// queue of the interface on failure.
+///
+/// `key` identifies the queue entry of the polling future and must be passed to
+/// [cancel] if the future is dropped before acquiring the lock.
+pub(crate) fn poll_lock(
+    interface: &CBaseRef<'_>,
+    key: &mut Option<usize>,
+    cx: &mut Context<'_>,
+    try_lock: impl Fn() -> bool,
+) -> Poll<()> {
+    let id = queue_id(interface);
+    if !try_lock() {
+        LOCK_QUEUE.register(id, key, cx.waker());
+        if let Some(thread) = repoll_thread() {
+            thread.unpark()
+        }
+        fence(Ordering::SeqCst);
+
+        // The lock may have been released before the waker was registered.
+        if !try_lock() {
+            return Poll::Pending;
+        }
+    }
+
+    if let Some(key) = key.take() {
+        LOCK_QUEUE.unregister(id, key);
+    }
+    Poll::Ready(())
+}
+
+/// Removes a future which did not acquire the lock of `interface` from the queue.
+///
+/// If the future has already been woken, the wake-up is passed on to the next waiter.
+pub(crate) fn cancel(interface: &CBaseRef<'_>, key: Option<usize>) {
+    if let Some(key) = key {
+        let id = queue_id(interface);
+        if !LOCK_QUEUE.unregister(id, key) {
+            LOCK_QUEUE.notify_one(id)
+        }
+    }
+}
+
+/// Notifies the queue of `interface` that the lock has been released.
+#[inline]
+pub(crate) fn notify_unlock(interface: &CBaseRef<'_>) {
+    LOCK_QUEUE.notify_one(queue_id(interface))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WakeQueue;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Wake, Waker};
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn waker() -> (Arc<CountingWaker>, Waker) {
+        let counter = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        (counter.clone(), Waker::from(counter))
+    }
+
+    #[test]
+    fn wake_order() {
+        let queue = WakeQueue::new();
+        let (first_count, first) = waker();
+        let (second_count, second) = waker();
+
+        let mut first_key = None;
+        let mut second_key = None;
+        queue.register(0, &mut first_key, &first);
+        queue.register(0, &mut second_key, &second);
+        queue.register(0, &mut first_key, &first);
+
+        queue.notify_one(0);
+        assert_eq!(first_count.0.load(Ordering::Relaxed), 1);
+        assert_eq!(second_count.0.load(Ordering::Relaxed), 0);
+        assert!(!queue.unregister(0, first_key.unwrap()));
+
+        queue.notify_one(0);
+        assert_eq!(second_count.0.load(Ordering::Relaxed), 1);
+
+        queue.notify_one(0);
+        assert_eq!(first_count.0.load(Ordering::Relaxed), 1);
+        assert_eq!(second_count.0.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn unregister() {
+        let queue = WakeQueue::new();
+        let (count, waker) = waker();
+
+        let mut key = None;
+        queue.register(0, &mut key, &waker);
+        assert!(queue.unregister(0, key.unwrap()));
+
+        queue.notify_one(0);
+        assert_eq!(count.0.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn separate_interfaces() {
+        let queue = WakeQueue::new();
+        let (first_count, first) = waker();
+        let (second_count, second) = waker();
+
+        let mut first_key = None;
+        let mut second_key = None;
+        queue.register(0, &mut first_key, &first);
+        queue.register(1, &mut second_key, &second);
+
+        queue.notify_one(1);
+        assert_eq!(first_count.0.load(Ordering::Relaxed), 0);
+        assert_eq!(second_count.0.load(Ordering::Relaxed), 1);
+        assert!(!queue.unregister(1, second_key.unwrap()));
+
+        queue.notify_one(1);
+        assert_eq!(first_count.0.load(Ordering::Relaxed), 0);
+        assert!(queue.unregister(0, first_key.unwrap()));
+    }
+
+    #[test]
+    fn repoll() {
+        let queue = WakeQueue::new();
+        let (first_count, first) = waker();
+        let (second_count, second) = waker();
+        let (third_count, third) = waker();
+
+        let mut first_key = None;
+        let mut second_key = None;
+        let mut third_key = None;
+        queue.register(0, &mut first_key, &first);
+        queue.register(0, &mut second_key, &second);
+        queue.register(1, &mut third_key, &third);
+
+        queue.repoll();
+        assert_eq!(first_count.0.load(Ordering::Relaxed), 1);
+        assert_eq!(second_count.0.load(Ordering::Relaxed), 0);
+        assert_eq!(third_count.0.load(Ordering::Relaxed), 1);
+
+        // Re-polled waiters keep their place in line.
+        assert!(queue.unregister(0, first_key.unwrap()));
+        assert!(queue.unregister(0, second_key.unwrap()));
+        assert!(queue.unregister(1, third_key.unwrap()));
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt::{Display, Formatter};
+use core::fmt::{Display, Formatter};
 
 /// Boolean value.
 #[repr(i8)]
@@ -9,7 +9,7 @@ pub enum Bool {
 }
 
 impl Display for Bool {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             Bool::False => write!(f, "false"),
             Bool::True => write!(f, "true"),
@@ -7,7 +7,7 @@ use crate::sys::api as sys_api;
 use crate::sys::api::SysBinding;
 use crate::version::api::VersionBinding;
 use crate::version::{api as ver_api, Version};
-use std::ptr::NonNull;
+use core::ptr::NonNull;
 
 /// Name of the `emf-core-base` interface.
 pub const CBASE_INTERFACE_NAME: &str = "emf::core_base";
@@ -134,24 +134,224 @@ pub trait CBaseBinding: SysBinding + VersionBinding + LibraryBinding + ModuleBin
     fn fetch_get_function_fn(&self) -> sys_api::GetFunctionFn;
 }
 
-impl CBaseBinding for CBaseInterface {
-    #[inline]
-    fn interface_version(&self) -> Version {
-        unsafe { self.vtable.as_ref().version }
-    }
-
-    #[inline]
-    fn base_module(&self) -> Option<NonNull<CBase>> {
-        self.base_module
-    }
-
-    #[inline]
-    fn fetch_has_function_fn(&self) -> sys_api::HasFunctionFn {
-        unsafe { self.vtable.as_ref().sys_has_function_fn }
-    }
-
-    #[inline]
-    fn fetch_get_function_fn(&self) -> sys_api::GetFunctionFn {
-        unsafe { self.vtable.as_ref().sys_get_function_fn }
-    }
+/// Implements [CBaseBinding] by calling the vtable of a [CBaseInterface].
+///
+/// The interface is accessed through the fields following `self`, which allows
+/// implementing the binding for the types wrapping an interface.
+macro_rules! impl_cbase_binding {
+    (impl<$($generic:ident: $bound:path),*> for $ty:ty, self$(.$field:ident)*) => {
+        const _: () = {
+            use $crate::sys::api as sys_api;
+            use $crate::version::Version;
+            use $crate::{CBase, CBaseBinding, CBaseInterface};
+            use core::ptr::NonNull;
+
+            #[inline]
+            fn base_interface<$($generic: $bound),*>(this: &$ty) -> &CBaseInterface {
+                &this$(.$field)*
+            }
+
+            impl<$($generic: $bound),*> CBaseBinding for $ty {
+                #[inline]
+                fn interface_version(&self) -> Version {
+                    unsafe { base_interface(self).vtable.as_ref().version }
+                }
+
+                #[inline]
+                fn base_module(&self) -> Option<NonNull<CBase>> {
+                    base_interface(self).base_module
+                }
+
+                #[inline]
+                fn fetch_has_function_fn(&self) -> sys_api::HasFunctionFn {
+                    unsafe { base_interface(self).vtable.as_ref().sys_has_function_fn }
+                }
+
+                #[inline]
+                fn fetch_get_function_fn(&self) -> sys_api::GetFunctionFn {
+                    unsafe { base_interface(self).vtable.as_ref().sys_get_function_fn }
+                }
+            }
+        };
+    };
 }
+
+pub(crate) use impl_cbase_binding;
+
+impl_cbase_binding!(impl<> for CBaseInterface, self);
+
+/// Invokes `$callback` with the entries `$extra`, followed by the functions of the version,
+/// library and module apis, except for [FnId::ModuleGetInterface](crate::FnId::ModuleGetInterface).
+///
+/// Every entry has the form `field => Id, fn(arguments) -> Result` and ends with
+/// `, out argument`, if the function writes to the buffer `argument`.
+macro_rules! api_functions {
+    ($callback:ident! { $($extra:tt)* }) => {
+        $callback! {
+            $($extra)*
+            version_new_short_fn => VersionNewShort,
+                fn(major: i32, minor: i32, patch: i32) -> Version;
+            version_new_long_fn => VersionNewLong,
+                fn(
+                    major: i32,
+                    minor: i32,
+                    patch: i32,
+                    release_type: ReleaseType,
+                    release_number: i8,
+                ) -> Version;
+            version_new_full_fn => VersionNewFull,
+                fn(
+                    major: i32,
+                    minor: i32,
+                    patch: i32,
+                    release_type: ReleaseType,
+                    release_number: i8,
+                    build: i64,
+                ) -> Version;
+            version_from_string_fn => VersionFromString,
+                fn(buffer: ConstSpan<u8>) -> Result<Version, Error>;
+            version_string_length_short_fn => VersionStringLengthShort,
+                fn(version: NonNullConst<Version>) -> usize;
+            version_string_length_long_fn => VersionStringLengthLong,
+                fn(version: NonNullConst<Version>) -> usize;
+            version_string_length_full_fn => VersionStringLengthFull,
+                fn(version: NonNullConst<Version>) -> usize;
+            version_as_string_short_fn => VersionAsStringShort,
+                fn(version: NonNullConst<Version>, buffer: MutSpan<u8>) -> Result<usize, Error>,
+                out buffer;
+            version_as_string_long_fn => VersionAsStringLong,
+                fn(version: NonNullConst<Version>, buffer: MutSpan<u8>) -> Result<usize, Error>,
+                out buffer;
+            version_as_string_full_fn => VersionAsStringFull,
+                fn(version: NonNullConst<Version>, buffer: MutSpan<u8>) -> Result<usize, Error>,
+                out buffer;
+            version_string_is_valid_fn => VersionStringIsValid,
+                fn(version_string: ConstSpan<u8>) -> Bool;
+            version_compare_fn => VersionCompare,
+                fn(lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> i32;
+            version_compare_weak_fn => VersionCompareWeak,
+                fn(lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> i32;
+            version_compare_strong_fn => VersionCompareStrong,
+                fn(lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> i32;
+            version_is_compatible_fn => VersionIsCompatible,
+                fn(lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> Bool;
+            library_register_loader_fn => LibraryRegisterLoader,
+                fn(
+                    loader: LibraryLoaderInterface,
+                    lib_type: NonNullConst<LibraryType>,
+                ) -> Result<LibraryLoaderHandle, Error>;
+            library_unregister_loader_fn => LibraryUnregisterLoader,
+                fn(handle: LibraryLoaderHandle) -> Result<i8, Error>;
+            library_get_loader_interface_fn => LibraryGetLoaderInterface,
+                fn(handle: LibraryLoaderHandle) -> Result<LibraryLoaderInterface, Error>;
+            library_get_loader_handle_from_type_fn => LibraryGetLoaderHandleFromType,
+                fn(lib_type: NonNullConst<LibraryType>) -> Result<LibraryLoaderHandle, Error>;
+            library_get_loader_handle_from_library_fn => LibraryGetLoaderHandleFromLibrary,
+                fn(handle: LibraryHandle) -> Result<LibraryLoaderHandle, Error>;
+            library_get_num_loaders_fn => LibraryGetNumLoaders, fn() -> usize;
+            library_library_exists_fn => LibraryLibraryExists, fn(handle: LibraryHandle) -> Bool;
+            library_type_exists_fn => LibraryTypeExists,
+                fn(lib_type: NonNullConst<LibraryType>) -> Bool;
+            library_get_library_types_fn => LibraryGetLibraryTypes,
+                fn(buffer: MutSpan<LibraryType>) -> Result<usize, Error>, out buffer;
+            library_create_library_handle_fn => LibraryCreateLibraryHandle, fn() -> LibraryHandle;
+            library_remove_library_handle_fn => LibraryRemoveLibraryHandle,
+                fn(handle: LibraryHandle) -> Result<i8, Error>;
+            library_link_library_fn => LibraryLinkLibrary,
+                fn(
+                    handle: LibraryHandle,
+                    loader: LibraryLoaderHandle,
+                    internal: InternalLibraryHandle,
+                ) -> Result<i8, Error>;
+            library_get_internal_library_handle_fn => LibraryGetInternalLibraryHandle,
+                fn(handle: LibraryHandle) -> Result<InternalLibraryHandle, Error>;
+            library_load_fn => LibraryLoad,
+                fn(loader: LibraryLoaderHandle, path: OSPathString) -> Result<LibraryHandle, Error>;
+            library_unload_fn => LibraryUnload, fn(handle: LibraryHandle) -> Result<i8, Error>;
+            library_get_data_symbol_fn => LibraryGetDataSymbol,
+                fn(
+                    handle: LibraryHandle,
+                    symbol: SymbolName,
+                ) -> Result<Symbol<NonNullConst<c_void>>, Error>;
+            library_get_function_symbol_fn => LibraryGetFunctionSymbol,
+                fn(handle: LibraryHandle, symbol: SymbolName) -> Result<Symbol<CBaseFn>, Error>;
+            module_register_loader_fn => ModuleRegisterLoader,
+                fn(
+                    loader: ModuleLoaderInterface,
+                    mod_type: NonNullConst<ModuleType>,
+                ) -> Result<ModuleLoaderHandle, Error>;
+            module_unregister_loader_fn => ModuleUnregisterLoader,
+                fn(loader: ModuleLoaderHandle) -> Result<i8, Error>;
+            module_get_loader_interface_fn => ModuleGetLoaderInterface,
+                fn(loader: ModuleLoaderHandle) -> Result<ModuleLoaderInterface, Error>;
+            module_get_loader_handle_from_type_fn => ModuleGetLoaderHandleFromType,
+                fn(mod_type: NonNullConst<ModuleType>) -> Result<ModuleLoaderHandle, Error>;
+            module_get_loader_handle_from_module_fn => ModuleGetLoaderHandleFromModule,
+                fn(handle: ModuleHandle) -> Result<ModuleLoaderHandle, Error>;
+            module_get_num_modules_fn => ModuleGetNumModules, fn() -> usize;
+            module_get_num_loaders_fn => ModuleGetNumLoaders, fn() -> usize;
+            module_get_num_exported_interfaces_fn => ModuleGetNumExportedInterfaces, fn() -> usize;
+            module_module_exists_fn => ModuleModuleExists, fn(handle: ModuleHandle) -> Bool;
+            module_type_exists_fn => ModuleTypeExists,
+                fn(mod_type: NonNullConst<ModuleType>) -> Bool;
+            module_exported_interface_exists_fn => ModuleExportedInterfaceExists,
+                fn(interface: NonNullConst<InterfaceDescriptor<'static>>) -> Bool;
+            module_get_modules_fn => ModuleGetModules,
+                fn(buffer: MutSpan<ModuleInfo>) -> Result<usize, Error>, out buffer;
+            module_get_module_types_fn => ModuleGetModuleTypes,
+                fn(buffer: MutSpan<ModuleType>) -> Result<usize, Error>, out buffer;
+            module_get_exported_interfaces_fn => ModuleGetExportedInterfaces,
+                fn(buffer: MutSpan<InterfaceDescriptor<'static>>) -> Result<usize, Error>, out buffer;
+            module_get_exported_interface_handle_fn => ModuleGetExportedInterfaceHandle,
+                fn(interface: NonNullConst<InterfaceDescriptor<'static>>) -> Result<ModuleHandle, Error>;
+            module_create_module_handle_fn => ModuleCreateModuleHandle, fn() -> ModuleHandle;
+            module_remove_module_handle_fn => ModuleRemoveModuleHandle,
+                fn(handle: ModuleHandle) -> Result<i8, Error>;
+            module_link_module_fn => ModuleLinkModule,
+                fn(
+                    handle: ModuleHandle,
+                    loader: ModuleLoaderHandle,
+                    internal: InternalModuleHandle,
+                ) -> Result<i8, Error>;
+            module_get_internal_module_handle_fn => ModuleGetInternalModuleHandle,
+                fn(handle: ModuleHandle) -> Result<InternalModuleHandle, Error>;
+            module_add_module_fn => ModuleAddModule,
+                fn(loader: ModuleLoaderHandle, path: OSPathString) -> Result<ModuleHandle, Error>;
+            module_remove_module_fn => ModuleRemoveModule,
+                fn(handle: ModuleHandle) -> Result<i8, Error>;
+            module_load_fn => ModuleLoad, fn(handle: ModuleHandle) -> Result<i8, Error>;
+            module_unload_fn => ModuleUnload, fn(handle: ModuleHandle) -> Result<i8, Error>;
+            module_initialize_fn => ModuleInitialize, fn(handle: ModuleHandle) -> Result<i8, Error>;
+            module_terminate_fn => ModuleTerminate, fn(handle: ModuleHandle) -> Result<i8, Error>;
+            module_add_dependency_fn => ModuleAddDependency,
+                fn(
+                    handle: ModuleHandle,
+                    interface: NonNullConst<InterfaceDescriptor<'static>>,
+                ) -> Result<i8, Error>;
+            module_remove_dependency_fn => ModuleRemoveDependency,
+                fn(
+                    handle: ModuleHandle,
+                    interface: NonNullConst<InterfaceDescriptor<'static>>,
+                ) -> Result<i8, Error>;
+            module_export_interface_fn => ModuleExportInterface,
+                fn(
+                    handle: ModuleHandle,
+                    interface: NonNullConst<InterfaceDescriptor<'static>>,
+                ) -> Result<i8, Error>;
+            module_get_load_dependencies_fn => ModuleGetLoadDependencies,
+                fn(handle: ModuleHandle) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>;
+            module_get_runtime_dependencies_fn => ModuleGetRuntimeDependencies,
+                fn(handle: ModuleHandle) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>;
+            module_get_exportable_interfaces_fn => ModuleGetExportableInterfaces,
+                fn(handle: ModuleHandle) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>;
+            module_fetch_status_fn => ModuleFetchStatus,
+                fn(handle: ModuleHandle) -> Result<ModuleStatus, Error>;
+            module_get_module_path_fn => ModuleGetModulePath,
+                fn(handle: ModuleHandle) -> Result<OSPathString, Error>;
+            module_get_module_info_fn => ModuleGetModuleInfo,
+                fn(handle: ModuleHandle) -> Result<NonNullConst<ModuleInfo>, Error>;
+        }
+    };
+}
+
+pub(crate) use api_functions;
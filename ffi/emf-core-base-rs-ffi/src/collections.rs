@@ -1,13 +1,21 @@
 //! Collections used by the `emf-core-base` interface.
 
+mod ffi_box;
+mod ffi_string;
+mod ffi_vec;
 mod non_null_const;
 mod optional;
 mod result;
 mod span;
+mod span_ref;
 mod static_vec;
 
+pub use ffi_box::{BoxCloneFn, BoxDropFn, FfiBox, FfiBoxVTable};
+pub use ffi_string::FfiString;
+pub use ffi_vec::{FfiVec, FfiVecVTable, VecCloneFn, VecDropFn};
 pub use non_null_const::NonNullConst;
 pub use optional::Optional;
 pub use result::Result;
 pub use span::{ConstSpan, MutSpan, Span};
-pub use static_vec::StaticVec;
+pub use span_ref::{SpanMut, SpanRef};
+pub use static_vec::{CapacityError, StaticVec};
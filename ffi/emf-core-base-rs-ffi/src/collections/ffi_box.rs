@@ -0,0 +1,338 @@
+use crate::collections::NonNullConst;
+#[cfg(feature = "shared_alloc")]
+use crate::extensions::alloc::{alloc_with_allocator, dealloc_with_allocator, AllocInterface};
+use crate::{Bool, TypeWrapper};
+use alloc::boxed::Box;
+#[cfg(feature = "shared_alloc")]
+use core::alloc::Layout;
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "shared_alloc")]
+use core::mem::align_of;
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+/// Releases a value, optionally dropping it first.
+pub type BoxDropFn<T> =
+    TypeWrapper<unsafe extern "C-unwind" fn(data: NonNull<T>, drop_value: Bool)>;
+
+/// Clones a value with the allocator of its origin.
+pub type BoxCloneFn<T> =
+    TypeWrapper<unsafe extern "C-unwind" fn(data: NonNullConst<T>) -> NonNull<T>>;
+
+/// VTable of a [FfiBox].
+///
+/// # Layout
+///
+/// ```c
+/// struct FfiBoxVTable {
+///     void (*drop_fn)(T* data, Bool drop_value);
+///     T* (*clone_fn)(const T* data); // nullable
+/// };
+/// ```
+#[repr(C)]
+pub struct FfiBoxVTable<T> {
+    /// Releases the value.
+    ///
+    /// The value is dropped before, if `drop_value` is [Bool::True].
+    pub drop_fn: BoxDropFn<T>,
+    /// Clones the value, if supported by its origin.
+    pub clone_fn: Option<BoxCloneFn<T>>,
+}
+
+impl<T> Copy for FfiBoxVTable<T> {}
+
+impl<T> Clone for FfiBoxVTable<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Debug for FfiBoxVTable<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FfiBoxVTable")
+            .field("drop_fn", &(self.drop_fn.0 as usize))
+            .field("clone_fn", &self.clone_fn.map(|f| f.0 as usize))
+            .finish()
+    }
+}
+
+/// An owned heap allocated value, which can be passed between modules.
+///
+/// The box carries the functions for releasing and cloning its value, so that it is
+/// always released by the allocator it was obtained from.
+///
+/// # Layout
+///
+/// ```c
+/// struct FfiBox {
+///     T* data; // non-null
+///     const FfiBoxVTable* vtable;
+/// };
+/// ```
+#[repr(C)]
+pub struct FfiBox<T> {
+    data: NonNull<T>,
+    vtable: NonNullConst<FfiBoxVTable<T>>,
+}
+
+unsafe impl<T: Send> Send for FfiBox<T> {}
+unsafe impl<T: Sync> Sync for FfiBox<T> {}
+
+impl<T> FfiBox<T> {
+    const VTABLE: FfiBoxVTable<T> = FfiBoxVTable {
+        drop_fn: TypeWrapper(Self::drop_fn),
+        clone_fn: None,
+    };
+
+    /// Moves `value` to the heap.
+    #[inline]
+    pub fn new(value: T) -> Self {
+        Self::from(Box::new(value))
+    }
+
+    /// Constructs a box from its raw parts.
+    ///
+    /// # Safety
+    ///
+    /// `data` must point to an initialized value, which can be released with `vtable`.
+    #[inline]
+    pub const unsafe fn from_raw_parts(
+        data: NonNull<T>,
+        vtable: NonNullConst<FfiBoxVTable<T>>,
+    ) -> Self {
+        Self { data, vtable }
+    }
+
+    /// Decomposes the box into its raw parts.
+    #[inline]
+    pub fn into_raw_parts(self) -> (NonNull<T>, NonNullConst<FfiBoxVTable<T>>) {
+        let this = ManuallyDrop::new(self);
+        (this.data, this.vtable)
+    }
+
+    /// Fetches the vtable.
+    #[inline]
+    pub fn vtable(&self) -> NonNullConst<FfiBoxVTable<T>> {
+        self.vtable
+    }
+
+    /// Moves the value out of the box.
+    ///
+    /// The memory is released by its origin.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        let this = ManuallyDrop::new(self);
+        unsafe {
+            let value = core::ptr::read(this.data.as_ptr());
+            (this.vtable.as_ref().drop_fn)(this.data, Bool::False);
+            value
+        }
+    }
+
+    /// Converts the box into a [Box].
+    ///
+    /// The value is moved into a box of the current module
+    /// and the original memory is released by its origin.
+    #[inline]
+    pub fn into_box(self) -> Box<T> {
+        Box::new(self.into_inner())
+    }
+
+    unsafe extern "C-unwind" fn drop_fn(data: NonNull<T>, drop_value: Bool) {
+        let value = Box::from_raw(data.as_ptr());
+        if drop_value == Bool::False {
+            drop(Box::from_raw(Box::into_raw(value) as *mut ManuallyDrop<T>))
+        }
+    }
+}
+
+#[cfg(feature = "shared_alloc")]
+impl<T> FfiBox<T> {
+    const ALLOC_VTABLE: FfiBoxVTable<T> = FfiBoxVTable {
+        drop_fn: TypeWrapper(Self::alloc_drop_fn),
+        clone_fn: None,
+    };
+
+    /// Moves `value` to memory obtained from `allocator`.
+    ///
+    /// The memory is released through `allocator`, which must outlive the box.
+    /// Clones of the box are allocated by the module cloning them.
+    ///
+    /// # Return
+    ///
+    /// Box on success, `value` if the allocation fails.
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    pub unsafe fn new_in(value: T, allocator: AllocInterface) -> Result<Self, T> {
+        match alloc_with_allocator(allocator, Layout::new::<T>()) {
+            Some(data) => {
+                let data = data.cast::<T>();
+                data.as_ptr().write(value);
+                Ok(Self::from_raw_parts(
+                    data,
+                    NonNullConst::from(&Self::ALLOC_VTABLE),
+                ))
+            }
+            None => Err(value),
+        }
+    }
+
+    unsafe extern "C-unwind" fn alloc_drop_fn(data: NonNull<T>, drop_value: Bool) {
+        if drop_value == Bool::True {
+            core::ptr::drop_in_place(data.as_ptr());
+        }
+        dealloc_with_allocator(data.cast(), align_of::<T>())
+    }
+}
+
+impl<T: Clone> FfiBox<T> {
+    const CLONE_VTABLE: FfiBoxVTable<T> = FfiBoxVTable {
+        drop_fn: TypeWrapper(Self::drop_fn),
+        clone_fn: Some(TypeWrapper(Self::clone_fn)),
+    };
+
+    /// Moves `value` to the heap, using the current module to clone it.
+    ///
+    /// Boxes constructed with [FfiBox::new] or [From] are cloned by the module cloning them.
+    #[inline]
+    pub fn new_cloneable(value: T) -> Self {
+        unsafe {
+            Self::from_raw_parts(
+                NonNull::from(Box::leak(Box::new(value))),
+                NonNullConst::from(&Self::CLONE_VTABLE),
+            )
+        }
+    }
+
+    unsafe extern "C-unwind" fn clone_fn(data: NonNullConst<T>) -> NonNull<T> {
+        NonNull::from(Box::leak(Box::new(data.as_ref().clone())))
+    }
+}
+
+impl<T> Drop for FfiBox<T> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { (self.vtable.as_ref().drop_fn)(self.data, Bool::True) }
+    }
+}
+
+impl<T: Clone> Clone for FfiBox<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        match unsafe { self.vtable.as_ref().clone_fn } {
+            Some(clone_fn) => unsafe {
+                Self::from_raw_parts(clone_fn(NonNullConst::from(self.data)), self.vtable)
+            },
+            None => Self::new((**self).clone()),
+        }
+    }
+}
+
+impl<T: Default> Default for FfiBox<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> Deref for FfiBox<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { self.data.as_ref() }
+    }
+}
+
+impl<T> DerefMut for FfiBox<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { self.data.as_mut() }
+    }
+}
+
+impl<T> AsRef<T> for FfiBox<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self
+    }
+}
+
+impl<T> AsMut<T> for FfiBox<T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+impl<T: Debug> Debug for FfiBox<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: Display> Display for FfiBox<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&**self, f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for FfiBox<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Eq> Eq for FfiBox<T> {}
+
+impl<T: Hash> Hash for FfiBox<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl<T> From<Box<T>> for FfiBox<T> {
+    #[inline]
+    fn from(value: Box<T>) -> Self {
+        unsafe {
+            Self::from_raw_parts(
+                NonNull::from(Box::leak(value)),
+                NonNullConst::from(&Self::VTABLE),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::FfiBox;
+    use alloc::rc::Rc;
+
+    #[test]
+    fn box_conversion() {
+        let value = Rc::new(5);
+        let boxed = FfiBox::new(value.clone());
+        assert_eq!(**boxed, 5);
+        assert_eq!(Rc::strong_count(&value), 2);
+
+        let cloneable = FfiBox::new_cloneable(value.clone());
+        let cloned = cloneable.clone();
+        assert_eq!(cloned, cloneable);
+        assert_eq!(Rc::strong_count(&value), 4);
+        drop((cloneable, cloned));
+
+        let inner = boxed.into_box();
+        assert_eq!(Rc::strong_count(&value), 2);
+        drop(inner);
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
@@ -0,0 +1,199 @@
+use crate::collections::FfiVec;
+#[cfg(feature = "shared_alloc")]
+use crate::extensions::alloc::AllocInterface;
+use alloc::string::{String, ToString};
+use core::borrow::Borrow;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::Deref;
+
+/// An owned `UTF-8` string, which can be passed between modules.
+///
+/// # Layout
+///
+/// The string has the same layout as a [FfiVec] of bytes:
+///
+/// ```c
+/// struct FfiString {
+///     uint8_t* data;
+///     size_t length;
+///     size_t capacity;
+///     const FfiVecVTable* vtable;
+/// };
+/// ```
+#[repr(transparent)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct FfiString {
+    vec: FfiVec<u8>,
+}
+
+impl FfiString {
+    /// Constructs an empty string.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from(String::new())
+    }
+
+    /// Moves the content of `string` into a buffer obtained from `allocator`.
+    ///
+    /// The buffer is released through `allocator`, which must outlive the string.
+    ///
+    /// # Return
+    ///
+    /// String on success, `string` if the allocation fails.
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    #[cfg(feature = "shared_alloc")]
+    #[inline]
+    pub unsafe fn from_string_in(
+        string: String,
+        allocator: AllocInterface,
+    ) -> Result<Self, String> {
+        match FfiVec::from_vec_in(string.into_bytes(), allocator) {
+            Ok(vec) => Ok(Self::from_utf8_unchecked(vec)),
+            Err(vec) => Err(String::from_utf8_unchecked(vec)),
+        }
+    }
+
+    /// Converts a vector of bytes to a string.
+    ///
+    /// # Failure
+    ///
+    /// Fails if `vec` is not valid `UTF-8`.
+    ///
+    /// # Return
+    ///
+    /// String on success, the vector otherwise.
+    #[inline]
+    pub fn from_utf8(vec: FfiVec<u8>) -> Result<Self, FfiVec<u8>> {
+        match core::str::from_utf8(&vec) {
+            Ok(_) => Ok(Self { vec }),
+            Err(_) => Err(vec),
+        }
+    }
+
+    /// Converts a vector of bytes to a string without checking its content.
+    ///
+    /// # Safety
+    ///
+    /// `vec` must be valid `UTF-8`.
+    #[inline]
+    pub const unsafe fn from_utf8_unchecked(vec: FfiVec<u8>) -> Self {
+        Self { vec }
+    }
+
+    /// Fetches the string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe { core::str::from_utf8_unchecked(&self.vec) }
+    }
+
+    /// Fetches the bytes of the string.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.vec
+    }
+
+    /// Converts the string into its bytes.
+    #[inline]
+    pub fn into_bytes(self) -> FfiVec<u8> {
+        self.vec
+    }
+
+    /// Converts the string into a [String].
+    ///
+    /// The content is moved into a buffer of the current module
+    /// and the original buffer is released by its origin.
+    #[inline]
+    pub fn into_string(self) -> String {
+        unsafe { String::from_utf8_unchecked(self.vec.into_vec()) }
+    }
+}
+
+impl Default for FfiString {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for FfiString {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for FfiString {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for FfiString {
+    #[inline]
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Display for FfiString {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+impl Debug for FfiString {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl From<String> for FfiString {
+    #[inline]
+    fn from(string: String) -> Self {
+        Self {
+            vec: FfiVec::from_cloneable(string.into_bytes()),
+        }
+    }
+}
+
+impl From<&str> for FfiString {
+    #[inline]
+    fn from(string: &str) -> Self {
+        Self::from(string.to_string())
+    }
+}
+
+impl From<FfiString> for String {
+    #[inline]
+    fn from(string: FfiString) -> Self {
+        string.into_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::{FfiString, FfiVec};
+    use alloc::string::{String, ToString};
+    use alloc::vec;
+
+    #[test]
+    fn string_conversion() {
+        let string = FfiString::from("Hello, world!");
+        assert_eq!(string.as_str(), "Hello, world!");
+        assert_eq!(string.clone(), string);
+        assert_eq!(string.to_string(), "Hello, world!");
+        assert_eq!(String::from(string), "Hello, world!");
+
+        let invalid = FfiVec::from(vec![0xFF, 0xFE]);
+        assert!(FfiString::from_utf8(invalid).is_err());
+        assert!(FfiString::new().is_empty());
+    }
+}
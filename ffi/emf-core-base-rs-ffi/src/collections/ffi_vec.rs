@@ -0,0 +1,399 @@
+use crate::collections::NonNullConst;
+#[cfg(feature = "shared_alloc")]
+use crate::extensions::alloc::{alloc_with_allocator, dealloc_with_allocator, AllocInterface};
+use crate::TypeWrapper;
+use alloc::vec::Vec;
+#[cfg(feature = "shared_alloc")]
+use core::alloc::Layout;
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "shared_alloc")]
+use core::mem::align_of;
+use core::mem::ManuallyDrop;
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+/// Drops the first `length` elements of a buffer and releases it.
+pub type VecDropFn<T> =
+    TypeWrapper<unsafe extern "C-unwind" fn(data: NonNull<T>, length: usize, capacity: usize)>;
+
+/// Clones a vector with the allocator of its origin.
+pub type VecCloneFn<T> =
+    TypeWrapper<unsafe extern "C-unwind" fn(vec: NonNullConst<FfiVec<T>>) -> FfiVec<T>>;
+
+/// VTable of a [FfiVec].
+///
+/// # Layout
+///
+/// ```c
+/// struct FfiVecVTable {
+///     void (*drop_fn)(T* data, size_t length, size_t capacity);
+///     FfiVec (*clone_fn)(const FfiVec* vec); // nullable
+/// };
+/// ```
+#[repr(C)]
+pub struct FfiVecVTable<T> {
+    /// Drops the first `length` elements and releases the buffer.
+    ///
+    /// Passing a `length` of `0` releases the buffer without dropping any element.
+    pub drop_fn: VecDropFn<T>,
+    /// Clones the vector, if supported by its origin.
+    pub clone_fn: Option<VecCloneFn<T>>,
+}
+
+impl<T> Copy for FfiVecVTable<T> {}
+
+impl<T> Clone for FfiVecVTable<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Debug for FfiVecVTable<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FfiVecVTable")
+            .field("drop_fn", &(self.drop_fn.0 as usize))
+            .field("clone_fn", &self.clone_fn.map(|f| f.0 as usize))
+            .finish()
+    }
+}
+
+/// An owned, growable array, which can be passed between modules.
+///
+/// The vector carries the functions for releasing and cloning its buffer, so that it is
+/// always released by the allocator it was obtained from.
+///
+/// # Layout
+///
+/// ```c
+/// struct FfiVec {
+///     T* data;       // non-null, dangling if `capacity` is `0`
+///     size_t length;
+///     size_t capacity;
+///     const FfiVecVTable* vtable;
+/// };
+/// ```
+#[repr(C)]
+pub struct FfiVec<T> {
+    data: NonNull<T>,
+    length: usize,
+    capacity: usize,
+    vtable: NonNullConst<FfiVecVTable<T>>,
+}
+
+unsafe impl<T: Send> Send for FfiVec<T> {}
+unsafe impl<T: Sync> Sync for FfiVec<T> {}
+
+impl<T> FfiVec<T> {
+    const VTABLE: FfiVecVTable<T> = FfiVecVTable {
+        drop_fn: TypeWrapper(Self::drop_fn),
+        clone_fn: None,
+    };
+
+    /// Constructs an empty vector.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from(Vec::new())
+    }
+
+    /// Constructs a vector from its raw parts.
+    ///
+    /// # Safety
+    ///
+    /// `data` must point to a buffer of `capacity` elements, of which the first `length`
+    /// are initialized, and which can be released with `vtable`.
+    #[inline]
+    pub const unsafe fn from_raw_parts(
+        data: NonNull<T>,
+        length: usize,
+        capacity: usize,
+        vtable: NonNullConst<FfiVecVTable<T>>,
+    ) -> Self {
+        Self {
+            data,
+            length,
+            capacity,
+            vtable,
+        }
+    }
+
+    /// Decomposes the vector into its raw parts.
+    #[inline]
+    pub fn into_raw_parts(self) -> (NonNull<T>, usize, usize, NonNullConst<FfiVecVTable<T>>) {
+        let this = ManuallyDrop::new(self);
+        (this.data, this.length, this.capacity, this.vtable)
+    }
+
+    /// Fetches the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Checks whether the vector is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Fetches the number of elements the buffer can hold.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Fetches the vtable.
+    #[inline]
+    pub fn vtable(&self) -> NonNullConst<FfiVecVTable<T>> {
+        self.vtable
+    }
+
+    /// Fetches a slice of the elements.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        unsafe { core::slice::from_raw_parts(self.data.as_ptr(), self.length) }
+    }
+
+    /// Fetches a mutable slice of the elements.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        unsafe { core::slice::from_raw_parts_mut(self.data.as_ptr(), self.length) }
+    }
+
+    /// Converts the vector into a [Vec].
+    ///
+    /// The elements are moved into a buffer of the current module
+    /// and the original buffer is released by its origin.
+    pub fn into_vec(self) -> Vec<T> {
+        let this = ManuallyDrop::new(self);
+        let mut vec = Vec::with_capacity(this.length);
+        unsafe {
+            core::ptr::copy_nonoverlapping(this.data.as_ptr(), vec.as_mut_ptr(), this.length);
+            vec.set_len(this.length);
+            (this.vtable.as_ref().drop_fn)(this.data, 0, this.capacity);
+        }
+        vec
+    }
+
+    unsafe extern "C-unwind" fn drop_fn(data: NonNull<T>, length: usize, capacity: usize) {
+        drop(Vec::from_raw_parts(data.as_ptr(), length, capacity))
+    }
+}
+
+#[cfg(feature = "shared_alloc")]
+impl<T> FfiVec<T> {
+    const ALLOC_VTABLE: FfiVecVTable<T> = FfiVecVTable {
+        drop_fn: TypeWrapper(Self::alloc_drop_fn),
+        clone_fn: None,
+    };
+
+    /// Moves the elements of `vec` into a buffer obtained from `allocator`.
+    ///
+    /// The buffer is released through `allocator`, which must outlive the vector.
+    /// Clones of the vector are allocated by the module cloning them.
+    ///
+    /// # Return
+    ///
+    /// Vector on success, `vec` if the allocation fails.
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    pub unsafe fn from_vec_in(mut vec: Vec<T>, allocator: AllocInterface) -> Result<Self, Vec<T>> {
+        let layout = match Layout::array::<T>(vec.len()) {
+            Ok(layout) => layout,
+            Err(_) => return Err(vec),
+        };
+        let data = match alloc_with_allocator(allocator, layout) {
+            Some(data) => data.cast::<T>(),
+            None => return Err(vec),
+        };
+
+        let length = vec.len();
+        core::ptr::copy_nonoverlapping(vec.as_ptr(), data.as_ptr(), length);
+        vec.set_len(0);
+        Ok(Self::from_raw_parts(
+            data,
+            length,
+            length,
+            NonNullConst::from(&Self::ALLOC_VTABLE),
+        ))
+    }
+
+    unsafe extern "C-unwind" fn alloc_drop_fn(data: NonNull<T>, length: usize, _capacity: usize) {
+        core::ptr::drop_in_place(core::ptr::slice_from_raw_parts_mut(data.as_ptr(), length));
+        dealloc_with_allocator(data.cast(), align_of::<T>())
+    }
+}
+
+impl<T: Clone> FfiVec<T> {
+    const CLONE_VTABLE: FfiVecVTable<T> = FfiVecVTable {
+        drop_fn: TypeWrapper(Self::drop_fn),
+        clone_fn: Some(TypeWrapper(Self::clone_fn)),
+    };
+
+    /// Constructs a vector, which is cloned by the current module.
+    ///
+    /// Vectors constructed with [From] are cloned by the module cloning them.
+    #[inline]
+    pub fn from_cloneable(vec: Vec<T>) -> Self {
+        let mut vec = ManuallyDrop::new(vec);
+        unsafe {
+            Self::from_raw_parts(
+                NonNull::new_unchecked(vec.as_mut_ptr()),
+                vec.len(),
+                vec.capacity(),
+                NonNullConst::from(&Self::CLONE_VTABLE),
+            )
+        }
+    }
+
+    unsafe extern "C-unwind" fn clone_fn(vec: NonNullConst<FfiVec<T>>) -> FfiVec<T> {
+        Self::from_cloneable(vec.as_ref().as_slice().to_vec())
+    }
+}
+
+impl<T> Drop for FfiVec<T> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { (self.vtable.as_ref().drop_fn)(self.data, self.length, self.capacity) }
+    }
+}
+
+impl<T: Clone> Clone for FfiVec<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        match unsafe { self.vtable.as_ref().clone_fn } {
+            Some(clone_fn) => unsafe { clone_fn(NonNullConst::from(self)) },
+            None => Self::from(self.as_slice().to_vec()),
+        }
+    }
+}
+
+impl<T> Default for FfiVec<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Deref for FfiVec<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T> DerefMut for FfiVec<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<T> AsRef<[T]> for FfiVec<T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> AsMut<[T]> for FfiVec<T> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [T] {
+        self.as_mut_slice()
+    }
+}
+
+impl<T: Debug> Debug for FfiVec<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<T: PartialEq> PartialEq for FfiVec<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq> Eq for FfiVec<T> {}
+
+impl<T: Hash> Hash for FfiVec<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<T> From<Vec<T>> for FfiVec<T> {
+    #[inline]
+    fn from(vec: Vec<T>) -> Self {
+        let mut vec = ManuallyDrop::new(vec);
+        unsafe {
+            Self::from_raw_parts(
+                NonNull::new_unchecked(vec.as_mut_ptr()),
+                vec.len(),
+                vec.capacity(),
+                NonNullConst::from(&Self::VTABLE),
+            )
+        }
+    }
+}
+
+impl<T: Clone> From<&[T]> for FfiVec<T> {
+    #[inline]
+    fn from(slice: &[T]) -> Self {
+        Self::from(slice.to_vec())
+    }
+}
+
+impl<T> From<FfiVec<T>> for Vec<T> {
+    #[inline]
+    fn from(vec: FfiVec<T>) -> Self {
+        vec.into_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::FfiVec;
+    use alloc::rc::Rc;
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn vec_conversion() {
+        let vec = FfiVec::from(vec![1, 2, 3]);
+        assert_eq!(vec.len(), 3);
+        assert_eq!(*vec, [1, 2, 3]);
+        assert_eq!(vec.clone(), vec);
+
+        let vec = FfiVec::from_cloneable(vec![String::from("a"), String::from("b")]);
+        let cloned = vec.clone();
+        assert_eq!(cloned, vec);
+        assert_eq!(Vec::from(cloned), vec!["a", "b"]);
+        assert!(FfiVec::<u8>::new().is_empty());
+    }
+
+    #[test]
+    fn vec_drop() {
+        let value = Rc::new(0);
+        let vec = FfiVec::from(vec![value.clone(), value.clone()]);
+        assert_eq!(Rc::strong_count(&value), 3);
+
+        let vec = Vec::from(vec);
+        assert_eq!(Rc::strong_count(&value), 3);
+        drop(vec);
+        assert_eq!(Rc::strong_count(&value), 1);
+
+        drop(FfiVec::from(vec![value.clone()]));
+        assert_eq!(Rc::strong_count(&value), 1);
+    }
+}
@@ -1,8 +1,8 @@
 //! Implementation of the `NonNullConst<T>` type.
-use std::cmp::Ordering;
-use std::fmt::{Debug, Formatter};
-use std::hash::{Hash, Hasher};
-use std::ptr::NonNull;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ptr::NonNull;
 
 /// A type representing a `*const T` but non-zero.
 ///
@@ -146,7 +146,7 @@ where
 }
 
 impl<T: ?Sized> Debug for NonNullConst<T> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         Debug::fmt(&self.ptr, f)
     }
 }
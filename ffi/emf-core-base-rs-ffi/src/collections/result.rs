@@ -1,5 +1,5 @@
 use crate::collections::Optional;
-use std::fmt::Debug;
+use core::fmt::Debug;
 
 /// A type that represents either success ([`Result::Ok`]) or failure ([`Result::Err`]).
 #[repr(C, i8)]
@@ -132,7 +132,7 @@ impl<T, E> Result<T, E> {
 
     /// Maps the `Result<T, E>` to the native `Result<T, E>`.
     #[inline]
-    pub fn into_rust(self) -> std::result::Result<T, E> {
+    pub fn into_rust(self) -> core::result::Result<T, E> {
         match self {
             Result::Ok(x) => Ok(x),
             Result::Err(x) => Err(x),
@@ -218,9 +218,9 @@ where
     }
 }
 
-impl<T, E> From<std::result::Result<T, E>> for Result<T, E> {
+impl<T, E> From<core::result::Result<T, E>> for Result<T, E> {
     #[inline]
-    fn from(val: std::result::Result<T, E>) -> Self {
+    fn from(val: core::result::Result<T, E>) -> Self {
         match val {
             Ok(x) => Result::Ok(x),
             Err(x) => Result::Err(x),
@@ -228,7 +228,7 @@ impl<T, E> From<std::result::Result<T, E>> for Result<T, E> {
     }
 }
 
-impl<T, E> From<Result<T, E>> for std::result::Result<T, E> {
+impl<T, E> From<Result<T, E>> for core::result::Result<T, E> {
     #[inline]
     fn from(val: Result<T, E>) -> Self {
         val.into_rust()
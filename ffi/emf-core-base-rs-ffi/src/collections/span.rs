@@ -1,7 +1,9 @@
-use std::hash::{Hash, Hasher};
-use std::ops::{Deref, DerefMut};
-use std::ptr::NonNull;
-use std::slice::{Iter, IterMut};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+use core::slice::{Iter, IterMut};
 
 /// An immutable span.
 pub type ConstSpan<T> = Span<T, false>;
@@ -28,7 +30,7 @@ where
     #[inline]
     pub fn new() -> Self {
         Self {
-            data: std::ptr::null(),
+            data: core::ptr::null(),
             length: 0,
         }
     }
@@ -111,9 +113,9 @@ where
     fn as_ref(&self) -> &[T] {
         unsafe {
             if self.data.is_null() {
-                std::slice::from_raw_parts(NonNull::dangling().as_ptr(), 0)
+                core::slice::from_raw_parts(NonNull::dangling().as_ptr(), 0)
             } else {
-                std::slice::from_raw_parts(self.data, self.length)
+                core::slice::from_raw_parts(self.data, self.length)
             }
         }
     }
@@ -127,9 +129,9 @@ where
     fn as_mut(&mut self) -> &mut [T] {
         unsafe {
             if self.data.is_null() {
-                std::slice::from_raw_parts_mut(NonNull::dangling().as_ptr(), 0)
+                core::slice::from_raw_parts_mut(NonNull::dangling().as_ptr(), 0)
             } else {
-                std::slice::from_raw_parts_mut(self.data as *mut T, self.length)
+                core::slice::from_raw_parts_mut(self.data as *mut T, self.length)
             }
         }
     }
@@ -0,0 +1,554 @@
+use crate::collections::{ConstSpan, MutSpan};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::slice::{Iter, IterMut};
+
+/// An immutable span borrowing its elements for the lifetime `'a`, akin to `&'a [T]`.
+///
+/// Has the same layout as a [ConstSpan].
+#[repr(transparent)]
+#[derive(Copy, Clone)]
+pub struct SpanRef<'a, T>
+where
+    T: Copy + Sized,
+{
+    span: ConstSpan<T>,
+    _phantom: PhantomData<&'a [T]>,
+}
+
+impl<'a, T> SpanRef<'a, T>
+where
+    T: Copy + Sized,
+{
+    /// Create a new empty span.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_span(ConstSpan::new())
+    }
+
+    /// Creates a new span from a pointer and a length.
+    ///
+    /// # Safety
+    ///
+    /// Same restrictions as [from_raw_parts](std::slice::from_raw_parts) apply.
+    #[inline]
+    pub unsafe fn from_raw_parts(ptr: *const T, length: usize) -> Self {
+        Self::from_span(ConstSpan::from_raw_parts(ptr, length))
+    }
+
+    /// Attaches a lifetime to an unbound span.
+    ///
+    /// # Safety
+    ///
+    /// The elements of `span` must remain valid and unchanged for the lifetime `'a`.
+    #[inline]
+    pub unsafe fn from_unbound(span: ConstSpan<T>) -> Self {
+        Self::from_span(span)
+    }
+
+    #[inline]
+    fn from_span(span: ConstSpan<T>) -> Self {
+        Self {
+            span,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Fetches an immutable pointer of the elements the span points to.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.span.as_ptr()
+    }
+
+    /// Retrieves the length of the span.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.span.len()
+    }
+
+    /// Checks if the span is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.span.is_empty()
+    }
+
+    /// Fetches the elements, bound to the lifetime of the span.
+    #[inline]
+    pub fn as_slice(&self) -> &'a [T] {
+        unsafe { core::slice::from_raw_parts(self.span.as_ref().as_ptr(), self.span.len()) }
+    }
+
+    /// Constructs an iterator.
+    #[inline]
+    pub fn iter(&self) -> Iter<'a, T> {
+        self.as_slice().iter()
+    }
+
+    /// Detaches the lifetime from the span.
+    #[inline]
+    pub fn into_unbound(self) -> ConstSpan<T> {
+        self.span
+    }
+}
+
+unsafe impl<T> Send for SpanRef<'_, T> where T: Copy + Sized + Sync {}
+unsafe impl<T> Sync for SpanRef<'_, T> where T: Copy + Sized + Sync {}
+
+impl<T> AsRef<[T]> for SpanRef<'_, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.as_slice()
+    }
+}
+
+impl<T> Default for SpanRef<'_, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Deref for SpanRef<'_, T>
+where
+    T: Copy + Sized,
+{
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T> Debug for SpanRef<'_, T>
+where
+    T: Copy + Debug + Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<T> PartialEq for SpanRef<'_, T>
+where
+    T: Copy + PartialEq + Sized,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T> Eq for SpanRef<'_, T> where T: Copy + Eq + Sized {}
+
+impl<T> PartialOrd for SpanRef<'_, T>
+where
+    T: Copy + PartialOrd + Sized,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T> Ord for SpanRef<'_, T>
+where
+    T: Copy + Ord + Sized,
+{
+    #[inline]
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
+impl<T> Hash for SpanRef<'_, T>
+where
+    T: Copy + Hash + Sized,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<'a, T> IntoIterator for SpanRef<'a, T>
+where
+    T: Copy + Sized,
+{
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> From<&'a T> for SpanRef<'a, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn from(p: &'a T) -> Self {
+        Self::from_span(ConstSpan::from(p))
+    }
+}
+
+impl<'a, T> From<&'a [T]> for SpanRef<'a, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn from(p: &'a [T]) -> Self {
+        Self::from_span(ConstSpan::from(p))
+    }
+}
+
+impl<'a, T, const N: usize> From<&'a [T; N]> for SpanRef<'a, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn from(p: &'a [T; N]) -> Self {
+        Self::from_span(ConstSpan::from(p))
+    }
+}
+
+impl<'a, T> From<&'a Vec<T>> for SpanRef<'a, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn from(p: &'a Vec<T>) -> Self {
+        Self::from_span(ConstSpan::from(p))
+    }
+}
+
+impl<'a> From<&'a str> for SpanRef<'a, u8> {
+    #[inline]
+    fn from(p: &'a str) -> Self {
+        Self::from_span(ConstSpan::from(p))
+    }
+}
+
+impl<'a> From<&'a String> for SpanRef<'a, u8> {
+    #[inline]
+    fn from(p: &'a String) -> Self {
+        Self::from_span(ConstSpan::from(p))
+    }
+}
+
+impl<'a, T> From<SpanMut<'a, T>> for SpanRef<'a, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn from(p: SpanMut<'a, T>) -> Self {
+        Self::from(p.into_slice() as &'a [T])
+    }
+}
+
+impl<T> From<SpanRef<'_, T>> for ConstSpan<T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn from(p: SpanRef<'_, T>) -> Self {
+        p.into_unbound()
+    }
+}
+
+/// A mutable span borrowing its elements for the lifetime `'a`, akin to `&'a mut [T]`.
+///
+/// Has the same layout as a [MutSpan].
+#[repr(transparent)]
+pub struct SpanMut<'a, T>
+where
+    T: Copy + Sized,
+{
+    span: MutSpan<T>,
+    _phantom: PhantomData<&'a mut [T]>,
+}
+
+impl<'a, T> SpanMut<'a, T>
+where
+    T: Copy + Sized,
+{
+    /// Create a new empty span.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_span(MutSpan::new())
+    }
+
+    /// Creates a new span from a mutable pointer and a length.
+    ///
+    /// # Safety
+    ///
+    /// Same restrictions as [from_raw_parts_mut](std::slice::from_raw_parts_mut) apply.
+    #[inline]
+    pub unsafe fn from_raw_parts_mut(ptr: *mut T, length: usize) -> Self {
+        Self::from_span(MutSpan::from_raw_parts_mut(ptr, length))
+    }
+
+    /// Attaches a lifetime to an unbound span.
+    ///
+    /// # Safety
+    ///
+    /// The elements of `span` must remain valid and must not be accessed
+    /// through any other path for the lifetime `'a`.
+    #[inline]
+    pub unsafe fn from_unbound(span: MutSpan<T>) -> Self {
+        Self::from_span(span)
+    }
+
+    #[inline]
+    fn from_span(span: MutSpan<T>) -> Self {
+        Self {
+            span,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Fetches an immutable pointer of the elements the span points to.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.span.as_ptr()
+    }
+
+    /// Fetches a mutable pointer of the elements the span points to.
+    #[inline]
+    pub fn as_ptr_mut(&mut self) -> *mut T {
+        self.span.as_ptr_mut()
+    }
+
+    /// Retrieves the length of the span.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.span.len()
+    }
+
+    /// Checks if the span is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.span.is_empty()
+    }
+
+    /// Reborrows the span for a shorter lifetime.
+    #[inline]
+    pub fn reborrow(&mut self) -> SpanMut<'_, T> {
+        SpanMut::from_span(self.span)
+    }
+
+    /// Converts the span into a mutable slice bound to the lifetime `'a`.
+    #[inline]
+    pub fn into_slice(mut self) -> &'a mut [T] {
+        let slice = self.span.as_mut();
+        unsafe { core::slice::from_raw_parts_mut(slice.as_mut_ptr(), slice.len()) }
+    }
+
+    /// Constructs an iterator.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.span.iter()
+    }
+
+    /// Constructs a mutable iterator.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.span.iter_mut()
+    }
+
+    /// Detaches the lifetime from the span.
+    #[inline]
+    pub fn into_unbound(self) -> MutSpan<T> {
+        self.span
+    }
+}
+
+unsafe impl<T> Send for SpanMut<'_, T> where T: Copy + Sized + Send {}
+unsafe impl<T> Sync for SpanMut<'_, T> where T: Copy + Sized + Sync {}
+
+impl<T> AsRef<[T]> for SpanMut<'_, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.span.as_ref()
+    }
+}
+
+impl<T> AsMut<[T]> for SpanMut<'_, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn as_mut(&mut self) -> &mut [T] {
+        self.span.as_mut()
+    }
+}
+
+impl<T> Default for SpanMut<'_, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Deref for SpanMut<'_, T>
+where
+    T: Copy + Sized,
+{
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_ref()
+    }
+}
+
+impl<T> DerefMut for SpanMut<'_, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut()
+    }
+}
+
+impl<T> Debug for SpanMut<'_, T>
+where
+    T: Copy + Debug + Sized,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.as_ref(), f)
+    }
+}
+
+impl<T> PartialEq for SpanMut<'_, T>
+where
+    T: Copy + PartialEq + Sized,
+{
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl<T> Eq for SpanMut<'_, T> where T: Copy + Eq + Sized {}
+
+impl<T> Hash for SpanMut<'_, T>
+where
+    T: Copy + Hash + Sized,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_ref().hash(state)
+    }
+}
+
+impl<'a, T> IntoIterator for SpanMut<'a, T>
+where
+    T: Copy + Sized,
+{
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_slice().iter_mut()
+    }
+}
+
+impl<'a, T> From<&'a mut T> for SpanMut<'a, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn from(p: &'a mut T) -> Self {
+        Self::from_span(MutSpan::from(p))
+    }
+}
+
+impl<'a, T> From<&'a mut [T]> for SpanMut<'a, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn from(p: &'a mut [T]) -> Self {
+        Self::from_span(MutSpan::from(p))
+    }
+}
+
+impl<'a, T, const N: usize> From<&'a mut [T; N]> for SpanMut<'a, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn from(p: &'a mut [T; N]) -> Self {
+        Self::from_span(MutSpan::from(p))
+    }
+}
+
+impl<'a, T> From<&'a mut Vec<T>> for SpanMut<'a, T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn from(p: &'a mut Vec<T>) -> Self {
+        Self::from_span(MutSpan::from(p))
+    }
+}
+
+impl<T> From<SpanMut<'_, T>> for MutSpan<T>
+where
+    T: Copy + Sized,
+{
+    #[inline]
+    fn from(p: SpanMut<'_, T>) -> Self {
+        p.into_unbound()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::{ConstSpan, MutSpan, SpanMut, SpanRef};
+    use alloc::string::String;
+    use core::mem::{align_of, size_of};
+
+    #[test]
+    fn layout() {
+        assert_eq!(size_of::<SpanRef<'_, u32>>(), size_of::<ConstSpan<u32>>());
+        assert_eq!(align_of::<SpanRef<'_, u32>>(), align_of::<ConstSpan<u32>>());
+        assert_eq!(size_of::<SpanMut<'_, u32>>(), size_of::<MutSpan<u32>>());
+        assert_eq!(align_of::<SpanMut<'_, u32>>(), align_of::<MutSpan<u32>>());
+    }
+
+    #[test]
+    fn borrow() {
+        let string = String::from("1.2.3");
+        let span = SpanRef::from(&string);
+        assert_eq!(span.as_slice(), b"1.2.3");
+        assert_eq!(ConstSpan::from(span).as_ref(), b"1.2.3");
+        assert!(SpanRef::<u8>::new().is_empty());
+
+        let mut buffer = [0u8; 4];
+        let mut span = SpanMut::from(&mut buffer);
+        span.reborrow()[0] = 1;
+        span.iter_mut().skip(1).for_each(|v| *v = 2);
+        assert_eq!(SpanRef::from(span).as_slice(), [1, 2, 2, 2]);
+        assert_eq!(buffer, [1, 2, 2, 2]);
+    }
+}
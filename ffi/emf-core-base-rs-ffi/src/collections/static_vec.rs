@@ -1,9 +1,43 @@
-use std::cmp::{min, Ordering};
-use std::fmt::{Debug, Display, Formatter};
-use std::hash::{Hash, Hasher};
-use std::ops::{Deref, DerefMut};
-use std::os::raw::c_char;
-use std::slice::{Iter, IterMut};
+use alloc::vec::Vec;
+use core::cmp::{min, Ordering};
+use core::convert::TryFrom;
+use core::ffi::c_char;
+use core::fmt::{Debug, Display, Formatter};
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+use core::slice::{Iter, IterMut};
+use core::str::Utf8Error;
+
+/// Error signaling that the capacity of a [StaticVec] is exceeded.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct CapacityError {
+    capacity: usize,
+    required: usize,
+}
+
+impl CapacityError {
+    /// Retrieves the capacity of the vector.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Retrieves the number of elements which were required.
+    pub fn required(&self) -> usize {
+        self.required
+    }
+}
+
+impl Display for CapacityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Required capacity {} exceeds the capacity {}",
+            self.required, self.capacity
+        )
+    }
+}
+
+impl core::error::Error for CapacityError {}
 
 /// A contiguous statically sized array type.
 #[repr(C)]
@@ -63,7 +97,7 @@ where
 
         if index < self.length {
             unsafe {
-                std::ptr::copy(
+                core::ptr::copy(
                     self.as_ptr().add(index),
                     self.as_mut_ptr().add(index + 1),
                     self.length - index,
@@ -78,7 +112,7 @@ where
 
     /// Creates a new empty vector.
     pub fn new() -> Self {
-        unsafe { std::mem::zeroed() }
+        unsafe { core::mem::zeroed() }
     }
 
     /// Removes and returns the element at position `index` within the vector,
@@ -91,7 +125,7 @@ where
 
         if index < (self.length - 1) {
             unsafe {
-                std::ptr::copy(
+                core::ptr::copy(
                     self.as_ptr().add(index + 1),
                     self.as_mut_ptr().add(index),
                     self.length - 1 - index,
@@ -104,13 +138,43 @@ where
 
     /// Appends the value to the end of the vector, returns `true` on success.
     pub fn push(&mut self, value: T) -> bool {
-        if self.length == self.capacity() {
-            return false;
-        }
+        self.try_push(value).is_ok()
+    }
+
+    /// Appends the value to the end of the vector.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the vector is full.
+    pub fn try_push(&mut self, value: T) -> Result<(), CapacityError> {
+        self.check_capacity(1)?;
         self.data[self.length] = value;
         self.length += 1;
 
-        true
+        Ok(())
+    }
+
+    /// Appends all elements of `slice` to the end of the vector.
+    ///
+    /// # Failure
+    ///
+    /// Fails without modifying the vector if not all elements fit.
+    pub fn try_extend_from_slice(&mut self, slice: &[T]) -> Result<(), CapacityError> {
+        self.check_capacity(slice.len())?;
+        self.data[self.length..self.length + slice.len()].copy_from_slice(slice);
+        self.length += slice.len();
+
+        Ok(())
+    }
+
+    fn check_capacity(&self, additional: usize) -> Result<(), CapacityError> {
+        match self.length.checked_add(additional) {
+            Some(required) if required <= N => Ok(()),
+            required => Err(CapacityError {
+                capacity: N,
+                required: required.unwrap_or(usize::MAX),
+            }),
+        }
     }
 
     /// Removes the last element from the vector and returns it, or `None` if it is empty.
@@ -150,6 +214,49 @@ where
     }
 }
 
+impl<const N: usize> StaticVec<u8, N> {
+    /// Constructs a vector from the longest prefix of `str` which fits into the capacity.
+    ///
+    /// The string is only truncated on a character boundary.
+    pub fn from_str_lossy(str: &str) -> Self {
+        Self::from(&str.as_bytes()[..floor_char_boundary(str, N)])
+    }
+
+    /// Interprets the vector as a `UTF-8` string.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the vector does not contain valid `UTF-8`.
+    pub fn as_str(&self) -> Result<&str, Utf8Error> {
+        core::str::from_utf8(self.as_slice())
+    }
+}
+
+impl<const N: usize> StaticVec<c_char, N> {
+    /// Constructs a vector from the longest prefix of `str` which fits into the capacity.
+    ///
+    /// The string is only truncated on a character boundary.
+    pub fn from_str_lossy(str: &str) -> Self {
+        Self::from(as_c_chars(&str[..floor_char_boundary(str, N)]))
+    }
+}
+
+/// Computes the largest character boundary of `str` not exceeding `index`.
+fn floor_char_boundary(str: &str, index: usize) -> usize {
+    if index >= str.len() {
+        str.len()
+    } else {
+        (0..=index)
+            .rev()
+            .find(|&i| str.is_char_boundary(i))
+            .unwrap_or(0)
+    }
+}
+
+fn as_c_chars(str: &str) -> &[c_char] {
+    unsafe { &*(str.as_bytes() as *const [u8] as *const [c_char]) }
+}
+
 unsafe impl<T, const N: usize> Send for StaticVec<T, N> where T: Copy + Sized + Send {}
 unsafe impl<T, const N: usize> Sync for StaticVec<T, N> where T: Copy + Sized + Sync {}
 
@@ -175,7 +282,7 @@ impl<T, const N: usize> Debug for StaticVec<T, N>
 where
     T: Copy + Sized + Debug,
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         self.as_slice().fmt(f)
     }
 }
@@ -245,8 +352,8 @@ where
     fn from(slice: &[T]) -> Self {
         let min_size = min(N, slice.len());
         unsafe {
-            let mut vec: Self = std::mem::zeroed();
-            std::ptr::copy_nonoverlapping(slice.as_ptr(), vec.as_mut_ptr(), min_size);
+            let mut vec: Self = core::mem::zeroed();
+            core::ptr::copy_nonoverlapping(slice.as_ptr(), vec.as_mut_ptr(), min_size);
             vec.set_len(min_size);
             vec
         }
@@ -260,23 +367,31 @@ where
     fn from(slice: &mut [T]) -> Self {
         let min_size = min(N, slice.len());
         unsafe {
-            let mut vec: Self = std::mem::zeroed();
-            std::ptr::copy_nonoverlapping(slice.as_ptr(), vec.as_mut_ptr(), min_size);
+            let mut vec: Self = core::mem::zeroed();
+            core::ptr::copy_nonoverlapping(slice.as_ptr(), vec.as_mut_ptr(), min_size);
             vec.set_len(min_size);
             vec
         }
     }
 }
 
-impl<const N: usize> From<&'_ str> for StaticVec<u8, N> {
-    fn from(str: &str) -> Self {
-        Self::from(str.as_bytes())
+impl<const N: usize> TryFrom<&'_ str> for StaticVec<u8, N> {
+    type Error = CapacityError;
+
+    fn try_from(str: &str) -> Result<Self, Self::Error> {
+        let mut vec = Self::new();
+        vec.try_extend_from_slice(str.as_bytes())?;
+        Ok(vec)
     }
 }
 
-impl<const N: usize> From<&'_ str> for StaticVec<c_char, N> {
-    fn from(str: &str) -> Self {
-        unsafe { Self::from(&*(str.as_bytes() as *const [u8] as *const [c_char])) }
+impl<const N: usize> TryFrom<&'_ str> for StaticVec<c_char, N> {
+    type Error = CapacityError;
+
+    fn try_from(str: &str) -> Result<Self, Self::Error> {
+        let mut vec = Self::new();
+        vec.try_extend_from_slice(as_c_chars(str))?;
+        Ok(vec)
     }
 }
 
@@ -341,11 +456,48 @@ where
 }
 
 impl<const N: usize> Display for StaticVec<u8, N> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if let Ok(v) = std::str::from_utf8(self.as_slice()) {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if let Ok(v) = self.as_str() {
             write!(f, "{}", v)
         } else {
             write!(f, "Invalid string")
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::collections::StaticVec;
+    use core::convert::TryFrom;
+
+    #[test]
+    fn checked() {
+        let mut vec = StaticVec::<u8, 4>::try_from("abc").unwrap();
+        assert_eq!(vec.as_str(), Ok("abc"));
+        assert!(vec.try_push(b'd').is_ok());
+
+        let err = vec.try_push(b'e').unwrap_err();
+        assert_eq!((err.capacity(), err.required()), (4, 5));
+
+        vec.clear();
+        assert!(vec.try_extend_from_slice(b"12345").is_err());
+        assert!(vec.is_empty());
+        assert!(StaticVec::<u8, 4>::try_from("12345").is_err());
+    }
+
+    #[test]
+    fn lossy() {
+        assert_eq!(
+            StaticVec::<u8, 4>::from_str_lossy("123456").as_str(),
+            Ok("1234")
+        );
+        assert_eq!(
+            StaticVec::<u8, 4>::from_str_lossy("aäöü").as_str(),
+            Ok("aä")
+        );
+        assert_eq!(StaticVec::<u8, 1>::from_str_lossy("ä").as_str(), Ok(""));
+
+        let invalid = StaticVec::<u8, 4>::from(&[0xFF, 0xFE][..]);
+        assert!(invalid.as_str().is_err());
+    }
+}
@@ -1,5 +1,5 @@
 //! Error api
-use std::fmt::{Debug, Display, Formatter};
+use core::fmt::{Debug, Display, Formatter};
 
 pub mod error;
 pub mod error_info;
@@ -14,24 +14,32 @@ pub struct StaticError<T: ?Sized + 'static + Display + Debug> {
 
 impl<T: ?Sized + 'static + Display + Debug + Sync> StaticError<T> {
     /// Constructs a new error.
-    pub fn new(error: &'static T) -> Self {
+    pub const fn new(error: &'static T) -> Self {
         Self { error }
     }
 }
 
+impl StaticError<str> {
+    /// Fetches the error message.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        self.error
+    }
+}
+
 impl<T: ?Sized + 'static + Display + Debug + Sync> Display for StaticError<T> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         Display::fmt(self.error, f)
     }
 }
 
 impl<T: ?Sized + 'static + Display + Debug + Sync> Debug for StaticError<T> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         Debug::fmt(self.error, f)
     }
 }
 
-impl<T: ?Sized + 'static + Display + Debug + Sync> std::error::Error for StaticError<T> {}
+impl<T: ?Sized + 'static + Display + Debug + Sync> core::error::Error for StaticError<T> {}
 
 /// Simple error.
 pub struct SimpleError<T: Display + Debug + Send> {
@@ -46,15 +54,15 @@ impl<T: Display + Debug + Send> SimpleError<T> {
 }
 
 impl<T: Display + Debug + Send> Display for SimpleError<T> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         Display::fmt(&self.error, f)
     }
 }
 
 impl<T: Display + Debug + Send> Debug for SimpleError<T> {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         Debug::fmt(&self.error, f)
     }
 }
 
-impl<T: Display + Debug + Send> std::error::Error for SimpleError<T> {}
+impl<T: Display + Debug + Send> core::error::Error for SimpleError<T> {}
@@ -1,9 +1,11 @@
 //! Error type.
 use crate::collections::{NonNullConst, Optional};
-use crate::errors::ErrorInfo;
+use crate::errors::{ErrorInfo, StaticError};
+use crate::guard::abort_on_unwind;
 use crate::TypeWrapper;
-use std::fmt::{Debug, Display, Formatter};
-use std::ptr::NonNull;
+use alloc::boxed::Box;
+use core::fmt::{Debug, Display, Formatter};
+use core::ptr::NonNull;
 
 /// Opaque structure representing an error.
 #[repr(C)]
@@ -63,14 +65,14 @@ impl ErrorRef {
 }
 
 impl Display for ErrorRef {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         Display::fmt(&self.display_info(), f)
     }
 }
 
 impl Debug for ErrorRef {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         Debug::fmt(&self.debug_info(), f)
     }
 }
@@ -85,6 +87,25 @@ pub struct Error {
 unsafe impl Send for Error {}
 
 impl Error {
+    /// Constructs a new error from a static error.
+    ///
+    /// The resulting error does not allocate, which makes it usable in
+    /// out-of-memory and early-initialization paths.
+    /// The debug info is identical to the display info.
+    #[inline]
+    pub const fn from_static(error: &'static StaticError<str>) -> Self {
+        unsafe {
+            Self {
+                internal: ErrorRef {
+                    data: Some(NonNullConst::new_unchecked(
+                        error as *const StaticError<str> as *const ErrorData,
+                    )),
+                    vtable: NonNullConst::new_unchecked(&<&'static StaticError<str>>::VTABLE),
+                },
+            }
+        }
+    }
+
     /// Lower-level source, if it exists.
     ///
     /// # Safety
@@ -117,21 +138,22 @@ impl Drop for Error {
 }
 
 impl Display for Error {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         Display::fmt(&self.internal, f)
     }
 }
 
 impl Debug for Error {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         Debug::fmt(&self.internal, f)
     }
 }
 
+/// Boxes the error, which only requires `alloc`.
 impl<T> From<T> for Error
 where
-    T: std::error::Error + Send,
+    T: core::error::Error + Send,
 {
     fn from(error: T) -> Self {
         Self::from(Box::new(box_error::OwnedError::new(error)))
@@ -175,13 +197,47 @@ pub trait AsErrorVTable {
     unsafe extern "C-unwind" fn debug_info_fn(data: Option<NonNullConst<ErrorData>>) -> ErrorInfo;
 }
 
+impl AsErrorVTable for &'static StaticError<str> {
+    const VTABLE: ErrorVTable = ErrorVTable {
+        cleanup_fn: TypeWrapper(Self::cleanup_fn),
+        source_fn: TypeWrapper(Self::source_fn),
+        display_info_fn: TypeWrapper(Self::display_info_fn),
+        debug_info_fn: TypeWrapper(Self::debug_info_fn),
+    };
+
+    unsafe extern "C-unwind" fn cleanup_fn(_data: Option<NonNull<ErrorData>>) {}
+
+    unsafe extern "C-unwind" fn source_fn(
+        _data: Option<NonNullConst<ErrorData>>,
+    ) -> Optional<ErrorRef> {
+        Optional::None
+    }
+
+    unsafe extern "C-unwind" fn display_info_fn(
+        data: Option<NonNullConst<ErrorData>>,
+    ) -> ErrorInfo {
+        abort_on_unwind(|| {
+            let error = &*data.unwrap().cast::<StaticError<str>>().as_ptr();
+            ErrorInfo::from_static(&error.error)
+        })
+    }
+
+    unsafe extern "C-unwind" fn debug_info_fn(data: Option<NonNullConst<ErrorData>>) -> ErrorInfo {
+        Self::display_info_fn(data)
+    }
+}
+
 mod box_error {
     use crate::collections::{NonNullConst, Optional};
     use crate::errors::error::{AsErrorVTable, Error as Err, ErrorData, ErrorRef, ErrorVTable};
     use crate::errors::ErrorInfo;
+    use crate::guard::abort_on_unwind;
     use crate::TypeWrapper;
-    use std::error::Error;
-    use std::ptr::NonNull;
+    use alloc::boxed::Box;
+    use alloc::format;
+    use alloc::string::String;
+    use core::error::Error;
+    use core::ptr::NonNull;
 
     pub struct InternalError {
         error: NonNullConst<dyn Error + 'static>,
@@ -236,31 +292,37 @@ mod box_error {
         unsafe extern "C-unwind" fn source_fn(
             data: Option<NonNullConst<ErrorData>>,
         ) -> Optional<ErrorRef> {
-            data.unwrap()
-                .cast::<InternalError>()
-                .as_ref()
-                .source()
-                .as_ref()
-                .map_or(Optional::None, |error| {
-                    Optional::Some(ErrorRef::from(error.as_ref()))
-                })
+            abort_on_unwind(|| {
+                data.unwrap()
+                    .cast::<InternalError>()
+                    .as_ref()
+                    .source()
+                    .as_ref()
+                    .map_or(Optional::None, |error| {
+                        Optional::Some(ErrorRef::from(error.as_ref()))
+                    })
+            })
         }
 
         unsafe extern "C-unwind" fn display_info_fn(
             data: Option<NonNullConst<ErrorData>>,
         ) -> ErrorInfo {
-            ErrorInfo::from(
-                data.unwrap()
-                    .cast::<InternalError>()
-                    .as_ref()
-                    .display_info(),
-            )
+            abort_on_unwind(|| {
+                ErrorInfo::from(
+                    data.unwrap()
+                        .cast::<InternalError>()
+                        .as_ref()
+                        .display_info(),
+                )
+            })
         }
 
         unsafe extern "C-unwind" fn debug_info_fn(
             data: Option<NonNullConst<ErrorData>>,
         ) -> ErrorInfo {
-            ErrorInfo::from(data.unwrap().cast::<InternalError>().as_ref().debug_info())
+            abort_on_unwind(|| {
+                ErrorInfo::from(data.unwrap().cast::<InternalError>().as_ref().debug_info())
+            })
         }
     }
 
@@ -319,39 +381,47 @@ mod box_error {
         };
 
         unsafe extern "C-unwind" fn cleanup_fn(data: Option<NonNull<ErrorData>>) {
-            drop(Box::<OwnedError<T>>::from_raw(
-                data.unwrap().cast().as_ptr(),
-            ))
+            abort_on_unwind(|| {
+                drop(Box::<OwnedError<T>>::from_raw(
+                    data.unwrap().cast().as_ptr(),
+                ))
+            })
         }
 
         unsafe extern "C-unwind" fn source_fn(
             data: Option<NonNullConst<ErrorData>>,
         ) -> Optional<ErrorRef> {
-            data.unwrap()
-                .cast::<OwnedError<T>>()
-                .as_ref()
-                .source()
-                .as_ref()
-                .map_or(Optional::None, |error| {
-                    Optional::Some(ErrorRef::from(error.as_ref()))
-                })
+            abort_on_unwind(|| {
+                data.unwrap()
+                    .cast::<OwnedError<T>>()
+                    .as_ref()
+                    .source()
+                    .as_ref()
+                    .map_or(Optional::None, |error| {
+                        Optional::Some(ErrorRef::from(error.as_ref()))
+                    })
+            })
         }
 
         unsafe extern "C-unwind" fn display_info_fn(
             data: Option<NonNullConst<ErrorData>>,
         ) -> ErrorInfo {
-            ErrorInfo::from(
-                data.unwrap()
-                    .cast::<OwnedError<T>>()
-                    .as_ref()
-                    .display_info(),
-            )
+            abort_on_unwind(|| {
+                ErrorInfo::from(
+                    data.unwrap()
+                        .cast::<OwnedError<T>>()
+                        .as_ref()
+                        .display_info(),
+                )
+            })
         }
 
         unsafe extern "C-unwind" fn debug_info_fn(
             data: Option<NonNullConst<ErrorData>>,
         ) -> ErrorInfo {
-            ErrorInfo::from(data.unwrap().cast::<OwnedError<T>>().as_ref().debug_info())
+            abort_on_unwind(|| {
+                ErrorInfo::from(data.unwrap().cast::<OwnedError<T>>().as_ref().debug_info())
+            })
         }
     }
 }
@@ -359,9 +429,10 @@ mod box_error {
 #[cfg(test)]
 mod tests {
     use crate::collections::Optional;
-    use crate::errors::Error as Err;
-    use std::error::Error;
-    use std::fmt::{Debug, Display, Formatter};
+    use crate::errors::{Error as Err, StaticError};
+    use alloc::format;
+    use core::error::Error;
+    use core::fmt::{Debug, Display, Formatter};
 
     #[derive(Copy, Clone)]
     struct MyError {
@@ -369,13 +440,13 @@ mod tests {
     }
 
     impl Debug for MyError {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
             f.write_str("my error debug!")
         }
     }
 
     impl Display for MyError {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
             f.write_str("my error display!")
         }
     }
@@ -392,13 +463,13 @@ mod tests {
     }
 
     impl Debug for MyInternalError {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
             Display::fmt(self.error, f)
         }
     }
 
     impl Display for MyInternalError {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
             Display::fmt(self.error, f)
         }
     }
@@ -435,4 +506,18 @@ mod tests {
 
         assert_eq!(unsafe { source.source() }, Optional::None)
     }
+
+    #[test]
+    fn static_error() {
+        static ERROR: StaticError<str> = StaticError::new("my static error!");
+        let error = Err::from_static(&ERROR);
+
+        assert_eq!(error.display_info().as_ref(), ERROR.as_str());
+        assert_eq!(error.debug_info().as_ref(), ERROR.as_str());
+        assert_eq!(
+            error.display_info().as_str().as_ptr(),
+            ERROR.as_str().as_ptr()
+        );
+        assert_eq!(unsafe { error.source() }, Optional::None)
+    }
 }
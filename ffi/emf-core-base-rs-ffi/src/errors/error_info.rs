@@ -1,8 +1,10 @@
 //! Error info type.
 use crate::collections::{ConstSpan, NonNullConst};
+use crate::guard::abort_on_unwind;
 use crate::TypeWrapper;
-use std::fmt::{Debug, Display, Formatter};
-use std::ptr::NonNull;
+use alloc::boxed::Box;
+use core::fmt::{Debug, Display, Formatter};
+use core::ptr::NonNull;
 
 /// `UTF-8` error string.
 pub type ErrorString = ConstSpan<u8>;
@@ -40,6 +42,21 @@ pub struct ErrorInfo {
 }
 
 impl ErrorInfo {
+    /// Constructs a new error info from a static string.
+    ///
+    /// The resulting error info does not allocate and can be cloned freely.
+    #[inline]
+    pub const fn from_static(info: &'static &'static str) -> Self {
+        unsafe {
+            Self {
+                data: Some(NonNull::new_unchecked(
+                    info as *const &str as *mut ErrorInfoData,
+                )),
+                vtable: NonNullConst::new_unchecked(&<&'static str>::VTABLE),
+            }
+        }
+    }
+
     /// Fetches the error string.
     #[inline]
     pub fn as_str(&self) -> ErrorString {
@@ -75,7 +92,10 @@ impl AsRef<str> for ErrorInfo {
             ""
         } else {
             unsafe {
-                std::str::from_utf8_unchecked(std::slice::from_raw_parts(data.as_ptr(), data.len()))
+                core::str::from_utf8_unchecked(core::slice::from_raw_parts(
+                    data.as_ptr(),
+                    data.len(),
+                ))
             }
         }
     }
@@ -83,14 +103,14 @@ impl AsRef<str> for ErrorInfo {
 
 impl Display for ErrorInfo {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.write_str(self.as_ref())
     }
 }
 
 impl Debug for ErrorInfo {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         f.write_str(self.as_ref())
     }
 }
@@ -149,26 +169,51 @@ where
     };
 
     unsafe extern "C-unwind" fn cleanup_fn(data: Option<NonNull<ErrorInfoData>>) {
-        drop(Box::<T>::from_raw(data.unwrap().cast().as_ptr()))
+        abort_on_unwind(|| drop(Box::<T>::from_raw(data.unwrap().cast().as_ptr())))
+    }
+
+    unsafe extern "C-unwind" fn clone_fn(
+        data: Option<NonNullConst<ErrorInfoData>>,
+    ) -> Option<NonNull<ErrorInfoData>> {
+        abort_on_unwind(|| {
+            let new: Box<T> = Box::new(data.unwrap().cast::<T>().as_ref().clone());
+            Some(NonNull::from(Box::leak(new)).cast())
+        })
+    }
+
+    unsafe extern "C-unwind" fn as_str_fn(
+        data: Option<NonNullConst<ErrorInfoData>>,
+    ) -> ErrorString {
+        abort_on_unwind(|| ErrorString::from(data.unwrap().cast::<T>().as_ref().as_ref()))
     }
+}
+
+impl AsErrorInfoVTable for &'static str {
+    const VTABLE: ErrorInfoVTable = ErrorInfoVTable {
+        cleanup_fn: TypeWrapper(Self::cleanup_fn),
+        clone_fn: TypeWrapper(Self::clone_fn),
+        as_str_fn: TypeWrapper(Self::as_str_fn),
+    };
+
+    unsafe extern "C-unwind" fn cleanup_fn(_data: Option<NonNull<ErrorInfoData>>) {}
 
     unsafe extern "C-unwind" fn clone_fn(
         data: Option<NonNullConst<ErrorInfoData>>,
     ) -> Option<NonNull<ErrorInfoData>> {
-        let new: Box<T> = Box::new(data.unwrap().cast::<T>().as_ref().clone());
-        Some(NonNull::from(Box::leak(new)).cast())
+        data.map(|data| data.into_mut())
     }
 
     unsafe extern "C-unwind" fn as_str_fn(
         data: Option<NonNullConst<ErrorInfoData>>,
     ) -> ErrorString {
-        ErrorString::from(data.unwrap().cast::<T>().as_ref().as_ref())
+        abort_on_unwind(|| ErrorString::from(*data.unwrap().cast::<&'static str>().as_ref()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::errors::ErrorInfo;
+    use alloc::boxed::Box;
 
     #[test]
     fn box_error() {
@@ -178,4 +223,14 @@ mod tests {
         assert_eq!(*error_info.as_ref(), **error_str);
         assert_eq!(*error_info.clone().as_ref(), **error_str);
     }
+
+    #[test]
+    fn static_error() {
+        static ERROR_STR: &str = "my static error message";
+        let error_info = ErrorInfo::from_static(&ERROR_STR);
+
+        assert_eq!(error_info.as_ref(), ERROR_STR);
+        assert_eq!(error_info.clone().as_ref(), ERROR_STR);
+        assert_eq!(error_info.as_str().as_ptr(), ERROR_STR.as_ptr());
+    }
 }
@@ -1,4 +1,168 @@
 //! Extensions to the `emf-core-base` interface.
+//!
+//! An extension is identified by its name and version. It is either exposed by the
+//! interface through [SysBinding::get_function] with a dedicated [FnId], or exported
+//! as an interface by a module, in which case it can be discovered with
+//! [ModuleBinding::get_exported_interface_handle]. The latter allows defining new
+//! extensions without extending [FnId].
+use crate::collections::{NonNullConst, Optional, Result, SpanRef};
+use crate::errors::{Error, StaticError};
+use crate::module::{InterfaceDescriptor, InterfaceName};
+use crate::version::Version;
+use crate::{Bool, CBase, CBaseBinding, FnId, TypeWrapper};
+use core::convert::TryFrom;
+use core::ptr::NonNull;
 
+#[cfg(feature = "shared_alloc")]
+pub mod alloc;
+#[cfg(feature = "log")]
+pub mod log;
 #[cfg(feature = "unwind_internal")]
 pub mod unwind_internal;
+
+/// Function returning the interface of an extension.
+pub type GetExtensionInterfaceFn<T> = TypeWrapper<
+    unsafe extern "C-unwind" fn(base_module: Option<NonNull<CBase>>) -> NonNullConst<T>,
+>;
+
+/// An extension to the interface.
+pub trait Extension {
+    /// Name of the extension.
+    const NAME: &'static str;
+
+    /// Version of the extension.
+    const VERSION: Version;
+
+    /// Id of the function returning the interface of the extension.
+    ///
+    /// Extensions without an id are discovered through the exported interfaces.
+    const FN_ID: Option<FnId> = None;
+
+    /// Type of the interface of the extension.
+    type Interface;
+
+    /// Constructs the descriptor of the extension interface.
+    #[inline]
+    fn descriptor() -> InterfaceDescriptor<'static> {
+        InterfaceDescriptor {
+            name: InterfaceName::try_from(Self::NAME)
+                .expect("interface name exceeds the maximum length"),
+            version: Self::VERSION,
+            extensions: SpanRef::new(),
+        }
+    }
+}
+
+/// Checks whether the extension is available.
+///
+/// # Safety
+///
+/// The function crosses the ffi boundary.
+pub unsafe fn has_extension<E: Extension>(interface: &impl CBaseBinding) -> bool {
+    match E::FN_ID {
+        Some(id) => interface.has_function(id) == Bool::True,
+        None => interface
+            .get_exported_interface_handle(NonNullConst::from(&E::descriptor()))
+            .into_rust()
+            .is_ok(),
+    }
+}
+
+/// Fetches the interface of an extension.
+///
+/// # Failure
+///
+/// Fails if the extension is not available.
+///
+/// # Return
+///
+/// Interface of the extension on success, error otherwise.
+///
+/// # Safety
+///
+/// The function crosses the ffi boundary.
+pub unsafe fn fetch_extension<E: Extension>(
+    interface: &impl CBaseBinding,
+) -> Result<NonNullConst<E::Interface>, Error> {
+    static NOT_AVAILABLE: StaticError<str> = StaticError::new("The extension is not available.");
+
+    match E::FN_ID {
+        Some(id) => match interface.get_function(id) {
+            Optional::Some(func) => {
+                let func: GetExtensionInterfaceFn<E::Interface> = core::mem::transmute(func);
+                Result::Ok(func(interface.base_module()))
+            }
+            Optional::None => Result::Err(Error::from_static(&NOT_AVAILABLE)),
+        },
+        None => {
+            let descriptor = E::descriptor();
+            let descriptor = NonNullConst::from(&descriptor);
+            let handle = match interface.get_exported_interface_handle(descriptor) {
+                Result::Ok(handle) => handle,
+                Result::Err(e) => return Result::Err(e),
+            };
+            match interface.get_interface(handle, descriptor) {
+                Result::Ok(extension) => Result::Ok(NonNullConst::from(
+                    extension.interface.cast::<E::Interface>(),
+                )),
+                Result::Err(e) => Result::Err(e),
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{fetch_extension, has_extension, Extension};
+    use crate::record::{Call, Recording, ReplayInterface, Value};
+    use crate::version::{ReleaseType, Version};
+    use crate::FnId;
+
+    struct TestExtension;
+
+    impl Extension for TestExtension {
+        const NAME: &'static str = "test::extension";
+        const VERSION: Version = Version {
+            major: 1,
+            minor: 0,
+            patch: 0,
+            build: 0,
+            release_number: 0,
+            release_type: ReleaseType::Stable,
+        };
+        type Interface = i32;
+    }
+
+    static INTERFACE: i32 = 42;
+
+    #[test]
+    fn exported_extension() {
+        let call = |id, result| Call {
+            id,
+            args: Vec::new(),
+            result,
+        };
+        let recording = Recording::from(vec![
+            call(FnId::ModuleGetExportedInterfaceHandle, Value::Int(1)),
+            call(
+                FnId::ModuleGetInterface,
+                Value::Ptr(&INTERFACE as *const i32 as u64),
+            ),
+            call(
+                FnId::ModuleGetExportedInterfaceHandle,
+                Value::Err("not exported".to_string()),
+            ),
+        ]);
+        let replay = ReplayInterface::new(recording);
+        let interface = replay.interface();
+
+        unsafe {
+            let extension = fetch_extension::<TestExtension>(&interface)
+                .into_rust()
+                .unwrap();
+            assert_eq!(*extension.as_ref(), 42);
+            assert!(!has_extension::<TestExtension>(&interface));
+        }
+        assert_eq!(replay.remaining(), 0);
+    }
+}
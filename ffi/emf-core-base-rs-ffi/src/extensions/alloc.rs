@@ -0,0 +1,305 @@
+//! The `alloc` extension.
+//!
+//! Every module may be linked against its own allocator, therefore memory must be
+//! released by the allocator it was obtained from. The extension exports a single allocator,
+//! shared by all modules, so that memory allocated in one module can be released in another.
+//!
+//! Memory obtained from the unaligned functions is aligned to [DEFAULT_ALIGNMENT] and
+//! must be released with [AllocBinding::dealloc]. Memory obtained from the aligned
+//! functions must be released with [AllocBinding::dealloc_aligned].
+//!
+//! The ffi collections can be built on the shared allocator with
+//! [FfiVec::from_vec_in](crate::collections::FfiVec::from_vec_in),
+//! [FfiBox::new_in](crate::collections::FfiBox::new_in) and
+//! [FfiString::from_string_in](crate::collections::FfiString::from_string_in).
+//! Their vtable releases them through the [AllocInterface] they were obtained from.
+use crate::collections::NonNullConst;
+use crate::extensions::Extension;
+use crate::version::{ReleaseType, Version};
+use crate::TypeWrapper;
+use core::alloc::Layout;
+use core::ffi::c_void;
+use core::mem::{align_of, size_of};
+use core::ptr::NonNull;
+
+/// Name of the extension.
+pub const ALLOC_INTERFACE_NAME: &str = "emf::cbase::alloc";
+
+/// Major version.
+pub const ALLOC_VERSION_MAJOR: i32 = 0;
+
+/// Minor version.
+pub const ALLOC_VERSION_MINOR: i32 = 1;
+
+/// Patch version.
+pub const ALLOC_VERSION_PATCH: i32 = 0;
+
+/// Release type.
+pub const ALLOC_VERSION_RELEASE_TYPE: ReleaseType = ReleaseType::Stable;
+
+/// Release number.
+pub const ALLOC_VERSION_RELEASE_NUMBER: i8 = 0;
+
+/// Build number.
+pub const ALLOC_VERSION_BUILD: i64 = 0;
+
+/// Version string.
+pub const ALLOC_VERSION_STRING: &str = "0.1.0";
+
+/// Alignment of the memory returned by the unaligned functions.
+pub const DEFAULT_ALIGNMENT: usize = 16;
+
+/// Opaque structure representing an allocator.
+#[repr(C)]
+pub struct Allocator {
+    _dummy: [u8; 0],
+}
+
+pub type AllocFn = TypeWrapper<
+    unsafe extern "C-unwind" fn(
+        allocator: Option<NonNull<Allocator>>,
+        size: usize,
+    ) -> Option<NonNull<c_void>>,
+>;
+pub type ReallocFn = TypeWrapper<
+    unsafe extern "C-unwind" fn(
+        allocator: Option<NonNull<Allocator>>,
+        ptr: Option<NonNull<c_void>>,
+        size: usize,
+    ) -> Option<NonNull<c_void>>,
+>;
+pub type DeallocFn = TypeWrapper<
+    unsafe extern "C-unwind" fn(
+        allocator: Option<NonNull<Allocator>>,
+        ptr: Option<NonNull<c_void>>,
+    ),
+>;
+pub type AllocAlignedFn = TypeWrapper<
+    unsafe extern "C-unwind" fn(
+        allocator: Option<NonNull<Allocator>>,
+        size: usize,
+        alignment: usize,
+    ) -> Option<NonNull<c_void>>,
+>;
+pub type ReallocAlignedFn = TypeWrapper<
+    unsafe extern "C-unwind" fn(
+        allocator: Option<NonNull<Allocator>>,
+        ptr: Option<NonNull<c_void>>,
+        size: usize,
+        alignment: usize,
+    ) -> Option<NonNull<c_void>>,
+>;
+pub type DeallocAlignedFn = TypeWrapper<
+    unsafe extern "C-unwind" fn(
+        allocator: Option<NonNull<Allocator>>,
+        ptr: Option<NonNull<c_void>>,
+        alignment: usize,
+    ),
+>;
+
+/// VTable of an allocator.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct AllocVTable {
+    pub alloc_fn: AllocFn,
+    pub realloc_fn: ReallocFn,
+    pub dealloc_fn: DeallocFn,
+    pub alloc_aligned_fn: AllocAlignedFn,
+    pub realloc_aligned_fn: ReallocAlignedFn,
+    pub dealloc_aligned_fn: DeallocAlignedFn,
+}
+
+/// Extension interface.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct AllocInterface {
+    pub allocator: Option<NonNull<Allocator>>,
+    pub vtable: NonNullConst<AllocVTable>,
+}
+
+unsafe impl Send for AllocInterface {}
+unsafe impl Sync for AllocInterface {}
+
+impl Extension for AllocInterface {
+    const NAME: &'static str = ALLOC_INTERFACE_NAME;
+    const VERSION: Version = Version {
+        major: ALLOC_VERSION_MAJOR,
+        minor: ALLOC_VERSION_MINOR,
+        patch: ALLOC_VERSION_PATCH,
+        build: ALLOC_VERSION_BUILD,
+        release_number: ALLOC_VERSION_RELEASE_NUMBER,
+        release_type: ALLOC_VERSION_RELEASE_TYPE,
+    };
+    type Interface = Self;
+}
+
+/// Helper trait for using the extension.
+pub trait AllocBinding {
+    /// Allocates `size` bytes aligned to [DEFAULT_ALIGNMENT].
+    ///
+    /// # Return
+    ///
+    /// Pointer to the memory, [Option::None] on failure.
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    unsafe fn alloc(&self, size: usize) -> Option<NonNull<c_void>>;
+
+    /// Resizes the memory pointed to by `ptr` to `size` bytes.
+    ///
+    /// Behaves like [AllocBinding::alloc], if `ptr` is [Option::None].
+    ///
+    /// # Return
+    ///
+    /// Pointer to the resized memory, [Option::None] on failure,
+    /// in which case `ptr` remains valid.
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    /// `ptr` must have been returned by [AllocBinding::alloc] or [AllocBinding::realloc].
+    unsafe fn realloc(&self, ptr: Option<NonNull<c_void>>, size: usize) -> Option<NonNull<c_void>>;
+
+    /// Releases the memory pointed to by `ptr`.
+    ///
+    /// Does nothing, if `ptr` is [Option::None].
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    /// `ptr` must have been returned by [AllocBinding::alloc] or [AllocBinding::realloc].
+    unsafe fn dealloc(&self, ptr: Option<NonNull<c_void>>);
+
+    /// Allocates `size` bytes aligned to `alignment`.
+    ///
+    /// # Return
+    ///
+    /// Pointer to the memory, [Option::None] on failure.
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    /// `alignment` must be a power of two.
+    unsafe fn alloc_aligned(&self, size: usize, alignment: usize) -> Option<NonNull<c_void>>;
+
+    /// Resizes the memory pointed to by `ptr` to `size` bytes.
+    ///
+    /// Behaves like [AllocBinding::alloc_aligned], if `ptr` is [Option::None].
+    ///
+    /// # Return
+    ///
+    /// Pointer to the resized memory, [Option::None] on failure,
+    /// in which case `ptr` remains valid.
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    /// `ptr` must have been returned by [AllocBinding::alloc_aligned] or
+    /// [AllocBinding::realloc_aligned] with the same `alignment`.
+    unsafe fn realloc_aligned(
+        &self,
+        ptr: Option<NonNull<c_void>>,
+        size: usize,
+        alignment: usize,
+    ) -> Option<NonNull<c_void>>;
+
+    /// Releases the memory pointed to by `ptr`.
+    ///
+    /// Does nothing, if `ptr` is [Option::None].
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    /// `ptr` must have been returned by [AllocBinding::alloc_aligned] or
+    /// [AllocBinding::realloc_aligned] with the same `alignment`.
+    unsafe fn dealloc_aligned(&self, ptr: Option<NonNull<c_void>>, alignment: usize);
+}
+
+impl AllocBinding for AllocInterface {
+    #[inline]
+    unsafe fn alloc(&self, size: usize) -> Option<NonNull<c_void>> {
+        (self.vtable.as_ref().alloc_fn)(self.allocator, size)
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: Option<NonNull<c_void>>, size: usize) -> Option<NonNull<c_void>> {
+        (self.vtable.as_ref().realloc_fn)(self.allocator, ptr, size)
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: Option<NonNull<c_void>>) {
+        (self.vtable.as_ref().dealloc_fn)(self.allocator, ptr)
+    }
+
+    #[inline]
+    unsafe fn alloc_aligned(&self, size: usize, alignment: usize) -> Option<NonNull<c_void>> {
+        (self.vtable.as_ref().alloc_aligned_fn)(self.allocator, size, alignment)
+    }
+
+    #[inline]
+    unsafe fn realloc_aligned(
+        &self,
+        ptr: Option<NonNull<c_void>>,
+        size: usize,
+        alignment: usize,
+    ) -> Option<NonNull<c_void>> {
+        (self.vtable.as_ref().realloc_aligned_fn)(self.allocator, ptr, size, alignment)
+    }
+
+    #[inline]
+    unsafe fn dealloc_aligned(&self, ptr: Option<NonNull<c_void>>, alignment: usize) {
+        (self.vtable.as_ref().dealloc_aligned_fn)(self.allocator, ptr, alignment)
+    }
+}
+
+/// Allocates memory for `layout`, preceded by a copy of the `allocator`.
+///
+/// The allocator is stored directly in front of the returned pointer, so that the memory
+/// can be released by [dealloc_with_allocator] without knowing its origin.
+///
+/// # Return
+///
+/// Pointer to the memory, [Option::None] on failure.
+///
+/// # Safety
+///
+/// The function crosses the ffi boundary.
+pub(crate) unsafe fn alloc_with_allocator(
+    allocator: AllocInterface,
+    layout: Layout,
+) -> Option<NonNull<u8>> {
+    let (alignment, offset) = header_layout(layout.align());
+    let size = offset.checked_add(layout.size())?;
+    let base = allocator.alloc_aligned(size, alignment)?.cast::<u8>();
+
+    let data = NonNull::new_unchecked(base.as_ptr().add(offset));
+    header(data).as_ptr().write(allocator);
+    Some(data)
+}
+
+/// Releases memory allocated by [alloc_with_allocator].
+///
+/// # Safety
+///
+/// The function crosses the ffi boundary.
+/// `data` must have been returned by [alloc_with_allocator] with the same `alignment`.
+pub(crate) unsafe fn dealloc_with_allocator(data: NonNull<u8>, alignment: usize) {
+    let (alignment, offset) = header_layout(alignment);
+    let allocator = header(data).as_ptr().read();
+    let base = data.as_ptr().sub(offset);
+    allocator.dealloc_aligned(NonNull::new(base.cast()), alignment)
+}
+
+/// Computes the alignment of an allocation and the offset of its data.
+#[inline]
+fn header_layout(alignment: usize) -> (usize, usize) {
+    let alignment = alignment.max(align_of::<AllocInterface>());
+    let offset = (size_of::<AllocInterface>() + alignment - 1) & !(alignment - 1);
+    (alignment, offset)
+}
+
+#[inline]
+unsafe fn header(data: NonNull<u8>) -> NonNull<AllocInterface> {
+    NonNull::new_unchecked(data.as_ptr().sub(size_of::<AllocInterface>())).cast()
+}
@@ -0,0 +1,206 @@
+//! The `log` extension.
+//!
+//! The extension is exported as an interface by the module providing the logger.
+//! Any module can discover it through
+//! [ModuleBinding::get_exported_interface_handle](crate::module::api::ModuleBinding::get_exported_interface_handle).
+use crate::collections::{ConstSpan, NonNullConst};
+use crate::extensions::Extension;
+use crate::version::{ReleaseType, Version};
+use crate::TypeWrapper;
+use core::fmt::{Display, Formatter};
+use core::ptr::NonNull;
+
+/// Name of the extension.
+pub const LOG_INTERFACE_NAME: &str = "emf::cbase::log";
+
+/// Major version.
+pub const LOG_VERSION_MAJOR: i32 = 0;
+
+/// Minor version.
+pub const LOG_VERSION_MINOR: i32 = 1;
+
+/// Patch version.
+pub const LOG_VERSION_PATCH: i32 = 0;
+
+/// Release type.
+pub const LOG_VERSION_RELEASE_TYPE: ReleaseType = ReleaseType::Stable;
+
+/// Release number.
+pub const LOG_VERSION_RELEASE_NUMBER: i8 = 0;
+
+/// Build number.
+pub const LOG_VERSION_BUILD: i64 = 0;
+
+/// Version string.
+pub const LOG_VERSION_STRING: &str = "0.1.0";
+
+/// Verbosity level of a message.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Level {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Level::Error => write!(f, "ERROR"),
+            Level::Warn => write!(f, "WARN"),
+            Level::Info => write!(f, "INFO"),
+            Level::Debug => write!(f, "DEBUG"),
+            Level::Trace => write!(f, "TRACE"),
+        }
+    }
+}
+
+/// Maximum verbosity level of the messages accepted by a logger.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum LevelFilter {
+    Off = 0,
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+impl LevelFilter {
+    /// Checks whether messages of the verbosity `level` pass the filter.
+    #[inline]
+    pub const fn allows(self, level: Level) -> bool {
+        level as i32 <= self as i32
+    }
+}
+
+impl Display for LevelFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LevelFilter::Off => write!(f, "OFF"),
+            LevelFilter::Error => write!(f, "ERROR"),
+            LevelFilter::Warn => write!(f, "WARN"),
+            LevelFilter::Info => write!(f, "INFO"),
+            LevelFilter::Debug => write!(f, "DEBUG"),
+            LevelFilter::Trace => write!(f, "TRACE"),
+        }
+    }
+}
+
+/// A key/value pair attached to a message.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub struct KeyValue {
+    /// Utf-8 encoded key.
+    pub key: ConstSpan<u8>,
+    /// Utf-8 encoded value.
+    pub value: ConstSpan<u8>,
+}
+
+/// A message sent to a logger.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub struct Record {
+    /// Verbosity level.
+    pub level: Level,
+    /// Utf-8 encoded target, usually the path of the emitting module.
+    pub target: ConstSpan<u8>,
+    /// Utf-8 encoded message.
+    pub message: ConstSpan<u8>,
+    /// Key/value pairs attached to the message.
+    pub key_values: ConstSpan<KeyValue>,
+}
+
+/// Opaque structure representing a logger.
+#[repr(C)]
+pub struct Logger {
+    _dummy: [u8; 0],
+}
+
+pub type LogFn = TypeWrapper<
+    unsafe extern "C-unwind" fn(logger: Option<NonNull<Logger>>, record: NonNullConst<Record>),
+>;
+pub type MaxLevelFn =
+    TypeWrapper<unsafe extern "C-unwind" fn(logger: Option<NonNull<Logger>>) -> LevelFilter>;
+pub type FlushFn = TypeWrapper<unsafe extern "C-unwind" fn(logger: Option<NonNull<Logger>>)>;
+
+/// VTable of a logger.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct LogVTable {
+    pub log_fn: LogFn,
+    pub max_level_fn: MaxLevelFn,
+    pub flush_fn: FlushFn,
+}
+
+/// Extension interface.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct LogInterface {
+    pub logger: Option<NonNull<Logger>>,
+    pub vtable: NonNullConst<LogVTable>,
+}
+
+unsafe impl Send for LogInterface {}
+unsafe impl Sync for LogInterface {}
+
+impl Extension for LogInterface {
+    const NAME: &'static str = LOG_INTERFACE_NAME;
+    const VERSION: Version = Version {
+        major: LOG_VERSION_MAJOR,
+        minor: LOG_VERSION_MINOR,
+        patch: LOG_VERSION_PATCH,
+        build: LOG_VERSION_BUILD,
+        release_number: LOG_VERSION_RELEASE_NUMBER,
+        release_type: LOG_VERSION_RELEASE_TYPE,
+    };
+    type Interface = Self;
+}
+
+/// Helper trait for using the extension.
+pub trait LogBinding {
+    /// Sends a message to the logger.
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    unsafe fn log(&self, record: &Record);
+
+    /// Fetches the maximum verbosity level accepted by the logger.
+    ///
+    /// # Return
+    ///
+    /// Maximum verbosity level.
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    unsafe fn max_level(&self) -> LevelFilter;
+
+    /// Flushes the buffered messages.
+    ///
+    /// # Safety
+    ///
+    /// The function crosses the ffi boundary.
+    unsafe fn flush(&self);
+}
+
+impl LogBinding for LogInterface {
+    #[inline]
+    unsafe fn log(&self, record: &Record) {
+        (self.vtable.as_ref().log_fn)(self.logger, NonNullConst::from(record))
+    }
+
+    #[inline]
+    unsafe fn max_level(&self) -> LevelFilter {
+        (self.vtable.as_ref().max_level_fn)(self.logger)
+    }
+
+    #[inline]
+    unsafe fn flush(&self) {
+        (self.vtable.as_ref().flush_fn)(self.logger)
+    }
+}
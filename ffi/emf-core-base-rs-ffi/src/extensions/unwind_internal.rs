@@ -1,9 +1,10 @@
 //! The `unwind_internal` extension.
 use crate::collections::{NonNullConst, Optional};
 use crate::errors::Error;
-use crate::version::ReleaseType;
-use crate::{CBase, CBaseBinding, TypeWrapper};
-use std::ptr::NonNull;
+use crate::extensions::Extension;
+use crate::version::{ReleaseType, Version};
+use crate::{CBase, CBaseBinding, FnId, TypeWrapper};
+use core::ptr::NonNull;
 
 /// Name of the extension.
 pub const UNWIND_INTERNAL_INTERFACE_NAME: &str = "emf::cbase::unwind_internal";
@@ -82,6 +83,20 @@ pub struct UnwindInternalInterface {
 unsafe impl Send for UnwindInternalInterface {}
 unsafe impl Sync for UnwindInternalInterface {}
 
+impl Extension for UnwindInternalInterface {
+    const NAME: &'static str = UNWIND_INTERNAL_INTERFACE_NAME;
+    const VERSION: Version = Version {
+        major: UNWIND_INTERNAL_VERSION_MAJOR,
+        minor: UNWIND_INTERNAL_VERSION_MINOR,
+        patch: UNWIND_INTERNAL_VERSION_PATCH,
+        build: UNWIND_INTERNAL_VERSION_BUILD,
+        release_number: UNWIND_INTERNAL_VERSION_RELEASE_NUMBER,
+        release_type: UNWIND_INTERNAL_VERSION_RELEASE_TYPE,
+    };
+    const FN_ID: Option<FnId> = Some(FnId::ExtGetUnwindInternalInterface);
+    type Interface = Self;
+}
+
 /// Helper trait for using the extension.
 pub trait UnwindInternalBinding<T>
 where
@@ -0,0 +1,24 @@
+//! Guard preventing panics from unwinding across the ffi boundary.
+
+/// Aborts the process, if dropped while unwinding.
+struct AbortOnUnwind;
+
+impl Drop for AbortOnUnwind {
+    fn drop(&mut self) {
+        // Panicking while unwinding aborts the process.
+        panic!("A panic attempted to unwind across the ffi boundary, aborting")
+    }
+}
+
+/// Calls `f`, aborting the process if it panics.
+///
+/// Protects the Rust code called through the interface, like the functions of an
+/// error vtable or the hooks of a decorator, which must not unwind into foreign code.
+/// Unlike `catch_unwind`, it is available without the `std` feature.
+#[inline]
+pub(crate) fn abort_on_unwind<R>(f: impl FnOnce() -> R) -> R {
+    let guard = AbortOnUnwind;
+    let result = f();
+    core::mem::forget(guard);
+    result
+}
@@ -1,19 +1,41 @@
-use crate::collections::{ConstSpan, NonNullConst, Optional, Result};
-use crate::errors::StaticError;
+use crate::collections::{MutSpan, NonNullConst, Optional, Result, SpanRef};
+use crate::errors::{Error, StaticError};
 use crate::module::{api as mod_api, InterfaceDescriptor, InterfaceName};
 use crate::sys::api as sys_api;
-use crate::version::{
-    Version, VERSION_BUILD, VERSION_MAJOR, VERSION_MINOR, VERSION_PATCH, VERSION_RELEASE_NUMBER,
-    VERSION_RELEASE_TYPE,
-};
-use crate::{CBase, CBaseBinding, CBaseInterface, FnId, CBASE_INTERFACE_NAME};
-use std::ptr::NonNull;
+use crate::version::{api as ver_api, VERSION};
+use crate::{Bool, CBase, CBaseBinding, CBaseInterface, FnId, CBASE_INTERFACE_NAME};
+use alloc::vec;
+use core::convert::TryFrom;
+use core::ptr::NonNull;
 
 /// Types that can fetch an `emf-core-base` interface.
 pub trait CBaseLoader {
     /// Type of the interface.
     type Interface: CBaseBinding;
 
+    /// Fetches the `emf-core-base` interface.
+    ///
+    /// The loader accepts every exported interface whose version is compatible with
+    /// [VERSION], not only the exact version. Functions that are newer than the
+    /// version of the host must be checked with [sys_api::SysBinding::has_function]
+    /// before they are used.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the host does not export a compatible interface.
+    ///
+    /// # Return
+    ///
+    /// Interface on success, error otherwise.
+    ///
+    /// # Safety
+    ///
+    /// The parameter `get_function_fn` must be able to accept `base_module`.
+    unsafe fn try_fetch_interface(
+        base_module: Option<NonNull<CBase>>,
+        get_function_fn: sys_api::GetFunctionFn,
+    ) -> Result<Self::Interface, Error>;
+
     /// Fetches the `emf-core-base` interface.
     ///
     /// # Safety
@@ -26,69 +48,355 @@ pub trait CBaseLoader {
     unsafe fn fetch_interface(
         base_module: Option<NonNull<CBase>>,
         get_function_fn: sys_api::GetFunctionFn,
-    ) -> Self::Interface;
+    ) -> Self::Interface {
+        match Self::try_fetch_interface(base_module, get_function_fn) {
+            Result::Ok(interface) => interface,
+            Result::Err(e) => match get_function_fn(base_module, FnId::SysPanic) {
+                Optional::None => panic!("Unable to fetch the interface"),
+                Optional::Some(func) => {
+                    let panic_fn: sys_api::PanicFn = core::mem::transmute(func);
+                    panic_fn(base_module, Optional::Some(e))
+                }
+            },
+        }
+    }
+}
+
+/// Fetches a function pointer from the host.
+unsafe fn fetch_fn<T: Copy>(
+    base_module: Option<NonNull<CBase>>,
+    get_function_fn: sys_api::GetFunctionFn,
+    id: FnId,
+    error: &'static StaticError<str>,
+) -> core::result::Result<T, Error> {
+    match get_function_fn(base_module, id) {
+        Optional::None => Err(Error::from_static(error)),
+        Optional::Some(func) => Ok(core::mem::transmute_copy(&func)),
+    }
+}
+
+/// Searches the exported interfaces for the newest version compatible with [VERSION].
+unsafe fn find_compatible_interface(
+    base_module: Option<NonNull<CBase>>,
+    get_function_fn: sys_api::GetFunctionFn,
+    is_compatible_fn: ver_api::IsCompatibleFn,
+) -> core::result::Result<InterfaceDescriptor<'static>, Error> {
+    static NUM_EXPORTED_INTERFACES_ERROR: StaticError<str> = StaticError::new(
+        "Could not fetch the function pointer to `FnId::ModuleGetNumExportedInterfaces`.",
+    );
+    static EXPORTED_INTERFACES_ERROR: StaticError<str> = StaticError::new(
+        "Could not fetch the function pointer to `FnId::ModuleGetExportedInterfaces`.",
+    );
+    static COMPARE_ERROR: StaticError<str> =
+        StaticError::new("Could not fetch the function pointer to `FnId::VersionCompare`.");
+    static NO_COMPATIBLE_INTERFACE: StaticError<str> =
+        StaticError::new("The host does not export a compatible `emf::core_base` interface.");
+
+    let get_num_exported_interfaces_fn: mod_api::GetNumExportedInterfacesFn = fetch_fn(
+        base_module,
+        get_function_fn,
+        FnId::ModuleGetNumExportedInterfaces,
+        &NUM_EXPORTED_INTERFACES_ERROR,
+    )?;
+    let get_exported_interfaces_fn: mod_api::GetExportedInterfacesFn = fetch_fn(
+        base_module,
+        get_function_fn,
+        FnId::ModuleGetExportedInterfaces,
+        &EXPORTED_INTERFACES_ERROR,
+    )?;
+    let compare_fn: ver_api::CompareFn = fetch_fn(
+        base_module,
+        get_function_fn,
+        FnId::VersionCompare,
+        &COMPARE_ERROR,
+    )?;
+
+    let num_interfaces = get_num_exported_interfaces_fn(base_module);
+    let mut interfaces = vec![cbase_interface_desc(); num_interfaces];
+    let num_interfaces =
+        get_exported_interfaces_fn(base_module, MutSpan::from(&mut interfaces)).into_rust()?;
+
+    interfaces
+        .into_iter()
+        .take(num_interfaces)
+        .filter(|desc| {
+            desc.name.as_str() == Ok(CBASE_INTERFACE_NAME)
+                && is_compatible_fn(
+                    base_module,
+                    NonNullConst::from(&VERSION),
+                    NonNullConst::from(&desc.version),
+                ) == Bool::True
+        })
+        .max_by(|lhs, rhs| {
+            compare_fn(
+                base_module,
+                NonNullConst::from(&lhs.version),
+                NonNullConst::from(&rhs.version),
+            )
+            .cmp(&0)
+            .reverse()
+        })
+        .ok_or_else(|| Error::from_static(&NO_COMPATIBLE_INTERFACE))
+}
+
+/// Descriptor of the targeted interface version.
+fn cbase_interface_desc() -> InterfaceDescriptor<'static> {
+    InterfaceDescriptor {
+        name: InterfaceName::try_from(CBASE_INTERFACE_NAME)
+            .expect("interface name exceeds the maximum length"),
+        version: VERSION,
+        extensions: SpanRef::new(),
+    }
 }
 
 impl CBaseLoader for CBaseInterface {
     type Interface = Self;
 
-    unsafe fn fetch_interface(
+    unsafe fn try_fetch_interface(
         base_module: Option<NonNull<CBase>>,
         get_function_fn: sys_api::GetFunctionFn,
-    ) -> Self::Interface {
-        let panic_fn: sys_api::PanicFn = match get_function_fn(base_module, FnId::SysPanic) {
-            Optional::None => panic!("Unable to fetch the interface"),
-            Optional::Some(func) => std::mem::transmute(func),
-        };
+    ) -> Result<Self::Interface, Error> {
+        static EXPORTED_INTERFACE_HANDLE_ERROR: StaticError<str> = StaticError::new(
+            "Could not fetch the function pointer to `FnId::ModuleGetExportedInterfaceHandle`.",
+        );
+        static INTERFACE_ERROR: StaticError<str> =
+            StaticError::new("Could not fetch the function pointer to `FnId::ModuleGetInterface`.");
+        static IS_COMPATIBLE_ERROR: StaticError<str> = StaticError::new(
+            "Could not fetch the function pointer to `FnId::VersionIsCompatible`.",
+        );
+        static INCOMPATIBLE_VERSION: StaticError<str> =
+            StaticError::new("The version of the `emf::core_base` interface is incompatible.");
 
-        let get_exported_interface_handle_fn: mod_api::GetExportedInterfaceHandleFn =
-            match get_function_fn(base_module, FnId::ModuleGetExportedInterfaceHandle) {
-                Optional::None => {
-                    let error = From::from(StaticError::new("Could not fetch the function pointer to `FnId::ModuleGetExportedInterfaceHandle."));
-                    panic_fn(base_module, Optional::Some(error))
-                }
-                Optional::Some(func) => std::mem::transmute(func),
-            };
+        let fetch = || -> core::result::Result<Self::Interface, Error> {
+            let get_exported_interface_handle_fn: mod_api::GetExportedInterfaceHandleFn = fetch_fn(
+                base_module,
+                get_function_fn,
+                FnId::ModuleGetExportedInterfaceHandle,
+                &EXPORTED_INTERFACE_HANDLE_ERROR,
+            )?;
+            let get_interface_fn: mod_api::GetInterfaceFn = fetch_fn(
+                base_module,
+                get_function_fn,
+                FnId::ModuleGetInterface,
+                &INTERFACE_ERROR,
+            )?;
+            let is_compatible_fn: ver_api::IsCompatibleFn = fetch_fn(
+                base_module,
+                get_function_fn,
+                FnId::VersionIsCompatible,
+                &IS_COMPATIBLE_ERROR,
+            )?;
 
-        let get_interface_fn: mod_api::GetInterfaceFn =
-            match get_function_fn(base_module, FnId::ModuleGetInterface) {
-                Optional::None => {
-                    let error = From::from(StaticError::new(
-                        "Could not fetch the function pointer to `FnId::ModuleGetInterface.",
-                    ));
-                    panic_fn(base_module, Optional::Some(error))
+            // Prefer the targeted version and fall back to the newest compatible one.
+            let mut cbase_interface_desc = cbase_interface_desc();
+            let module_handle = match get_exported_interface_handle_fn(
+                base_module,
+                NonNullConst::from(&cbase_interface_desc),
+            ) {
+                Result::Ok(handle) => handle,
+                Result::Err(_) => {
+                    cbase_interface_desc =
+                        find_compatible_interface(base_module, get_function_fn, is_compatible_fn)?;
+                    get_exported_interface_handle_fn(
+                        base_module,
+                        NonNullConst::from(&cbase_interface_desc),
+                    )
+                    .into_rust()?
                 }
-                Optional::Some(func) => std::mem::transmute(func),
             };
 
-        let cbase_interface_desc = InterfaceDescriptor {
-            name: InterfaceName::from(CBASE_INTERFACE_NAME),
-            version: Version {
-                major: VERSION_MAJOR,
-                minor: VERSION_MINOR,
-                patch: VERSION_PATCH,
-                build: VERSION_BUILD,
-                release_number: VERSION_RELEASE_NUMBER,
-                release_type: VERSION_RELEASE_TYPE,
-            },
-            extensions: ConstSpan::new(),
+            let interface = get_interface_fn(
+                base_module,
+                module_handle,
+                NonNullConst::from(&cbase_interface_desc),
+            )
+            .into_rust()?;
+            let interface = *interface.interface.cast::<Self::Interface>().as_ref();
+
+            // The exported descriptor may differ from the version of the vtable.
+            let version = interface.interface_version();
+            if is_compatible_fn(
+                base_module,
+                NonNullConst::from(&VERSION),
+                NonNullConst::from(&version),
+            ) == Bool::False
+            {
+                return Err(Error::from_static(&INCOMPATIBLE_VERSION));
+            }
+
+            Ok(interface)
+        };
+
+        Result::from(fetch())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::CBaseLoader;
+    use crate::record::{Call, Recording, ReplayInterface, Value};
+    use crate::version::{Version, VERSION};
+    use crate::{CBaseBinding, CBaseInterface, FnId, CBASE_INTERFACE_NAME};
+
+    fn version(version: &Version) -> Value {
+        Value::List(vec![
+            Value::Int(version.major as i64),
+            Value::Int(version.minor as i64),
+            Value::Int(version.patch as i64),
+            Value::Int(version.build),
+            Value::Int(version.release_number as i64),
+            Value::Int(version.release_type as i64),
+        ])
+    }
+
+    fn descriptor(name: &str, version: &Version) -> Value {
+        Value::List(vec![
+            Value::Bytes(name.as_bytes().to_vec()),
+            self::version(version),
+            Value::List(vec![]),
+        ])
+    }
+
+    fn call(id: FnId, args: Vec<Value>, result: Value) -> Call {
+        Call { id, args, result }
+    }
+
+    /// Calls which fetch the interface exported with `desc`.
+    fn fetch_calls(desc: Value) -> Vec<Call> {
+        vec![
+            call(
+                FnId::ModuleGetExportedInterfaceHandle,
+                vec![desc.clone()],
+                Value::Int(3),
+            ),
+            call(
+                FnId::ModuleGetInterface,
+                vec![Value::Int(3), desc],
+                Value::Ptr(1),
+            ),
+            // Version of the vtable.
+            call(
+                FnId::VersionIsCompatible,
+                vec![version(&VERSION), version(&VERSION)],
+                Value::Int(1),
+            ),
+        ]
+    }
+
+    /// Calls `try_fetch_interface` with the replayed interface.
+    fn fetch(replay: &ReplayInterface) -> Result<CBaseInterface, String> {
+        let result = unsafe {
+            CBaseInterface::try_fetch_interface(
+                replay.interface().base_module(),
+                replay.get_function_fn(),
+            )
         };
+        result
+            .into_rust()
+            .map_err(|e| e.display_info().as_ref().to_string())
+    }
+
+    fn with_patch(patch: i32) -> Version {
+        Version { patch, ..VERSION }
+    }
 
-        let module_handle = match get_exported_interface_handle_fn(
-            base_module,
-            NonNullConst::from(&cbase_interface_desc),
-        ) {
-            Result::Ok(handle) => handle,
-            Result::Err(e) => panic_fn(base_module, Optional::Some(e)),
+    #[test]
+    fn exact_match() {
+        let desc = descriptor(CBASE_INTERFACE_NAME, &VERSION);
+        let replay = ReplayInterface::new(Recording::from(fetch_calls(desc)));
+
+        let interface = fetch(&replay).unwrap();
+        assert_eq!(interface.interface_version(), VERSION);
+        assert_eq!(replay.finish(), Ok(()));
+    }
+
+    #[test]
+    fn compatible_fallback() {
+        let incompatible = Version {
+            major: VERSION.major + 1,
+            ..VERSION
         };
+        let mut calls = vec![
+            call(
+                FnId::ModuleGetExportedInterfaceHandle,
+                vec![descriptor(CBASE_INTERFACE_NAME, &VERSION)],
+                Value::Err("Interface not found".to_string()),
+            ),
+            call(FnId::ModuleGetNumExportedInterfaces, vec![], Value::Int(4)),
+            call(
+                FnId::ModuleGetExportedInterfaces,
+                vec![Value::Int(4)],
+                Value::List(vec![
+                    Value::Int(4),
+                    Value::List(vec![
+                        descriptor("other_interface", &VERSION),
+                        descriptor(CBASE_INTERFACE_NAME, &with_patch(1)),
+                        descriptor(CBASE_INTERFACE_NAME, &with_patch(3)),
+                        descriptor(CBASE_INTERFACE_NAME, &incompatible),
+                    ]),
+                ]),
+            ),
+            call(
+                FnId::VersionIsCompatible,
+                vec![version(&VERSION), version(&with_patch(1))],
+                Value::Int(1),
+            ),
+            call(
+                FnId::VersionIsCompatible,
+                vec![version(&VERSION), version(&with_patch(3))],
+                Value::Int(1),
+            ),
+            // `lhs` < `rhs`.
+            call(
+                FnId::VersionCompare,
+                vec![version(&with_patch(1)), version(&with_patch(3))],
+                Value::Int(1),
+            ),
+            call(
+                FnId::VersionIsCompatible,
+                vec![version(&VERSION), version(&incompatible)],
+                Value::Int(0),
+            ),
+        ];
+        // The newest compatible version is selected.
+        calls.extend(fetch_calls(descriptor(
+            CBASE_INTERFACE_NAME,
+            &with_patch(3),
+        )));
+        let replay = ReplayInterface::new(Recording::from(calls));
 
-        match get_interface_fn(
-            base_module,
-            module_handle,
-            NonNullConst::from(&cbase_interface_desc),
-        ) {
-            Result::Ok(interface) => *interface.interface.cast::<Self::Interface>().as_ref(),
-            Result::Err(e) => panic_fn(base_module, Optional::Some(e)),
-        }
+        assert!(fetch(&replay).is_ok());
+        assert_eq!(replay.finish(), Ok(()));
+    }
+
+    #[test]
+    fn no_compatible_interface() {
+        let replay = ReplayInterface::new(Recording::from(vec![
+            call(
+                FnId::ModuleGetExportedInterfaceHandle,
+                vec![descriptor(CBASE_INTERFACE_NAME, &VERSION)],
+                Value::Err("Interface not found".to_string()),
+            ),
+            call(FnId::ModuleGetNumExportedInterfaces, vec![], Value::Int(1)),
+            call(
+                FnId::ModuleGetExportedInterfaces,
+                vec![Value::Int(1)],
+                Value::List(vec![
+                    Value::Int(1),
+                    Value::List(vec![descriptor(CBASE_INTERFACE_NAME, &with_patch(1))]),
+                ]),
+            ),
+            call(
+                FnId::VersionIsCompatible,
+                vec![version(&VERSION), version(&with_patch(1))],
+                Value::Int(0),
+            ),
+        ]));
+
+        assert_eq!(
+            fetch(&replay).unwrap_err(),
+            "The host does not export a compatible `emf::core_base` interface."
+        );
+        assert_eq!(replay.finish(), Ok(()));
     }
 }
@@ -0,0 +1,631 @@
+//! Interception of the interface functions.
+//!
+//! An [InterceptedInterface] wraps an existing interface and forwards every call
+//! to it, invoking the [InterceptHooks] before and after each call. This allows
+//! layering tracing, metrics, argument validation or fault injection on top of
+//! the interface, without modifying the host.
+//!
+//! The hooks can additionally restrict the functions available through the decorator
+//! with [InterceptHooks::permits], which allows handing out capability-restricted
+//! interfaces, e.g. to untrusted modules.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use emf_core_base_rs_ffi::CBaseInterface;
+//! # let interface: CBaseInterface = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+//! use emf_core_base_rs_ffi::intercept::{InterceptHooks, InterceptedInterface};
+//! use emf_core_base_rs_ffi::sys::api::SysBinding;
+//! use emf_core_base_rs_ffi::FnId;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! #[derive(Debug, Default)]
+//! struct CallCounter(AtomicUsize);
+//!
+//! impl InterceptHooks for CallCounter {
+//!     fn before(&self, _id: FnId) {
+//!         self.0.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let intercepted = unsafe { InterceptedInterface::new(interface, CallCounter::default()) };
+//! unsafe {
+//!     SysBinding::lock(&*intercepted);
+//!     SysBinding::unlock(&*intercepted);
+//! }
+//! assert_eq!(intercepted.hooks().0.load(Ordering::Relaxed), 2);
+//! ```
+use crate::cbase::{api_functions, impl_cbase_binding};
+use crate::collections::{ConstSpan, MutSpan, NonNullConst, Optional, Result};
+use crate::errors::{Error, SimpleError};
+use crate::guard::abort_on_unwind;
+use crate::library::api::impl_library_binding;
+use crate::library::library_loader::LibraryLoaderInterface;
+use crate::library::{
+    InternalHandle as InternalLibraryHandle, LibraryHandle, LibraryType,
+    LoaderHandle as LibraryLoaderHandle, OSPathString, Symbol, SymbolName,
+};
+use crate::module::api::{self as mod_api, impl_module_binding};
+use crate::module::module_loader::ModuleLoaderInterface;
+use crate::module::{
+    Interface, InterfaceDescriptor, InternalHandle as InternalModuleHandle,
+    LoaderHandle as ModuleLoaderHandle, ModuleHandle, ModuleInfo, ModuleStatus, ModuleType,
+};
+use crate::sys::api::{self as sys_api, impl_sys_binding, GetFunctionFn};
+use crate::sys::sync_handler::SyncHandlerInterface;
+use crate::version::api::impl_version_binding;
+use crate::version::{ReleaseType, Version};
+use crate::{
+    Bool, CBase, CBaseBinding, CBaseFn, CBaseInterface, CBaseInterfaceVTable, FnId, TypeWrapper,
+    CBASE_INTERFACE_NAME,
+};
+use alloc::boxed::Box;
+use alloc::format;
+use core::ffi::c_void;
+use core::marker::PhantomPinned;
+use core::pin::Pin;
+use core::ptr::NonNull;
+
+#[cfg(feature = "unwind_internal")]
+use crate::extensions::unwind_internal::{
+    self, Context, GetUnwindInternalInterfaceFn, UnwindInternalInterface,
+};
+
+/// Hooks invoked around every intercepted call.
+///
+/// The hooks may be called concurrently from multiple threads, as the interface
+/// can be called without holding the lock,
+/// e.g. [SysBinding::try_lock](crate::sys::api::SysBinding::try_lock).
+/// They must not panic, as a panicking hook aborts the process.
+pub trait InterceptHooks: Send + Sync {
+    /// Called before the call is forwarded to the wrapped interface.
+    #[inline]
+    fn before(&self, _id: FnId) {}
+
+    /// Called after the wrapped interface returned.
+    ///
+    /// Is not called if the function unwinds or does not return,
+    /// like [FnId::SysShutdown] and [FnId::SysPanic].
+    #[inline]
+    fn after(&self, _id: FnId) {}
+
+    /// Checks whether the function may be called through the decorator.
+    ///
+    /// Functions which are not permitted are reported as missing by
+    /// [SysBinding::has_function](crate::sys::api::SysBinding::has_function) and
+    /// [SysBinding::get_function](crate::sys::api::SysBinding::get_function).
+    /// Calling them anyway through the vtable panics the interface.
+    /// [FnId::SysPanic] is always permitted, as it is used to report the violation.
+    #[inline]
+    fn permits(&self, _id: FnId) -> bool {
+        true
+    }
+}
+
+/// An interface decorator invoking hooks around every call.
+///
+/// The decorator exposes its own interface with trampolines for every function,
+/// which forward to the wrapped interface. Functions fetched with
+/// [SysBinding::get_function](crate::sys::api::SysBinding::get_function) and the interface
+/// returned by [ModuleBinding::get_interface](crate::module::api::ModuleBinding::get_interface)
+/// for the `emf-core-base` interface are intercepted as well, therefore the decorator can
+/// be passed to the `initialize` functions through [CBaseBinding::base_module] and
+/// [InterceptedInterface::get_function_fn].
+///
+/// # Note
+///
+/// Functions of the `unwind_internal` extension are forwarded without invoking the hooks,
+/// except for `FnId::ExtGetUnwindInternalInterface`. Other extension functions are not
+/// exposed by the decorator.
+#[derive(Debug)]
+pub struct InterceptedInterface<H> {
+    interface: CBaseInterface,
+    vtable: CBaseInterfaceVTable,
+    inner: CBaseInterface,
+    #[cfg(feature = "unwind_internal")]
+    unwind_internal: Option<UnwindInternal>,
+    hooks: H,
+    _pinned: PhantomPinned,
+}
+
+/// The wrapped and the forwarding `unwind_internal` interfaces.
+#[cfg(feature = "unwind_internal")]
+#[derive(Debug)]
+struct UnwindInternal {
+    inner: NonNullConst<UnwindInternalInterface>,
+    interface: UnwindInternalInterface,
+}
+
+unsafe impl<H: Send> Send for InterceptedInterface<H> {}
+unsafe impl<H: Sync> Sync for InterceptedInterface<H> {}
+
+macro_rules! trampolines {
+    ($(
+        $field:ident => $id:ident,
+            fn($($arg:ident: $ty:ty),* $(,)?) $(-> $ret:ty)? $(, out $buffer:ident)?;
+    )*) => {
+        // `CBaseFn` only serves as a type-erased function pointer.
+        #[allow(improper_ctypes_definitions)]
+        impl<H: InterceptHooks> InterceptedInterface<H> {
+            $(
+                unsafe extern "C-unwind" fn $field(
+                    base_module: Option<NonNull<CBase>>,
+                    $($arg: $ty),*
+                ) $(-> $ret)? {
+                    Self::intercept(base_module, FnId::$id, |this| {
+                        (this.inner.vtable.as_ref().$field)(this.inner.base_module, $($arg),*)
+                    })
+                }
+            )*
+
+            /// Constructs the vtable of the trampolines.
+            fn build_vtable(version: Version) -> CBaseInterfaceVTable {
+                CBaseInterfaceVTable {
+                    version,
+                    sys_shutdown_fn: TypeWrapper(Self::sys_shutdown_fn),
+                    sys_panic_fn: TypeWrapper(Self::sys_panic_fn),
+                    sys_has_function_fn: TypeWrapper(Self::sys_has_function_fn),
+                    sys_get_function_fn: TypeWrapper(Self::sys_get_function_fn),
+                    module_get_interface_fn: TypeWrapper(Self::module_get_interface_fn),
+                    $($field: TypeWrapper(Self::$field)),*
+                }
+            }
+
+            /// Fetches the trampoline of a function.
+            unsafe fn trampoline(&self, id: FnId) -> Optional<CBaseFn> {
+                use core::mem::transmute;
+
+                let vtable = &self.vtable;
+                // SAFETY: `CBaseFn` only serves as a type-erased function pointer,
+                // which is cast back to the type of the function `id` by the caller.
+                match id {
+                    FnId::SysShutdown => Optional::Some(transmute::<sys_api::ShutdownFn, CBaseFn>(
+                        vtable.sys_shutdown_fn,
+                    )),
+                    FnId::SysPanic => {
+                        Optional::Some(transmute::<sys_api::PanicFn, CBaseFn>(vtable.sys_panic_fn))
+                    }
+                    FnId::SysHasFunction => Optional::Some(
+                        transmute::<sys_api::HasFunctionFn, CBaseFn>(vtable.sys_has_function_fn),
+                    ),
+                    FnId::SysGetFunction => Optional::Some(
+                        transmute::<GetFunctionFn, CBaseFn>(vtable.sys_get_function_fn),
+                    ),
+                    FnId::ModuleGetInterface => {
+                        Optional::Some(transmute::<mod_api::GetInterfaceFn, CBaseFn>(
+                            vtable.module_get_interface_fn,
+                        ))
+                    }
+                    $(
+                        FnId::$id => Optional::Some(transmute::<
+                            TypeWrapper<
+                                unsafe extern "C-unwind" fn(
+                                    Option<NonNull<CBase>>
+                                    $(, $ty)*
+                                ) $(-> $ret)?
+                            >,
+                            CBaseFn,
+                        >(vtable.$field)),
+                    )*
+                    #[cfg(feature = "unwind_internal")]
+                    FnId::ExtGetUnwindInternalInterface => match self.unwind_internal {
+                        Some(_) => {
+                            let func: GetUnwindInternalInterfaceFn =
+                                TypeWrapper(Self::ext_get_unwind_internal_interface_fn);
+                            Optional::Some(transmute::<GetUnwindInternalInterfaceFn, CBaseFn>(func))
+                        }
+                        None => Optional::None,
+                    },
+                }
+            }
+        }
+    };
+}
+
+api_functions!(trampolines! {
+    sys_lock_fn => SysLock, fn();
+    sys_try_lock_fn => SysTryLock, fn() -> Bool;
+    sys_unlock_fn => SysUnlock, fn();
+    sys_get_sync_handler_fn => SysGetSyncHandler, fn() -> SyncHandlerInterface;
+    sys_set_sync_handler_fn => SysSetSyncHandler, fn(handler: Optional<SyncHandlerInterface>);
+});
+
+impl<H: InterceptHooks> InterceptedInterface<H> {
+    /// Wraps an interface.
+    ///
+    /// # Safety
+    ///
+    /// The wrapped interface must outlive the decorator.
+    pub unsafe fn new(inner: CBaseInterface, hooks: H) -> Pin<Box<Self>> {
+        let mut intercepted = Box::new(Self {
+            interface: inner,
+            vtable: Self::build_vtable(inner.interface_version()),
+            inner,
+            #[cfg(feature = "unwind_internal")]
+            unwind_internal: Self::wrap_unwind_internal(&inner),
+            hooks,
+            _pinned: PhantomPinned,
+        });
+
+        intercepted.interface = CBaseInterface {
+            base_module: Some(NonNull::from(&*intercepted).cast()),
+            vtable: NonNullConst::from(&intercepted.vtable),
+        };
+        Box::into_pin(intercepted)
+    }
+
+    /// Fetches the interface exposed by the decorator.
+    #[inline]
+    pub fn interface(&self) -> CBaseInterface {
+        self.interface
+    }
+
+    /// Fetches the wrapped interface.
+    #[inline]
+    pub fn inner(&self) -> CBaseInterface {
+        self.inner
+    }
+
+    /// Fetches a reference to the hooks.
+    #[inline]
+    pub fn hooks(&self) -> &H {
+        &self.hooks
+    }
+
+    /// Fetches the `get_function` function of the decorator.
+    ///
+    /// Can be passed together with [CBaseBinding::base_module]
+    /// to the `initialize` functions.
+    #[inline]
+    pub fn get_function_fn(&self) -> GetFunctionFn {
+        self.vtable.sys_get_function_fn
+    }
+
+    /// Casts the `base_module` of a trampoline back to the decorator.
+    #[inline]
+    unsafe fn from_base_module<'a>(base_module: Option<NonNull<CBase>>) -> &'a Self {
+        &*base_module
+            .expect("Invalid base module")
+            .cast::<Self>()
+            .as_ptr()
+    }
+
+    /// Forwards a call, invoking the hooks around it.
+    #[inline]
+    unsafe fn intercept<R>(
+        base_module: Option<NonNull<CBase>>,
+        id: FnId,
+        f: impl FnOnce(&Self) -> R,
+    ) -> R {
+        let this = Self::from_base_module(base_module);
+        this.check_permitted(id);
+        this.before(id);
+        let result = f(this);
+        this.after(id);
+        result
+    }
+
+    /// Invokes [InterceptHooks::before], aborting if the hook panics.
+    #[inline]
+    fn before(&self, id: FnId) {
+        abort_on_unwind(|| self.hooks.before(id))
+    }
+
+    /// Invokes [InterceptHooks::after], aborting if the hook panics.
+    #[inline]
+    fn after(&self, id: FnId) {
+        abort_on_unwind(|| self.hooks.after(id))
+    }
+
+    /// Invokes [InterceptHooks::permits], aborting if the hook panics.
+    #[inline]
+    fn permits(&self, id: FnId) -> bool {
+        abort_on_unwind(|| self.hooks.permits(id))
+    }
+
+    /// Panics the wrapped interface, if the function is not permitted.
+    #[inline]
+    unsafe fn check_permitted(&self, id: FnId) {
+        if !self.permits(id) {
+            let error = SimpleError::new(format!("The function `{:?}` is not permitted", id));
+            (self.inner.vtable.as_ref().sys_panic_fn)(
+                self.inner.base_module,
+                Optional::Some(Error::from(error)),
+            )
+        }
+    }
+
+    unsafe extern "C-unwind" fn sys_shutdown_fn(base_module: Option<NonNull<CBase>>) -> ! {
+        let this = Self::from_base_module(base_module);
+        this.check_permitted(FnId::SysShutdown);
+        this.before(FnId::SysShutdown);
+        (this.inner.vtable.as_ref().sys_shutdown_fn)(this.inner.base_module)
+    }
+
+    unsafe extern "C-unwind" fn sys_panic_fn(
+        base_module: Option<NonNull<CBase>>,
+        error: Optional<Error>,
+    ) -> ! {
+        let this = Self::from_base_module(base_module);
+        this.before(FnId::SysPanic);
+        (this.inner.vtable.as_ref().sys_panic_fn)(this.inner.base_module, error)
+    }
+
+    unsafe extern "C-unwind" fn sys_has_function_fn(
+        base_module: Option<NonNull<CBase>>,
+        id: FnId,
+    ) -> Bool {
+        Self::intercept(base_module, FnId::SysHasFunction, |this| {
+            if id != FnId::SysPanic && !this.permits(id) {
+                return Bool::False;
+            }
+            // Functions without a trampoline are not exposed by `sys_get_function_fn`.
+            match this.trampoline(id) {
+                Optional::Some(_) => {
+                    (this.inner.vtable.as_ref().sys_has_function_fn)(this.inner.base_module, id)
+                }
+                Optional::None => Bool::False,
+            }
+        })
+    }
+
+    #[allow(improper_ctypes_definitions)]
+    unsafe extern "C-unwind" fn sys_get_function_fn(
+        base_module: Option<NonNull<CBase>>,
+        id: FnId,
+    ) -> Optional<CBaseFn> {
+        Self::intercept(base_module, FnId::SysGetFunction, |this| {
+            if id != FnId::SysPanic && !this.permits(id) {
+                return Optional::None;
+            }
+            match (this.inner.vtable.as_ref().sys_get_function_fn)(this.inner.base_module, id) {
+                Optional::Some(_) => this.trampoline(id),
+                Optional::None => Optional::None,
+            }
+        })
+    }
+
+    unsafe extern "C-unwind" fn module_get_interface_fn(
+        base_module: Option<NonNull<CBase>>,
+        handle: ModuleHandle,
+        interface: NonNullConst<InterfaceDescriptor<'static>>,
+    ) -> Result<Interface, Error> {
+        Self::intercept(base_module, FnId::ModuleGetInterface, |this| {
+            let result = (this.inner.vtable.as_ref().module_get_interface_fn)(
+                this.inner.base_module,
+                handle,
+                interface,
+            );
+
+            // Hand out the decorator instead of the wrapped `emf-core-base` interface.
+            match result {
+                Result::Ok(_) if interface.as_ref().name.as_str() == Ok(CBASE_INTERFACE_NAME) => {
+                    Result::Ok(Interface {
+                        interface: NonNull::from(&this.interface).cast(),
+                    })
+                }
+                result => result,
+            }
+        })
+    }
+}
+
+#[cfg(feature = "unwind_internal")]
+impl<H: InterceptHooks> InterceptedInterface<H> {
+    /// Wraps the `unwind_internal` interface of `inner`, if available.
+    unsafe fn wrap_unwind_internal(inner: &CBaseInterface) -> Option<UnwindInternal> {
+        let func: GetUnwindInternalInterfaceFn = match (inner.vtable.as_ref().sys_get_function_fn)(
+            inner.base_module,
+            FnId::ExtGetUnwindInternalInterface,
+        ) {
+            // SAFETY: The function was fetched with the id of the
+            // `get_unwind_internal_interface` function.
+            Optional::Some(func) => {
+                core::mem::transmute::<CBaseFn, GetUnwindInternalInterfaceFn>(func)
+            }
+            Optional::None => return None,
+        };
+
+        Some(UnwindInternal {
+            inner: func(inner.base_module),
+            interface: UnwindInternalInterface {
+                set_context_fn: TypeWrapper(Self::ext_set_context_fn),
+                get_context_fn: TypeWrapper(Self::ext_get_context_fn),
+                set_shutdown_fn_fn: TypeWrapper(Self::ext_set_shutdown_fn_fn),
+                get_shutdown_fn_fn: TypeWrapper(Self::ext_get_shutdown_fn_fn),
+                set_panic_fn_fn: TypeWrapper(Self::ext_set_panic_fn_fn),
+                get_panic_fn_fn: TypeWrapper(Self::ext_get_panic_fn_fn),
+            },
+        })
+    }
+
+    /// Fetches the wrapped `unwind_internal` interface.
+    #[inline]
+    unsafe fn unwind_inner(
+        base_module: Option<NonNull<CBase>>,
+    ) -> (UnwindInternalInterface, Option<NonNull<CBase>>) {
+        let this = Self::from_base_module(base_module);
+        let unwind_internal = this
+            .unwind_internal
+            .as_ref()
+            .expect("The `unwind_internal` interface is not available");
+        (*unwind_internal.inner.as_ref(), this.inner.base_module)
+    }
+
+    unsafe extern "C-unwind" fn ext_get_unwind_internal_interface_fn(
+        base_module: Option<NonNull<CBase>>,
+    ) -> NonNullConst<UnwindInternalInterface> {
+        Self::intercept(base_module, FnId::ExtGetUnwindInternalInterface, |this| {
+            let unwind_internal = this
+                .unwind_internal
+                .as_ref()
+                .expect("The `unwind_internal` interface is not available");
+            NonNullConst::from(&unwind_internal.interface)
+        })
+    }
+
+    unsafe extern "C-unwind" fn ext_set_context_fn(
+        base_module: Option<NonNull<CBase>>,
+        context: Option<NonNull<Context>>,
+    ) {
+        let (inner, base_module) = Self::unwind_inner(base_module);
+        (inner.set_context_fn)(base_module, context)
+    }
+
+    unsafe extern "C-unwind" fn ext_get_context_fn(
+        base_module: Option<NonNull<CBase>>,
+    ) -> Option<NonNull<Context>> {
+        let (inner, base_module) = Self::unwind_inner(base_module);
+        (inner.get_context_fn)(base_module)
+    }
+
+    unsafe extern "C-unwind" fn ext_set_shutdown_fn_fn(
+        base_module: Option<NonNull<CBase>>,
+        shutdown_fn: Option<unwind_internal::ShutdownFn>,
+    ) {
+        let (inner, base_module) = Self::unwind_inner(base_module);
+        (inner.set_shutdown_fn_fn)(base_module, shutdown_fn)
+    }
+
+    unsafe extern "C-unwind" fn ext_get_shutdown_fn_fn(
+        base_module: Option<NonNull<CBase>>,
+    ) -> Option<unwind_internal::ShutdownFn> {
+        let (inner, base_module) = Self::unwind_inner(base_module);
+        (inner.get_shutdown_fn_fn)(base_module)
+    }
+
+    unsafe extern "C-unwind" fn ext_set_panic_fn_fn(
+        base_module: Option<NonNull<CBase>>,
+        panic_fn: Option<unwind_internal::PanicFn>,
+    ) {
+        let (inner, base_module) = Self::unwind_inner(base_module);
+        (inner.set_panic_fn_fn)(base_module, panic_fn)
+    }
+
+    unsafe extern "C-unwind" fn ext_get_panic_fn_fn(
+        base_module: Option<NonNull<CBase>>,
+    ) -> Option<unwind_internal::PanicFn> {
+        let (inner, base_module) = Self::unwind_inner(base_module);
+        (inner.get_panic_fn_fn)(base_module)
+    }
+}
+
+impl_cbase_binding!(impl<H: InterceptHooks> for InterceptedInterface<H>, self.interface);
+impl_sys_binding!(impl<H: InterceptHooks> for InterceptedInterface<H>, self.interface);
+impl_version_binding!(impl<H: InterceptHooks> for InterceptedInterface<H>, self.interface);
+impl_library_binding!(impl<H: InterceptHooks> for InterceptedInterface<H>, self.interface);
+impl_module_binding!(impl<H: InterceptHooks> for InterceptedInterface<H>, self.interface);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::{InterceptHooks, InterceptedInterface};
+    use crate::collections::Optional;
+    use crate::module::api::ModuleBinding;
+    use crate::record::{Call, Recording, ReplayInterface, Value};
+    use crate::sys::api::{self as sys_api, SysBinding};
+    use crate::{Bool, CBaseBinding, CBaseFn, FnId};
+    use std::panic::AssertUnwindSafe;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Default)]
+    struct CallLog {
+        calls: Mutex<Vec<(&'static str, FnId)>>,
+        denied: Option<FnId>,
+    }
+
+    impl CallLog {
+        fn calls(&self) -> Vec<(&'static str, FnId)> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    impl InterceptHooks for CallLog {
+        fn before(&self, id: FnId) {
+            self.calls.lock().unwrap().push(("before", id))
+        }
+
+        fn after(&self, id: FnId) {
+            self.calls.lock().unwrap().push(("after", id))
+        }
+
+        fn permits(&self, id: FnId) -> bool {
+            self.denied != Some(id)
+        }
+    }
+
+    #[test]
+    fn hooks() {
+        let replay = ReplayInterface::new(Recording::from(vec![Call {
+            id: FnId::ModuleGetNumModules,
+            args: vec![],
+            result: Value::Int(3),
+        }]));
+        let intercepted =
+            unsafe { InterceptedInterface::new(replay.interface(), CallLog::default()) };
+
+        assert_eq!(unsafe { intercepted.get_num_modules() }, 3);
+        assert!(replay.divergences().is_empty());
+
+        // Fetched functions are intercepted as well.
+        let unlock = match unsafe { intercepted.get_function(FnId::SysUnlock) } {
+            // SAFETY: The function was fetched with the id of the `unlock` function.
+            Optional::Some(func) => unsafe {
+                std::mem::transmute::<CBaseFn, sys_api::UnlockFn>(func)
+            },
+            Optional::None => panic!("`unlock` is not available"),
+        };
+        unsafe {
+            SysBinding::lock(&*intercepted);
+            unlock(intercepted.base_module());
+        }
+
+        assert_eq!(
+            intercepted.hooks().calls(),
+            vec![
+                ("before", FnId::ModuleGetNumModules),
+                ("after", FnId::ModuleGetNumModules),
+                ("before", FnId::SysGetFunction),
+                ("after", FnId::SysGetFunction),
+                ("before", FnId::SysLock),
+                ("after", FnId::SysLock),
+                ("before", FnId::SysUnlock),
+                ("after", FnId::SysUnlock),
+            ]
+        );
+    }
+
+    #[test]
+    fn denied_call() {
+        let replay = ReplayInterface::new(Recording::new());
+        let hooks = CallLog {
+            denied: Some(FnId::SysLock),
+            ..Default::default()
+        };
+        let intercepted = unsafe { InterceptedInterface::new(replay.interface(), hooks) };
+
+        unsafe {
+            assert_eq!(intercepted.has_function(FnId::SysLock), Bool::False);
+            assert!(matches!(
+                intercepted.get_function(FnId::SysLock),
+                Optional::None
+            ));
+            assert_eq!(intercepted.has_function(FnId::SysUnlock), Bool::True);
+        }
+
+        // Calling the function anyway is reported to the wrapped interface.
+        let saved_panic_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let payload = std::panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+            SysBinding::lock(&*intercepted)
+        }))
+        .unwrap_err();
+        std::panic::set_hook(saved_panic_hook);
+
+        let message = payload.downcast::<String>().unwrap();
+        assert_eq!(*message, "The function `SysLock` is not permitted");
+        assert!(!intercepted
+            .hooks()
+            .calls()
+            .contains(&("before", FnId::SysLock)));
+    }
+}
@@ -7,10 +7,19 @@
 //!
 //! Most of the interface is not thread-safe and must be manually synchronised with
 //! [sys::api::SysBinding::lock] or [sys::api::SysBinding::try_lock].
+//!
+//! # Features
+//!
+//! The `std` feature is enabled by default. Without it, the crate only depends on
+//! `core` and `alloc`, and the `record` module is unavailable.
+#![cfg_attr(not(feature = "std"), no_std)]
 #![feature(c_unwind)]
+extern crate alloc;
+
 mod boolean;
 mod cbase;
 mod fn_id;
+mod guard;
 mod type_wrapper;
 
 #[cfg(feature = "init")]
@@ -21,8 +30,11 @@ pub mod extensions;
 
 pub mod collections;
 pub mod errors;
+pub mod intercept;
 pub mod library;
 pub mod module;
+#[cfg(feature = "std")]
+pub mod record;
 pub mod sys;
 pub mod version;
 pub use boolean::Bool;
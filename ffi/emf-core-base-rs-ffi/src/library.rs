@@ -55,7 +55,7 @@
 //! }
 //! ```
 use crate::collections::{ConstSpan, StaticVec};
-use std::fmt::{Display, Formatter};
+use core::fmt::{Display, Formatter};
 
 pub mod api;
 pub mod library_loader;
@@ -80,7 +80,7 @@ pub enum PredefinedHandles {
 }
 
 impl Display for PredefinedHandles {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             PredefinedHandles::Native => write!(f, "Native"),
         }
@@ -115,7 +115,7 @@ pub struct LibraryHandle {
 }
 
 impl Display for LibraryHandle {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.id)
     }
 }
@@ -128,7 +128,7 @@ pub struct LoaderHandle {
 }
 
 impl Display for LoaderHandle {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.id)
     }
 }
@@ -141,7 +141,7 @@ pub struct InternalHandle {
 }
 
 impl Display for InternalHandle {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.id)
     }
 }
@@ -157,7 +157,7 @@ impl<T> Display for Symbol<T>
 where
     T: Display,
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", &self.symbol)
     }
 }
@@ -1,15 +1,15 @@
 //! Library api.
 //!
 //! The library api is exposed be the [LibraryBinding] trait.
-use crate::collections::{MutSpan, NonNullConst, Result};
+use crate::collections::{MutSpan, NonNullConst, Result, SpanMut};
 use crate::errors::Error;
 use crate::library::library_loader::LibraryLoaderInterface;
 use crate::library::{
     InternalHandle, LibraryHandle, LibraryType, LoaderHandle, OSPathString, Symbol, SymbolName,
 };
-use crate::{Bool, CBase, CBaseFn, CBaseInterface, TypeWrapper};
-use std::ffi::c_void;
-use std::ptr::NonNull;
+use crate::{Bool, CBase, CBaseFn, TypeWrapper};
+use core::ffi::c_void;
+use core::ptr::NonNull;
 
 pub type RegisterLoaderFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
@@ -263,7 +263,7 @@ pub trait LibraryBinding {
     /// # Safety
     ///
     /// The function is not thread-safe and crosses the ffi boundary.
-    unsafe fn get_library_types(&self, buffer: MutSpan<LibraryType>) -> Result<usize, Error>;
+    unsafe fn get_library_types(&self, buffer: SpanMut<'_, LibraryType>) -> Result<usize, Error>;
 
     /// Creates a new unlinked library handle.
     ///
@@ -428,125 +428,214 @@ pub trait LibraryBinding {
     ) -> Result<Symbol<CBaseFn>, Error>;
 }
 
-impl LibraryBinding for CBaseInterface {
-    #[inline]
-    unsafe fn register_loader(
-        &mut self,
-        loader: LibraryLoaderInterface,
-        lib_type: NonNullConst<LibraryType>,
-    ) -> Result<LoaderHandle, Error> {
-        (self.vtable.as_ref().library_register_loader_fn)(self.base_module, loader, lib_type)
-    }
-
-    #[inline]
-    unsafe fn unregister_loader(&mut self, handle: LoaderHandle) -> Result<i8, Error> {
-        (self.vtable.as_ref().library_unregister_loader_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn get_loader_interface(
-        &self,
-        handle: LoaderHandle,
-    ) -> Result<LibraryLoaderInterface, Error> {
-        (self.vtable.as_ref().library_get_loader_interface_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn get_loader_handle_from_type(
-        &self,
-        lib_type: NonNullConst<LibraryType>,
-    ) -> Result<LoaderHandle, Error> {
-        (self.vtable.as_ref().library_get_loader_handle_from_type_fn)(self.base_module, lib_type)
-    }
-
-    #[inline]
-    unsafe fn get_loader_handle_from_library(
-        &self,
-        handle: LibraryHandle,
-    ) -> Result<LoaderHandle, Error> {
-        (self
-            .vtable
-            .as_ref()
-            .library_get_loader_handle_from_library_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn get_num_loaders(&self) -> usize {
-        (self.vtable.as_ref().library_get_num_loaders_fn)(self.base_module)
-    }
-
-    #[inline]
-    unsafe fn library_exists(&self, handle: LibraryHandle) -> Bool {
-        (self.vtable.as_ref().library_library_exists_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn type_exists(&self, lib_type: NonNullConst<LibraryType>) -> Bool {
-        (self.vtable.as_ref().library_type_exists_fn)(self.base_module, lib_type)
-    }
-
-    #[inline]
-    unsafe fn get_library_types(&self, buffer: MutSpan<LibraryType>) -> Result<usize, Error> {
-        (self.vtable.as_ref().library_get_library_types_fn)(self.base_module, buffer)
-    }
-
-    #[inline]
-    unsafe fn create_library_handle(&mut self) -> LibraryHandle {
-        (self.vtable.as_ref().library_create_library_handle_fn)(self.base_module)
-    }
-
-    #[inline]
-    unsafe fn remove_library_handle(&mut self, handle: LibraryHandle) -> Result<i8, Error> {
-        (self.vtable.as_ref().library_remove_library_handle_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn link_library(
-        &mut self,
-        handle: LibraryHandle,
-        loader: LoaderHandle,
-        internal: InternalHandle,
-    ) -> Result<i8, Error> {
-        (self.vtable.as_ref().library_link_library_fn)(self.base_module, handle, loader, internal)
-    }
-
-    #[inline]
-    unsafe fn get_internal_library_handle(
-        &self,
-        handle: LibraryHandle,
-    ) -> Result<InternalHandle, Error> {
-        (self.vtable.as_ref().library_get_internal_library_handle_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn load(
-        &mut self,
-        loader: LoaderHandle,
-        path: OSPathString,
-    ) -> Result<LibraryHandle, Error> {
-        (self.vtable.as_ref().library_load_fn)(self.base_module, loader, path)
-    }
-
-    #[inline]
-    unsafe fn unload(&mut self, handle: LibraryHandle) -> Result<i8, Error> {
-        (self.vtable.as_ref().library_unload_fn)(self.base_module, handle)
-    }
+/// Implements [LibraryBinding] by calling the vtable of a [CBaseInterface].
+///
+/// The interface is accessed through the fields following `self`, which allows
+/// implementing the binding for the types wrapping an interface.
+macro_rules! impl_library_binding {
+    (impl<$($generic:ident: $bound:path),*> for $ty:ty, self$(.$field:ident)*) => {
+        const _: () = {
+            use $crate::collections::{NonNullConst, Result, SpanMut};
+            use $crate::errors::Error;
+            use $crate::library::api::LibraryBinding;
+            use $crate::library::library_loader::LibraryLoaderInterface;
+            use $crate::library::{
+                InternalHandle, LibraryHandle, LibraryType, LoaderHandle, OSPathString, Symbol,
+                SymbolName,
+            };
+            use $crate::{Bool, CBaseFn, CBaseInterface};
+            use core::ffi::c_void;
+
+            #[inline]
+            fn base_interface<$($generic: $bound),*>(this: &$ty) -> &CBaseInterface {
+                &this$(.$field)*
+            }
+
+            impl<$($generic: $bound),*> LibraryBinding for $ty {
+                #[inline]
+                unsafe fn register_loader(
+                    &mut self,
+                    loader: LibraryLoaderInterface,
+                    lib_type: NonNullConst<LibraryType>,
+                ) -> Result<LoaderHandle, Error> {
+                    (base_interface(self).vtable.as_ref().library_register_loader_fn)(
+                        base_interface(self).base_module,
+                        loader,
+                        lib_type,
+                    )
+                }
+
+                #[inline]
+                unsafe fn unregister_loader(&mut self, handle: LoaderHandle) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().library_unregister_loader_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_loader_interface(
+                    &self,
+                    handle: LoaderHandle,
+                ) -> Result<LibraryLoaderInterface, Error> {
+                    (base_interface(self).vtable.as_ref().library_get_loader_interface_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_loader_handle_from_type(
+                    &self,
+                    lib_type: NonNullConst<LibraryType>,
+                ) -> Result<LoaderHandle, Error> {
+                    (base_interface(self).vtable.as_ref().library_get_loader_handle_from_type_fn)(
+                        base_interface(self).base_module,
+                        lib_type,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_loader_handle_from_library(
+                    &self,
+                    handle: LibraryHandle,
+                ) -> Result<LoaderHandle, Error> {
+                    (base_interface(self).vtable.as_ref().library_get_loader_handle_from_library_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_num_loaders(&self) -> usize {
+                    (base_interface(self).vtable.as_ref().library_get_num_loaders_fn)(
+                        base_interface(self).base_module,
+                    )
+                }
+
+                #[inline]
+                unsafe fn library_exists(&self, handle: LibraryHandle) -> Bool {
+                    (base_interface(self).vtable.as_ref().library_library_exists_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn type_exists(&self, lib_type: NonNullConst<LibraryType>) -> Bool {
+                    (base_interface(self).vtable.as_ref().library_type_exists_fn)(
+                        base_interface(self).base_module,
+                        lib_type,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_library_types(
+                    &self,
+                    buffer: SpanMut<'_,
+                    LibraryType>,
+                ) -> Result<usize, Error> {
+                    (base_interface(self).vtable.as_ref().library_get_library_types_fn)(
+                        base_interface(self).base_module,
+                        buffer.into(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn create_library_handle(&mut self) -> LibraryHandle {
+                    (base_interface(self).vtable.as_ref().library_create_library_handle_fn)(
+                        base_interface(self).base_module,
+                    )
+                }
+
+                #[inline]
+                unsafe fn remove_library_handle(
+                    &mut self,
+                    handle: LibraryHandle,
+                ) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().library_remove_library_handle_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn link_library(
+                    &mut self,
+                    handle: LibraryHandle,
+                    loader: LoaderHandle,
+                    internal: InternalHandle,
+                ) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().library_link_library_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                        loader,
+                        internal,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_internal_library_handle(
+                    &self,
+                    handle: LibraryHandle,
+                ) -> Result<InternalHandle, Error> {
+                    (base_interface(self).vtable.as_ref().library_get_internal_library_handle_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn load(
+                    &mut self,
+                    loader: LoaderHandle,
+                    path: OSPathString,
+                ) -> Result<LibraryHandle, Error> {
+                    (base_interface(self).vtable.as_ref().library_load_fn)(
+                        base_interface(self).base_module,
+                        loader,
+                        path,
+                    )
+                }
+
+                #[inline]
+                unsafe fn unload(&mut self, handle: LibraryHandle) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().library_unload_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_data_symbol(
+                    &self,
+                    handle: LibraryHandle,
+                    symbol: SymbolName,
+                ) -> Result<Symbol<NonNullConst<c_void>>, Error> {
+                    (base_interface(self).vtable.as_ref().library_get_data_symbol_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                        symbol,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_function_symbol(
+                    &self,
+                    handle: LibraryHandle,
+                    symbol: SymbolName,
+                ) -> Result<Symbol<CBaseFn>, Error> {
+                    (base_interface(self).vtable.as_ref().library_get_function_symbol_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                        symbol,
+                    )
+                }
+            }
+        };
+    };
+}
 
-    #[inline]
-    unsafe fn get_data_symbol(
-        &self,
-        handle: LibraryHandle,
-        symbol: SymbolName,
-    ) -> Result<Symbol<NonNullConst<c_void>>, Error> {
-        (self.vtable.as_ref().library_get_data_symbol_fn)(self.base_module, handle, symbol)
-    }
+pub(crate) use impl_library_binding;
 
-    #[inline]
-    unsafe fn get_function_symbol(
-        &self,
-        handle: LibraryHandle,
-        symbol: SymbolName,
-    ) -> Result<Symbol<CBaseFn>, Error> {
-        (self.vtable.as_ref().library_get_function_symbol_fn)(self.base_module, handle, symbol)
-    }
-}
+impl_library_binding!(impl<> for CBaseInterface, self);
@@ -5,10 +5,13 @@ use crate::collections::{NonNullConst, Result};
 use crate::errors::Error;
 use crate::library::{InternalHandle, OSPathString, Symbol, SymbolName};
 use crate::{CBaseFn, TypeWrapper};
-use std::ffi::c_void;
+use core::ffi::c_void;
+use core::ptr::NonNull;
+
+/// Equivalent of `std::os::windows::raw::HANDLE`, which is not available in `core`.
 #[cfg(windows)]
-use std::os::windows::raw::HANDLE;
-use std::ptr::NonNull;
+#[allow(clippy::upper_case_acronyms)]
+type HANDLE = *mut c_void;
 
 /// Opaque structure representing a loader.
 #[repr(C)]
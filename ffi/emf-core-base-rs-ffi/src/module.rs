@@ -9,9 +9,10 @@
 //! use emf_core_base_rs_ffi::version::api::VersionBinding;
 //! use emf_core_base_rs_ffi::module::api::ModuleBinding;
 //! use emf_core_base_rs_ffi::library::OSPathString;
-//! use emf_core_base_rs_ffi::collections::{NonNullConst, ConstSpan, Optional};
+//! use emf_core_base_rs_ffi::collections::{NonNullConst, SpanRef, Optional};
 //! use emf_core_base_rs_ffi::module::{MODULE_LOADER_DEFAULT_HANDLE,
 //!     InterfaceDescriptor, InterfaceName};
+//! use std::convert::TryFrom;
 //!
 //! unsafe {
 //!     // `base_interface` has the type `&mut dyn CBaseBinding`.
@@ -49,9 +50,9 @@
 //!     }
 //!
 //!     let interface_desc = InterfaceDescriptor {
-//!         name: InterfaceName::from("jobs_interface"),
+//!         name: InterfaceName::try_from("jobs_interface").unwrap(),
 //!         version: VersionBinding::new_short(base_interface, 1, 0, 0),
-//!         extensions: ConstSpan::new()
+//!         extensions: SpanRef::new()
 //!     };
 //!
 //!     if let Err(e) = ModuleBinding::export_interface(
@@ -68,11 +69,14 @@
 //!     SysBinding::unlock(base_interface);
 //! }
 //! ```
-use crate::collections::{ConstSpan, StaticVec};
+use crate::collections::{SpanRef, StaticVec};
 use crate::version::Version;
-use std::ffi::c_void;
-use std::fmt::{Display, Formatter};
-use std::ptr::NonNull;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ffi::c_void;
+use core::fmt::{Display, Formatter};
+use core::ptr::NonNull;
 
 pub mod api;
 pub mod module_loader;
@@ -113,7 +117,7 @@ pub enum PredefinedHandles {
 }
 
 impl Display for PredefinedHandles {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             PredefinedHandles::Native => write!(f, "Native"),
         }
@@ -131,7 +135,7 @@ pub enum ModuleStatus {
 }
 
 impl Display for ModuleStatus {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             ModuleStatus::Unloaded => write!(f, "Unloaded"),
             ModuleStatus::Terminated => write!(f, "Terminated"),
@@ -148,7 +152,7 @@ pub struct ModuleHandle {
 }
 
 impl Display for ModuleHandle {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.id)
     }
 }
@@ -161,7 +165,7 @@ pub struct LoaderHandle {
 }
 
 impl Display for LoaderHandle {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.id)
     }
 }
@@ -174,7 +178,7 @@ pub struct InternalHandle {
 }
 
 impl Display for InternalHandle {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.id)
     }
 }
@@ -210,25 +214,28 @@ pub struct ModuleInfo {
 }
 
 impl Display for ModuleInfo {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}, {}", &self.name, &self.version)
     }
 }
 
 /// Information regarding an interface.
+///
+/// The extensions are borrowed for the lifetime `'a`. The raw function types use
+/// `InterfaceDescriptor<'static>`, as the interface does not track how long the extensions
+/// of the descriptors it returns remain valid.
 #[repr(C)]
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
-pub struct InterfaceDescriptor {
+pub struct InterfaceDescriptor<'a> {
     pub name: InterfaceName,
     pub version: Version,
-    pub extensions: ConstSpan<InterfaceExtension>,
+    pub extensions: SpanRef<'a, InterfaceExtension>,
 }
 
-impl Display for InterfaceDescriptor {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+impl Display for InterfaceDescriptor<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         let extensions: Vec<String> = self
             .extensions
-            .as_ref()
             .iter()
             .map(|ext| format!("{}", ext))
             .collect();
@@ -1,7 +1,7 @@
 //! Module api.
 //!
 //! The library api is exposed be the [ModuleBinding] trait.
-use crate::collections::{ConstSpan, MutSpan, NonNullConst, Result};
+use crate::collections::{ConstSpan, MutSpan, NonNullConst, Result, SpanMut};
 use crate::errors::Error;
 use crate::library::OSPathString;
 use crate::module::module_loader::ModuleLoaderInterface;
@@ -9,8 +9,8 @@ use crate::module::{
     Interface, InterfaceDescriptor, InternalHandle, LoaderHandle, ModuleHandle, ModuleInfo,
     ModuleStatus, ModuleType,
 };
-use crate::{Bool, CBase, CBaseInterface, TypeWrapper};
-use std::ptr::NonNull;
+use crate::{Bool, CBase, TypeWrapper};
+use core::ptr::NonNull;
 
 pub type RegisterLoaderFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
@@ -71,7 +71,7 @@ pub type TypeExistsFn = TypeWrapper<
 pub type ExportedInterfaceExistsFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         base_module: Option<NonNull<CBase>>,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'static>>,
     ) -> Bool,
 >;
 
@@ -92,14 +92,14 @@ pub type GetModuleTypesFn = TypeWrapper<
 pub type GetExportedInterfacesFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         base_module: Option<NonNull<CBase>>,
-        buffer: MutSpan<InterfaceDescriptor>,
+        buffer: MutSpan<InterfaceDescriptor<'static>>,
     ) -> Result<usize, Error>,
 >;
 
 pub type GetExportedInterfaceHandleFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         base_module: Option<NonNull<CBase>>,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'static>>,
     ) -> Result<ModuleHandle, Error>,
 >;
 
@@ -176,7 +176,7 @@ pub type AddDependencyFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         base_module: Option<NonNull<CBase>>,
         handle: ModuleHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'static>>,
     ) -> Result<i8, Error>,
 >;
 
@@ -184,7 +184,7 @@ pub type RemoveDependencyFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         base_module: Option<NonNull<CBase>>,
         handle: ModuleHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'static>>,
     ) -> Result<i8, Error>,
 >;
 
@@ -192,7 +192,7 @@ pub type ExportInterfaceFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         base_module: Option<NonNull<CBase>>,
         handle: ModuleHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'static>>,
     ) -> Result<i8, Error>,
 >;
 
@@ -200,21 +200,21 @@ pub type GetLoadDependenciesFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         base_module: Option<NonNull<CBase>>,
         handle: ModuleHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>,
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>,
 >;
 
 pub type GetRuntimeDependenciesFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         base_module: Option<NonNull<CBase>>,
         handle: ModuleHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>,
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>,
 >;
 
 pub type GetExportableInterfacesFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         base_module: Option<NonNull<CBase>>,
         handle: ModuleHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>,
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>,
 >;
 
 pub type FetchStatusFn = TypeWrapper<
@@ -242,7 +242,7 @@ pub type GetInterfaceFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         base_module: Option<NonNull<CBase>>,
         handle: ModuleHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'static>>,
     ) -> Result<Interface, Error>,
 >;
 
@@ -406,7 +406,7 @@ pub trait ModuleBinding {
     /// The function is not thread-safe and crosses the ffi boundary.
     unsafe fn exported_interface_exists(
         &self,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'_>>,
     ) -> Bool;
 
     /// Copies the available module info into a buffer.
@@ -422,7 +422,7 @@ pub trait ModuleBinding {
     /// # Safety
     ///
     /// The function is not thread-safe and crosses the ffi boundary.
-    unsafe fn get_modules(&self, buffer: MutSpan<ModuleInfo>) -> Result<usize, Error>;
+    unsafe fn get_modules(&self, buffer: SpanMut<'_, ModuleInfo>) -> Result<usize, Error>;
 
     /// Copies the available module types into a buffer.
     ///
@@ -437,7 +437,7 @@ pub trait ModuleBinding {
     /// # Safety
     ///
     /// The function is not thread-safe and crosses the ffi boundary.
-    unsafe fn get_module_types(&self, buffer: MutSpan<ModuleType>) -> Result<usize, Error>;
+    unsafe fn get_module_types(&self, buffer: SpanMut<'_, ModuleType>) -> Result<usize, Error>;
 
     /// Copies the descriptors of the exported interfaces into a buffer.
     ///
@@ -454,7 +454,7 @@ pub trait ModuleBinding {
     /// The function is not thread-safe and crosses the ffi boundary.
     unsafe fn get_exported_interfaces(
         &self,
-        buffer: MutSpan<InterfaceDescriptor>,
+        buffer: SpanMut<'_, InterfaceDescriptor<'static>>,
     ) -> Result<usize, Error>;
 
     /// Fetches the module handle of the exported interface.
@@ -472,7 +472,7 @@ pub trait ModuleBinding {
     /// The function is not thread-safe and crosses the ffi boundary.
     unsafe fn get_exported_interface_handle(
         &self,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'_>>,
     ) -> Result<ModuleHandle, Error>;
 
     /// Creates a new unlinked module handle.
@@ -668,7 +668,7 @@ pub trait ModuleBinding {
     unsafe fn add_dependency(
         &mut self,
         handle: ModuleHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'_>>,
     ) -> Result<i8, Error>;
 
     /// Removes an existing runtime dependency from the module.
@@ -687,7 +687,7 @@ pub trait ModuleBinding {
     unsafe fn remove_dependency(
         &mut self,
         handle: ModuleHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'_>>,
     ) -> Result<i8, Error>;
 
     /// Exports an interface of a module.
@@ -707,7 +707,7 @@ pub trait ModuleBinding {
     unsafe fn export_interface(
         &mut self,
         handle: ModuleHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'_>>,
     ) -> Result<i8, Error>;
 
     /// Fetches the load dependencies of a module.
@@ -726,7 +726,7 @@ pub trait ModuleBinding {
     unsafe fn get_load_dependencies(
         &self,
         handle: ModuleHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>;
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>;
 
     /// Fetches the runtime dependencies of a module.
     ///
@@ -744,7 +744,7 @@ pub trait ModuleBinding {
     unsafe fn get_runtime_dependencies(
         &self,
         handle: ModuleHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>;
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>;
 
     /// Fetches the exportable interfaces of a module.
     ///
@@ -762,7 +762,7 @@ pub trait ModuleBinding {
     unsafe fn get_exportable_interfaces(
         &self,
         handle: ModuleHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>;
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>;
 
     /// Fetches the load status of a module.
     ///
@@ -829,245 +829,398 @@ pub trait ModuleBinding {
     unsafe fn get_interface(
         &self,
         handle: ModuleHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'_>>,
     ) -> Result<Interface, Error>;
 }
 
-impl ModuleBinding for CBaseInterface {
-    #[inline]
-    unsafe fn register_loader(
-        &mut self,
-        loader: ModuleLoaderInterface,
-        mod_type: NonNullConst<ModuleType>,
-    ) -> Result<LoaderHandle, Error> {
-        (self.vtable.as_ref().module_register_loader_fn)(self.base_module, loader, mod_type)
-    }
-
-    #[inline]
-    unsafe fn unregister_loader(&mut self, loader: LoaderHandle) -> Result<i8, Error> {
-        (self.vtable.as_ref().module_unregister_loader_fn)(self.base_module, loader)
-    }
-
-    #[inline]
-    unsafe fn get_loader_interface(
-        &self,
-        loader: LoaderHandle,
-    ) -> Result<ModuleLoaderInterface, Error> {
-        (self.vtable.as_ref().module_get_loader_interface_fn)(self.base_module, loader)
-    }
-
-    #[inline]
-    unsafe fn get_loader_handle_from_type(
-        &self,
-        mod_type: NonNullConst<ModuleType>,
-    ) -> Result<LoaderHandle, Error> {
-        (self.vtable.as_ref().module_get_loader_handle_from_type_fn)(self.base_module, mod_type)
-    }
-
-    #[inline]
-    unsafe fn get_loader_handle_from_module(
-        &self,
-        handle: ModuleHandle,
-    ) -> Result<LoaderHandle, Error> {
-        (self.vtable.as_ref().module_get_loader_handle_from_module_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn get_num_modules(&self) -> usize {
-        (self.vtable.as_ref().module_get_num_modules_fn)(self.base_module)
-    }
-
-    #[inline]
-    unsafe fn get_num_loaders(&self) -> usize {
-        (self.vtable.as_ref().module_get_num_loaders_fn)(self.base_module)
-    }
-
-    #[inline]
-    unsafe fn get_num_exported_interfaces(&self) -> usize {
-        (self.vtable.as_ref().module_get_num_exported_interfaces_fn)(self.base_module)
-    }
-
-    #[inline]
-    unsafe fn module_exists(&self, handle: ModuleHandle) -> Bool {
-        (self.vtable.as_ref().module_module_exists_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn type_exists(&self, mod_type: NonNullConst<ModuleType>) -> Bool {
-        (self.vtable.as_ref().module_type_exists_fn)(self.base_module, mod_type)
-    }
-
-    #[inline]
-    unsafe fn exported_interface_exists(
-        &self,
-        interface: NonNullConst<InterfaceDescriptor>,
-    ) -> Bool {
-        (self.vtable.as_ref().module_exported_interface_exists_fn)(self.base_module, interface)
-    }
-
-    #[inline]
-    unsafe fn get_modules(&self, buffer: MutSpan<ModuleInfo>) -> Result<usize, Error> {
-        (self.vtable.as_ref().module_get_modules_fn)(self.base_module, buffer)
-    }
-
-    #[inline]
-    unsafe fn get_module_types(&self, buffer: MutSpan<ModuleType>) -> Result<usize, Error> {
-        (self.vtable.as_ref().module_get_module_types_fn)(self.base_module, buffer)
-    }
-
-    #[inline]
-    unsafe fn get_exported_interfaces(
-        &self,
-        buffer: MutSpan<InterfaceDescriptor>,
-    ) -> Result<usize, Error> {
-        (self.vtable.as_ref().module_get_exported_interfaces_fn)(self.base_module, buffer)
-    }
-
-    #[inline]
-    unsafe fn get_exported_interface_handle(
-        &self,
-        interface: NonNullConst<InterfaceDescriptor>,
-    ) -> Result<ModuleHandle, Error> {
-        (self.vtable.as_ref().module_get_exported_interface_handle_fn)(self.base_module, interface)
-    }
-
-    #[inline]
-    unsafe fn create_module_handle(&mut self) -> ModuleHandle {
-        (self.vtable.as_ref().module_create_module_handle_fn)(self.base_module)
-    }
-
-    #[inline]
-    unsafe fn remove_module_handle(&mut self, handle: ModuleHandle) -> Result<i8, Error> {
-        (self.vtable.as_ref().module_remove_module_handle_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn link_module(
-        &mut self,
-        handle: ModuleHandle,
-        loader: LoaderHandle,
-        internal: InternalHandle,
-    ) -> Result<i8, Error> {
-        (self.vtable.as_ref().module_link_module_fn)(self.base_module, handle, loader, internal)
-    }
-
-    #[inline]
-    unsafe fn get_internal_module_handle(
-        &self,
-        handle: ModuleHandle,
-    ) -> Result<InternalHandle, Error> {
-        (self.vtable.as_ref().module_get_internal_module_handle_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn add_module(
-        &mut self,
-        loader: LoaderHandle,
-        path: OSPathString,
-    ) -> Result<ModuleHandle, Error> {
-        (self.vtable.as_ref().module_add_module_fn)(self.base_module, loader, path)
-    }
-
-    #[inline]
-    unsafe fn remove_module(&mut self, handle: ModuleHandle) -> Result<i8, Error> {
-        (self.vtable.as_ref().module_remove_module_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn load(&mut self, handle: ModuleHandle) -> Result<i8, Error> {
-        (self.vtable.as_ref().module_load_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn unload(&mut self, handle: ModuleHandle) -> Result<i8, Error> {
-        (self.vtable.as_ref().module_unload_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn initialize(&mut self, handle: ModuleHandle) -> Result<i8, Error> {
-        (self.vtable.as_ref().module_initialize_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn terminate(&mut self, handle: ModuleHandle) -> Result<i8, Error> {
-        (self.vtable.as_ref().module_terminate_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn add_dependency(
-        &mut self,
-        handle: ModuleHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
-    ) -> Result<i8, Error> {
-        (self.vtable.as_ref().module_add_dependency_fn)(self.base_module, handle, interface)
-    }
-
-    #[inline]
-    unsafe fn remove_dependency(
-        &mut self,
-        handle: ModuleHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
-    ) -> Result<i8, Error> {
-        (self.vtable.as_ref().module_remove_dependency_fn)(self.base_module, handle, interface)
-    }
-
-    #[inline]
-    unsafe fn export_interface(
-        &mut self,
-        handle: ModuleHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
-    ) -> Result<i8, Error> {
-        (self.vtable.as_ref().module_export_interface_fn)(self.base_module, handle, interface)
-    }
-
-    #[inline]
-    unsafe fn get_load_dependencies(
-        &self,
-        handle: ModuleHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error> {
-        (self.vtable.as_ref().module_get_load_dependencies_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn get_runtime_dependencies(
-        &self,
-        handle: ModuleHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error> {
-        (self.vtable.as_ref().module_get_runtime_dependencies_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn get_exportable_interfaces(
-        &self,
-        handle: ModuleHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error> {
-        (self.vtable.as_ref().module_get_exportable_interfaces_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn fetch_status(&self, handle: ModuleHandle) -> Result<ModuleStatus, Error> {
-        (self.vtable.as_ref().module_fetch_status_fn)(self.base_module, handle)
-    }
-
-    #[inline]
-    unsafe fn get_module_path(&self, handle: ModuleHandle) -> Result<OSPathString, Error> {
-        (self.vtable.as_ref().module_get_module_path_fn)(self.base_module, handle)
-    }
+/// Implements [ModuleBinding] by calling the vtable of a [CBaseInterface].
+///
+/// The interface is accessed through the fields following `self`, which allows
+/// implementing the binding for the types wrapping an interface.
+macro_rules! impl_module_binding {
+    (impl<$($generic:ident: $bound:path),*> for $ty:ty, self$(.$field:ident)*) => {
+        const _: () = {
+            use $crate::collections::{ConstSpan, NonNullConst, Result, SpanMut};
+            use $crate::errors::Error;
+            use $crate::library::OSPathString;
+            use $crate::module::api::ModuleBinding;
+            use $crate::module::module_loader::ModuleLoaderInterface;
+            use $crate::module::{
+                Interface, InterfaceDescriptor, InternalHandle, LoaderHandle, ModuleHandle,
+                ModuleInfo, ModuleStatus, ModuleType,
+            };
+            use $crate::{Bool, CBaseInterface};
+
+            #[inline]
+            fn base_interface<$($generic: $bound),*>(this: &$ty) -> &CBaseInterface {
+                &this$(.$field)*
+            }
+
+            impl<$($generic: $bound),*> ModuleBinding for $ty {
+                #[inline]
+                unsafe fn register_loader(
+                    &mut self,
+                    loader: ModuleLoaderInterface,
+                    mod_type: NonNullConst<ModuleType>,
+                ) -> Result<LoaderHandle, Error> {
+                    (base_interface(self).vtable.as_ref().module_register_loader_fn)(
+                        base_interface(self).base_module,
+                        loader,
+                        mod_type,
+                    )
+                }
+
+                #[inline]
+                unsafe fn unregister_loader(&mut self, loader: LoaderHandle) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().module_unregister_loader_fn)(
+                        base_interface(self).base_module,
+                        loader,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_loader_interface(
+                    &self,
+                    loader: LoaderHandle,
+                ) -> Result<ModuleLoaderInterface, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_loader_interface_fn)(
+                        base_interface(self).base_module,
+                        loader,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_loader_handle_from_type(
+                    &self,
+                    mod_type: NonNullConst<ModuleType>,
+                ) -> Result<LoaderHandle, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_loader_handle_from_type_fn)(
+                        base_interface(self).base_module,
+                        mod_type,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_loader_handle_from_module(
+                    &self,
+                    handle: ModuleHandle,
+                ) -> Result<LoaderHandle, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_loader_handle_from_module_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_num_modules(&self) -> usize {
+                    (base_interface(self).vtable.as_ref().module_get_num_modules_fn)(
+                        base_interface(self).base_module,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_num_loaders(&self) -> usize {
+                    (base_interface(self).vtable.as_ref().module_get_num_loaders_fn)(
+                        base_interface(self).base_module,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_num_exported_interfaces(&self) -> usize {
+                    (base_interface(self).vtable.as_ref().module_get_num_exported_interfaces_fn)(
+                        base_interface(self).base_module,
+                    )
+                }
+
+                #[inline]
+                unsafe fn module_exists(&self, handle: ModuleHandle) -> Bool {
+                    (base_interface(self).vtable.as_ref().module_module_exists_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn type_exists(&self, mod_type: NonNullConst<ModuleType>) -> Bool {
+                    (base_interface(self).vtable.as_ref().module_type_exists_fn)(
+                        base_interface(self).base_module,
+                        mod_type,
+                    )
+                }
+
+                #[inline]
+                unsafe fn exported_interface_exists(
+                    &self,
+                    interface: NonNullConst<InterfaceDescriptor<'_>>,
+                ) -> Bool {
+                    (base_interface(self).vtable.as_ref().module_exported_interface_exists_fn)(
+                        base_interface(self).base_module,
+                        interface.cast(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_modules(
+                    &self,
+                    buffer: SpanMut<'_,
+                    ModuleInfo>,
+                ) -> Result<usize, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_modules_fn)(
+                        base_interface(self).base_module,
+                        buffer.into(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_module_types(
+                    &self,
+                    buffer: SpanMut<'_,
+                    ModuleType>,
+                ) -> Result<usize, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_module_types_fn)(
+                        base_interface(self).base_module,
+                        buffer.into(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_exported_interfaces(
+                    &self,
+                    buffer: SpanMut<'_, InterfaceDescriptor<'static>>,
+                ) -> Result<usize, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_exported_interfaces_fn)(
+                        base_interface(self).base_module,
+                        buffer.into(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_exported_interface_handle(
+                    &self,
+                    interface: NonNullConst<InterfaceDescriptor<'_>>,
+                ) -> Result<ModuleHandle, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_exported_interface_handle_fn)(
+                        base_interface(self).base_module,
+                        interface.cast(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn create_module_handle(&mut self) -> ModuleHandle {
+                    (base_interface(self).vtable.as_ref().module_create_module_handle_fn)(
+                        base_interface(self).base_module,
+                    )
+                }
+
+                #[inline]
+                unsafe fn remove_module_handle(
+                    &mut self,
+                    handle: ModuleHandle,
+                ) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().module_remove_module_handle_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn link_module(
+                    &mut self,
+                    handle: ModuleHandle,
+                    loader: LoaderHandle,
+                    internal: InternalHandle,
+                ) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().module_link_module_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                        loader,
+                        internal,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_internal_module_handle(
+                    &self,
+                    handle: ModuleHandle,
+                ) -> Result<InternalHandle, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_internal_module_handle_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn add_module(
+                    &mut self,
+                    loader: LoaderHandle,
+                    path: OSPathString,
+                ) -> Result<ModuleHandle, Error> {
+                    (base_interface(self).vtable.as_ref().module_add_module_fn)(
+                        base_interface(self).base_module,
+                        loader,
+                        path,
+                    )
+                }
+
+                #[inline]
+                unsafe fn remove_module(&mut self, handle: ModuleHandle) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().module_remove_module_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn load(&mut self, handle: ModuleHandle) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().module_load_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn unload(&mut self, handle: ModuleHandle) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().module_unload_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn initialize(&mut self, handle: ModuleHandle) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().module_initialize_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn terminate(&mut self, handle: ModuleHandle) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().module_terminate_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn add_dependency(
+                    &mut self,
+                    handle: ModuleHandle,
+                    interface: NonNullConst<InterfaceDescriptor<'_>>,
+                ) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().module_add_dependency_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                        interface.cast(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn remove_dependency(
+                    &mut self,
+                    handle: ModuleHandle,
+                    interface: NonNullConst<InterfaceDescriptor<'_>>,
+                ) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().module_remove_dependency_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                        interface.cast(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn export_interface(
+                    &mut self,
+                    handle: ModuleHandle,
+                    interface: NonNullConst<InterfaceDescriptor<'_>>,
+                ) -> Result<i8, Error> {
+                    (base_interface(self).vtable.as_ref().module_export_interface_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                        interface.cast(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_load_dependencies(
+                    &self,
+                    handle: ModuleHandle,
+                ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_load_dependencies_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_runtime_dependencies(
+                    &self,
+                    handle: ModuleHandle,
+                ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_runtime_dependencies_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_exportable_interfaces(
+                    &self,
+                    handle: ModuleHandle,
+                ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_exportable_interfaces_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn fetch_status(&self, handle: ModuleHandle) -> Result<ModuleStatus, Error> {
+                    (base_interface(self).vtable.as_ref().module_fetch_status_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_module_path(
+                    &self,
+                    handle: ModuleHandle,
+                ) -> Result<OSPathString, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_module_path_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_module_info(
+                    &self,
+                    handle: ModuleHandle,
+                ) -> Result<NonNullConst<ModuleInfo>, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_module_info_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_interface(
+                    &self,
+                    handle: ModuleHandle,
+                    interface: NonNullConst<InterfaceDescriptor<'_>>,
+                ) -> Result<Interface, Error> {
+                    (base_interface(self).vtable.as_ref().module_get_interface_fn)(
+                        base_interface(self).base_module,
+                        handle,
+                        interface.cast(),
+                    )
+                }
+            }
+        };
+    };
+}
 
-    #[inline]
-    unsafe fn get_module_info(
-        &self,
-        handle: ModuleHandle,
-    ) -> Result<NonNullConst<ModuleInfo>, Error> {
-        (self.vtable.as_ref().module_get_module_info_fn)(self.base_module, handle)
-    }
+pub(crate) use impl_module_binding;
 
-    #[inline]
-    unsafe fn get_interface(
-        &self,
-        handle: ModuleHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
-    ) -> Result<Interface, Error> {
-        (self.vtable.as_ref().module_get_interface_fn)(self.base_module, handle, interface)
-    }
-}
+impl_module_binding!(impl<> for CBaseInterface, self);
@@ -7,8 +7,8 @@ use crate::library::OSPathString;
 use crate::module::native_module::{NativeModule, NativeModuleInterface};
 use crate::module::{Interface, InterfaceDescriptor, InternalHandle, ModuleInfo, ModuleStatus};
 use crate::TypeWrapper;
-use std::ffi::c_void;
-use std::ptr::NonNull;
+use core::ffi::c_void;
+use core::ptr::NonNull;
 
 /// Opaque structure representing a module loader.
 #[repr(C)]
@@ -69,7 +69,7 @@ pub type GetInterfaceFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         loader: Option<NonNull<ModuleLoader>>,
         handle: InternalHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'static>>,
     ) -> Result<Interface, Error>,
 >;
 
@@ -91,21 +91,21 @@ pub type GetLoadDependenciesFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         loader: Option<NonNull<ModuleLoader>>,
         handle: InternalHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>,
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>,
 >;
 
 pub type GetRuntimeDependenciesFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         loader: Option<NonNull<ModuleLoader>>,
         handle: InternalHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>,
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>,
 >;
 
 pub type GetExportableInterfacesFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         loader: Option<NonNull<ModuleLoader>>,
         handle: InternalHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>,
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>,
 >;
 
 pub type GetExtendedVTableFn = TypeWrapper<
@@ -286,7 +286,7 @@ pub trait ModuleLoaderBinding {
     unsafe fn get_interface(
         &self,
         handle: InternalHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'_>>,
     ) -> Result<Interface, Error>;
 
     /// Fetches the module info from a module.
@@ -344,7 +344,7 @@ pub trait ModuleLoaderBinding {
     unsafe fn get_load_dependencies(
         &self,
         handle: InternalHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>;
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>;
 
     /// Fetches the runtime dependencies of a module.
     ///
@@ -364,7 +364,7 @@ pub trait ModuleLoaderBinding {
     unsafe fn get_runtime_dependencies(
         &self,
         handle: InternalHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>;
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>;
 
     /// Fetches the exportable interfaces of a module.
     ///
@@ -384,7 +384,7 @@ pub trait ModuleLoaderBinding {
     unsafe fn get_exportable_interfaces(
         &self,
         handle: InternalHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>;
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>;
 
     /// Fetches a pointer to the extended loader vtable.
     ///
@@ -440,9 +440,9 @@ impl ModuleLoaderBinding for ModuleLoaderInterface {
     unsafe fn get_interface(
         &self,
         handle: InternalHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'_>>,
     ) -> Result<Interface, Error> {
-        (self.vtable.as_ref().get_interface_fn)(self.loader, handle, interface)
+        (self.vtable.as_ref().get_interface_fn)(self.loader, handle, interface.cast())
     }
 
     #[inline]
@@ -462,7 +462,7 @@ impl ModuleLoaderBinding for ModuleLoaderInterface {
     unsafe fn get_load_dependencies(
         &self,
         handle: InternalHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error> {
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error> {
         (self.vtable.as_ref().get_load_dependencies_fn)(self.loader, handle)
     }
 
@@ -470,7 +470,7 @@ impl ModuleLoaderBinding for ModuleLoaderInterface {
     unsafe fn get_runtime_dependencies(
         &self,
         handle: InternalHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error> {
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error> {
         (self.vtable.as_ref().get_runtime_dependencies_fn)(self.loader, handle)
     }
 
@@ -478,7 +478,7 @@ impl ModuleLoaderBinding for ModuleLoaderInterface {
     unsafe fn get_exportable_interfaces(
         &self,
         handle: InternalHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error> {
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error> {
         (self.vtable.as_ref().get_exportable_interfaces_fn)(self.loader, handle)
     }
 
@@ -605,12 +605,12 @@ impl ModuleLoaderBinding for NativeModuleLoaderInterface {
     unsafe fn get_interface(
         &self,
         handle: InternalHandle,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'_>>,
     ) -> Result<Interface, Error> {
         (self.vtable.as_ref().loader_vtable.as_ref().get_interface_fn)(
             self.loader,
             handle,
-            interface,
+            interface.cast(),
         )
     }
 
@@ -641,7 +641,7 @@ impl ModuleLoaderBinding for NativeModuleLoaderInterface {
     unsafe fn get_load_dependencies(
         &self,
         handle: InternalHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error> {
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error> {
         (self
             .vtable
             .as_ref()
@@ -654,7 +654,7 @@ impl ModuleLoaderBinding for NativeModuleLoaderInterface {
     unsafe fn get_runtime_dependencies(
         &self,
         handle: InternalHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error> {
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error> {
         (self
             .vtable
             .as_ref()
@@ -667,7 +667,7 @@ impl ModuleLoaderBinding for NativeModuleLoaderInterface {
     unsafe fn get_exportable_interfaces(
         &self,
         handle: InternalHandle,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error> {
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error> {
         (self
             .vtable
             .as_ref()
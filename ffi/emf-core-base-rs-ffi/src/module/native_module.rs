@@ -6,7 +6,7 @@ use crate::errors::Error;
 use crate::module::{Interface, InterfaceDescriptor, ModuleHandle, ModuleInfo};
 use crate::sys::api::{GetFunctionFn, HasFunctionFn};
 use crate::{CBase, TypeWrapper};
-use std::ptr::NonNull;
+use core::ptr::NonNull;
 
 /// Opaque structure representing a native module.
 #[repr(C)]
@@ -38,7 +38,7 @@ pub type TerminateFn = TypeWrapper<
 pub type GetInterfaceFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         module: Option<NonNull<NativeModule>>,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'static>>,
     ) -> Result<Interface, Error>,
 >;
 
@@ -49,18 +49,18 @@ pub type GetModuleInfoFn = TypeWrapper<
 >;
 
 pub type GetLoadDependenciesFn =
-    TypeWrapper<unsafe extern "C-unwind" fn() -> ConstSpan<InterfaceDescriptor>>;
+    TypeWrapper<unsafe extern "C-unwind" fn() -> ConstSpan<InterfaceDescriptor<'static>>>;
 
 pub type GetRuntimeDependenciesFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         module: Option<NonNull<NativeModule>>,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>,
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>,
 >;
 
 pub type GetExportableInterfacesFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
         module: Option<NonNull<NativeModule>>,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>,
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>,
 >;
 
 /// Interface of a native module.
@@ -175,7 +175,7 @@ pub trait NativeModuleBinding {
     unsafe fn get_interface(
         &self,
         module: Option<NonNull<NativeModule>>,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'_>>,
     ) -> Result<Interface, Error>;
 
     /// Fetches the module info of the module.
@@ -209,7 +209,7 @@ pub trait NativeModuleBinding {
     /// The function crosses the ffi boundary.
     /// Direct usage of a [NativeModuleBinding] may break some invariants
     /// of the module api, if not handled with care.
-    unsafe fn get_load_dependencies(&self) -> ConstSpan<InterfaceDescriptor>;
+    unsafe fn get_load_dependencies(&self) -> ConstSpan<InterfaceDescriptor<'static>>;
 
     /// Fetches the runtime dependencies of the module.
     ///
@@ -229,7 +229,7 @@ pub trait NativeModuleBinding {
     unsafe fn get_runtime_dependencies(
         &self,
         module: Option<NonNull<NativeModule>>,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>;
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>;
 
     /// Fetches the exportable interfaces of the module.
     ///
@@ -249,7 +249,7 @@ pub trait NativeModuleBinding {
     unsafe fn get_exportable_interfaces(
         &self,
         module: Option<NonNull<NativeModule>>,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error>;
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error>;
 }
 
 impl NativeModuleBinding for NativeModuleInterface {
@@ -283,9 +283,9 @@ impl NativeModuleBinding for NativeModuleInterface {
     unsafe fn get_interface(
         &self,
         module: Option<NonNull<NativeModule>>,
-        interface: NonNullConst<InterfaceDescriptor>,
+        interface: NonNullConst<InterfaceDescriptor<'_>>,
     ) -> Result<Interface, Error> {
-        (self.get_interface_fn)(module, interface)
+        (self.get_interface_fn)(module, interface.cast())
     }
 
     #[inline]
@@ -297,7 +297,7 @@ impl NativeModuleBinding for NativeModuleInterface {
     }
 
     #[inline]
-    unsafe fn get_load_dependencies(&self) -> ConstSpan<InterfaceDescriptor> {
+    unsafe fn get_load_dependencies(&self) -> ConstSpan<InterfaceDescriptor<'static>> {
         (self.get_load_dependencies_fn)()
     }
 
@@ -305,7 +305,7 @@ impl NativeModuleBinding for NativeModuleInterface {
     unsafe fn get_runtime_dependencies(
         &self,
         module: Option<NonNull<NativeModule>>,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error> {
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error> {
         (self.get_runtime_dependencies_fn)(module)
     }
 
@@ -313,7 +313,7 @@ impl NativeModuleBinding for NativeModuleInterface {
     unsafe fn get_exportable_interfaces(
         &self,
         module: Option<NonNull<NativeModule>>,
-    ) -> Result<ConstSpan<InterfaceDescriptor>, Error> {
+    ) -> Result<ConstSpan<InterfaceDescriptor<'static>>, Error> {
         (self.get_exportable_interfaces_fn)(module)
     }
 }
@@ -0,0 +1,702 @@
+//! Recording and replaying of interface calls.
+//!
+//! A [RecordingInterface] wraps an existing interface and records every call to the
+//! version, library and module apis, together with its arguments and result, into a
+//! [Recording]. Recordings can be stored in a compact binary format and replayed by a
+//! [ReplayInterface], which implements the interface by returning the recorded results
+//! and flags every [Divergence] in the order or the arguments of the calls.
+//!
+//! # Pointers
+//!
+//! Pointers, like loader interfaces, symbols and interfaces, are recorded by address
+//! and are not compared during a replay, as they differ between runs. Replayed pointers
+//! must not be dereferenced, except for the `emf-core-base` interface returned by
+//! [ModuleBinding::get_interface](crate::module::api::ModuleBinding::get_interface),
+//! which is substituted by the replaying interface.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use emf_core_base_rs_ffi::CBaseInterface;
+//! # let interface: CBaseInterface = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+//! use emf_core_base_rs_ffi::record::{Recording, RecordingInterface, ReplayInterface};
+//! use emf_core_base_rs_ffi::module::api::ModuleBinding;
+//!
+//! // Record the calls in the field.
+//! let recorder = unsafe { RecordingInterface::new(interface) };
+//! let num_modules = unsafe { recorder.interface().get_num_modules() };
+//! recorder.recording().save("calls.emfrec").unwrap();
+//!
+//! // Replay them later.
+//! let replay = ReplayInterface::new(Recording::load("calls.emfrec").unwrap());
+//! assert_eq!(unsafe { replay.interface().get_num_modules() }, num_modules);
+//! assert!(replay.divergences().is_empty());
+//! ```
+use crate::cbase::api_functions;
+use crate::collections::{ConstSpan, MutSpan, NonNullConst, Optional, Result};
+use crate::errors::Error;
+use crate::guard::abort_on_unwind;
+use crate::library::library_loader::LibraryLoaderInterface;
+use crate::library::{
+    InternalHandle as InternalLibraryHandle, LibraryHandle, LibraryType,
+    LoaderHandle as LibraryLoaderHandle, OSPathString, Symbol, SymbolName,
+};
+use crate::module::api as mod_api;
+use crate::module::module_loader::ModuleLoaderInterface;
+use crate::module::{
+    InterfaceDescriptor, InternalHandle as InternalModuleHandle,
+    LoaderHandle as ModuleLoaderHandle, ModuleHandle, ModuleInfo, ModuleStatus, ModuleType,
+};
+use crate::sys::api as sys_api;
+use crate::version::{ReleaseType, Version};
+use crate::{Bool, CBase, CBaseFn, CBaseInterfaceVTable, FnId, TypeWrapper};
+use std::ffi::c_void;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::Path;
+use std::ptr::NonNull;
+
+mod encode;
+mod recorder;
+mod replayer;
+
+use encode::{decode_buffered, encode_buffered, Decode, Encode};
+
+pub use recorder::RecordingInterface;
+pub use replayer::{Divergence, ReplayInterface};
+
+/// Magic number at the start of a stored recording.
+const MAGIC: &[u8; 6] = b"EMFREC";
+
+/// Version of the storage format.
+const FORMAT_VERSION: u8 = 1;
+
+/// A recorded argument or result.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Value {
+    /// Absence of a value.
+    Unit,
+    /// An integer.
+    Int(i64),
+    /// A byte string.
+    Bytes(Vec<u8>),
+    /// An address, which is ignored when matching values.
+    Ptr(u64),
+    /// The message of an error.
+    Err(String),
+    /// A sequence of values.
+    List(Vec<Value>),
+}
+
+impl Value {
+    /// Checks whether two values are equal, ignoring the addresses.
+    pub fn matches(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Ptr(_), Value::Ptr(_)) => true,
+            (Value::List(lhs), Value::List(rhs)) => {
+                lhs.len() == rhs.len() && lhs.iter().zip(rhs).all(|(lhs, rhs)| lhs.matches(rhs))
+            }
+            (lhs, rhs) => lhs == rhs,
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Unit => write!(f, "()"),
+            Value::Int(value) => write!(f, "{}", value),
+            Value::Bytes(bytes) => write!(f, "{:?}", String::from_utf8_lossy(bytes)),
+            Value::Ptr(address) => write!(f, "{:#x}", address),
+            Value::Err(message) => write!(f, "Err({:?})", message),
+            Value::List(values) => {
+                write!(f, "[")?;
+                write_list(f, values)?;
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+/// Writes a comma separated list of values.
+fn write_list(f: &mut Formatter<'_>, values: &[Value]) -> std::fmt::Result {
+    for (i, value) in values.iter().enumerate() {
+        if i != 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", value)?;
+    }
+    Ok(())
+}
+
+/// A recorded call.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Call {
+    /// Id of the called function.
+    pub id: FnId,
+    /// Arguments of the call.
+    pub args: Vec<Value>,
+    /// Result of the call.
+    pub result: Value,
+}
+
+impl Display for Call {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}(", self.id)?;
+        write_list(f, &self.args)?;
+        write!(f, ") -> {}", self.result)
+    }
+}
+
+/// A sequence of recorded calls.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Recording {
+    calls: Vec<Call>,
+}
+
+impl Recording {
+    /// Constructs an empty recording.
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Fetches the recorded calls.
+    #[inline]
+    pub fn calls(&self) -> &[Call] {
+        &self.calls
+    }
+
+    /// Appends a call to the recording.
+    #[inline]
+    pub fn push(&mut self, call: Call) {
+        self.calls.push(call)
+    }
+
+    /// Fetches the number of recorded calls.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.calls.len()
+    }
+
+    /// Checks whether the recording is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.calls.is_empty()
+    }
+
+    /// Writes the recording in the binary format.
+    ///
+    /// # Failure
+    ///
+    /// Fails if `writer` fails.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+        for call in &self.calls {
+            write_int(&mut writer, call.id as i64)?;
+            write_uint(&mut writer, call.args.len() as u64)?;
+            for arg in &call.args {
+                write_value(&mut writer, arg)?;
+            }
+            write_value(&mut writer, &call.result)?;
+        }
+        writer.flush()
+    }
+
+    /// Reads a recording in the binary format.
+    ///
+    /// # Failure
+    ///
+    /// Fails if `reader` fails or the data is malformed.
+    pub fn read_from<R: Read>(mut reader: R) -> std::io::Result<Self> {
+        let mut header = [0; 7];
+        reader.read_exact(&mut header)?;
+        if &header[..6] != MAGIC || header[6] != FORMAT_VERSION {
+            return Err(invalid_data("Unknown recording format"));
+        }
+
+        let mut calls = Vec::new();
+        while let Some(byte) = read_byte(&mut reader)? {
+            let id = unzigzag(read_uint_from(&mut reader, byte)?);
+            let id = recorded_fn_id(id).ok_or_else(|| invalid_data("Unknown function id"))?;
+            let num_args = read_uint(&mut reader)?;
+            let args = (0..num_args)
+                .map(|_| read_value(&mut reader))
+                .collect::<std::io::Result<_>>()?;
+            let result = read_value(&mut reader)?;
+            calls.push(Call { id, args, result });
+        }
+
+        Ok(Self { calls })
+    }
+
+    /// Stores the recording in a file.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the file can not be written.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        self.write_to(BufWriter::new(File::create(path)?))
+    }
+
+    /// Loads a recording from a file.
+    ///
+    /// # Failure
+    ///
+    /// Fails if the file can not be read or is malformed.
+    pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+}
+
+impl From<Vec<Call>> for Recording {
+    #[inline]
+    fn from(calls: Vec<Call>) -> Self {
+        Self { calls }
+    }
+}
+
+impl IntoIterator for Recording {
+    type Item = Call;
+    type IntoIter = std::vec::IntoIter<Call>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.calls.into_iter()
+    }
+}
+
+// Values are stored as a tag byte, followed by the payload.
+// Integers use a LEB128 encoding, with a zigzag encoding for signed integers.
+const TAG_UNIT: u8 = 0;
+const TAG_INT: u8 = 1;
+const TAG_BYTES: u8 = 2;
+const TAG_PTR: u8 = 3;
+const TAG_ERR: u8 = 4;
+const TAG_LIST: u8 = 5;
+
+fn invalid_data(message: &str) -> std::io::Error {
+    std::io::Error::new(ErrorKind::InvalidData, message)
+}
+
+fn write_uint<W: Write>(writer: &mut W, mut value: u64) -> std::io::Result<()> {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_all(&[byte]);
+        }
+        writer.write_all(&[byte | 0x80])?;
+    }
+}
+
+fn write_int<W: Write>(writer: &mut W, value: i64) -> std::io::Result<()> {
+    write_uint(writer, ((value << 1) ^ (value >> 63)) as u64)
+}
+
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> std::io::Result<()> {
+    write_uint(writer, bytes.len() as u64)?;
+    writer.write_all(bytes)
+}
+
+fn write_value<W: Write>(writer: &mut W, value: &Value) -> std::io::Result<()> {
+    match value {
+        Value::Unit => writer.write_all(&[TAG_UNIT]),
+        Value::Int(value) => {
+            writer.write_all(&[TAG_INT])?;
+            write_int(writer, *value)
+        }
+        Value::Bytes(bytes) => {
+            writer.write_all(&[TAG_BYTES])?;
+            write_bytes(writer, bytes)
+        }
+        Value::Ptr(address) => {
+            writer.write_all(&[TAG_PTR])?;
+            write_uint(writer, *address)
+        }
+        Value::Err(message) => {
+            writer.write_all(&[TAG_ERR])?;
+            write_bytes(writer, message.as_bytes())
+        }
+        Value::List(values) => {
+            writer.write_all(&[TAG_LIST])?;
+            write_uint(writer, values.len() as u64)?;
+            values
+                .iter()
+                .try_for_each(|value| write_value(writer, value))
+        }
+    }
+}
+
+/// Reads a byte, returning `None` at the end of the stream.
+fn read_byte<R: Read>(reader: &mut R) -> std::io::Result<Option<u8>> {
+    let mut byte = [0];
+    loop {
+        match reader.read(&mut byte) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(byte[0])),
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+fn read_required_byte<R: Read>(reader: &mut R) -> std::io::Result<u8> {
+    read_byte(reader)?.ok_or_else(|| ErrorKind::UnexpectedEof.into())
+}
+
+/// Reads an unsigned integer, whose first byte was already read.
+fn read_uint_from<R: Read>(reader: &mut R, mut byte: u8) -> std::io::Result<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    loop {
+        if shift > 63 {
+            return Err(invalid_data("Integer overflow"));
+        }
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+        byte = read_required_byte(reader)?;
+    }
+}
+
+fn read_uint<R: Read>(reader: &mut R) -> std::io::Result<u64> {
+    let byte = read_required_byte(reader)?;
+    read_uint_from(reader, byte)
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+fn read_bytes<R: Read>(reader: &mut R) -> std::io::Result<Vec<u8>> {
+    let len = read_uint(reader)?;
+    let mut bytes = Vec::new();
+    reader.take(len).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 != len {
+        return Err(ErrorKind::UnexpectedEof.into());
+    }
+    Ok(bytes)
+}
+
+fn read_value<R: Read>(reader: &mut R) -> std::io::Result<Value> {
+    match read_required_byte(reader)? {
+        TAG_UNIT => Ok(Value::Unit),
+        TAG_INT => Ok(Value::Int(unzigzag(read_uint(reader)?))),
+        TAG_BYTES => Ok(Value::Bytes(read_bytes(reader)?)),
+        TAG_PTR => Ok(Value::Ptr(read_uint(reader)?)),
+        TAG_ERR => String::from_utf8(read_bytes(reader)?)
+            .map(Value::Err)
+            .map_err(|_| invalid_data("Invalid error message")),
+        TAG_LIST => {
+            let len = read_uint(reader)?;
+            (0..len)
+                .map(|_| read_value(reader))
+                .collect::<std::io::Result<_>>()
+                .map(Value::List)
+        }
+        _ => Err(invalid_data("Unknown value tag")),
+    }
+}
+
+macro_rules! recorded_functions {
+    ($(
+        $field:ident => $id:ident,
+            fn($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty $(, out $buffer:ident)?;
+    )*) => {
+        /// Converts a raw id to the id of a recorded function.
+        fn recorded_fn_id(id: i64) -> Option<FnId> {
+            $(
+                if id == FnId::$id as i64 {
+                    return Some(FnId::$id);
+                }
+            )*
+            if id == FnId::ModuleGetInterface as i64 {
+                return Some(FnId::ModuleGetInterface);
+            }
+            None
+        }
+
+        // `CBaseFn` only serves as a type-erased function pointer.
+        #[allow(improper_ctypes_definitions)]
+        impl RecordingInterface {
+            $(
+                unsafe extern "C-unwind" fn $field(
+                    base_module: Option<NonNull<CBase>>,
+                    $($arg: $ty),*
+                ) -> $ret {
+                    let this = Self::from_base_module(base_module);
+                    let args = abort_on_unwind(|| vec![$(Encode::encode(&$arg)),*]);
+                    let result = (this.inner.vtable.as_ref().$field)(this.inner.base_module, $($arg),*);
+                    abort_on_unwind(|| {
+                        let value = recorded_functions!(@encode result $(, $buffer)?);
+                        this.record(FnId::$id, args, value);
+                    });
+                    result
+                }
+            )*
+
+            /// Constructs the vtable of the trampolines.
+            fn build_vtable(version: Version) -> CBaseInterfaceVTable {
+                recorded_functions!(@vtable version $($field)*)
+            }
+
+            /// Fetches the trampoline of a function.
+            unsafe fn trampoline(&self, id: FnId) -> Optional<CBaseFn> {
+                recorded_functions!(@trampoline self id $($field => $id, fn($($ty),*) -> $ret;)*)
+            }
+        }
+
+        // `CBaseFn` only serves as a type-erased function pointer.
+        #[allow(improper_ctypes_definitions)]
+        impl ReplayInterface {
+            $(
+                unsafe extern "C-unwind" fn $field(
+                    base_module: Option<NonNull<CBase>>,
+                    $($arg: $ty),*
+                ) -> $ret {
+                    let args = vec![$(Encode::encode(&$arg)),*];
+                    Self::replay(base_module, FnId::$id, args, |_result, _arena| {
+                        recorded_functions!(@decode _result _arena $(, $buffer)?)
+                    })
+                }
+            )*
+
+            /// Constructs the vtable of the fake interface.
+            fn build_vtable(version: Version) -> CBaseInterfaceVTable {
+                recorded_functions!(@vtable version $($field)*)
+            }
+
+            /// Fetches a function of the fake interface.
+            unsafe fn trampoline(&self, id: FnId) -> Optional<CBaseFn> {
+                recorded_functions!(@trampoline self id $($field => $id, fn($($ty),*) -> $ret;)*)
+            }
+        }
+    };
+    (@encode $result:ident) => {
+        Encode::encode(&$result)
+    };
+    (@encode $result:ident, $buffer:ident) => {
+        encode_buffered(&$result, $buffer)
+    };
+    (@decode $result:ident $arena:ident) => {
+        Decode::decode($result, $arena)
+    };
+    (@decode $result:ident $arena:ident, $buffer:ident) => {
+        decode_buffered($result, $arena, $buffer)
+    };
+    (@vtable $version:ident $($field:ident)*) => {
+        CBaseInterfaceVTable {
+            version: $version,
+            sys_shutdown_fn: TypeWrapper(Self::sys_shutdown_fn),
+            sys_panic_fn: TypeWrapper(Self::sys_panic_fn),
+            sys_has_function_fn: TypeWrapper(Self::sys_has_function_fn),
+            sys_get_function_fn: TypeWrapper(Self::sys_get_function_fn),
+            sys_lock_fn: TypeWrapper(Self::sys_lock_fn),
+            sys_try_lock_fn: TypeWrapper(Self::sys_try_lock_fn),
+            sys_unlock_fn: TypeWrapper(Self::sys_unlock_fn),
+            sys_get_sync_handler_fn: TypeWrapper(Self::sys_get_sync_handler_fn),
+            sys_set_sync_handler_fn: TypeWrapper(Self::sys_set_sync_handler_fn),
+            module_get_interface_fn: TypeWrapper(Self::module_get_interface_fn),
+            $($field: TypeWrapper(Self::$field)),*
+        }
+    };
+    (@trampoline $this:ident $fn_id:ident $(
+        $field:ident => $id:ident, fn($($ty:ty),*) -> $ret:ty;
+    )*) => {{
+        use std::mem::transmute;
+
+        let vtable = &$this.vtable;
+        // SAFETY: `CBaseFn` only serves as a type-erased function pointer,
+        // which is cast back to the type of the function `id` by the caller.
+        match $fn_id {
+            FnId::SysShutdown => {
+                Optional::Some(transmute::<sys_api::ShutdownFn, CBaseFn>(vtable.sys_shutdown_fn))
+            }
+            FnId::SysPanic => {
+                Optional::Some(transmute::<sys_api::PanicFn, CBaseFn>(vtable.sys_panic_fn))
+            }
+            FnId::SysHasFunction => Optional::Some(transmute::<sys_api::HasFunctionFn, CBaseFn>(
+                vtable.sys_has_function_fn,
+            )),
+            FnId::SysGetFunction => Optional::Some(transmute::<sys_api::GetFunctionFn, CBaseFn>(
+                vtable.sys_get_function_fn,
+            )),
+            FnId::SysLock => {
+                Optional::Some(transmute::<sys_api::LockFn, CBaseFn>(vtable.sys_lock_fn))
+            }
+            FnId::SysTryLock => {
+                Optional::Some(transmute::<sys_api::TryLockFn, CBaseFn>(vtable.sys_try_lock_fn))
+            }
+            FnId::SysUnlock => {
+                Optional::Some(transmute::<sys_api::UnlockFn, CBaseFn>(vtable.sys_unlock_fn))
+            }
+            FnId::SysGetSyncHandler => Optional::Some(
+                transmute::<sys_api::GetSyncHandlerFn, CBaseFn>(vtable.sys_get_sync_handler_fn),
+            ),
+            FnId::SysSetSyncHandler => Optional::Some(
+                transmute::<sys_api::SetSyncHandlerFn, CBaseFn>(vtable.sys_set_sync_handler_fn),
+            ),
+            FnId::ModuleGetInterface => Optional::Some(
+                transmute::<mod_api::GetInterfaceFn, CBaseFn>(vtable.module_get_interface_fn),
+            ),
+            $(
+                FnId::$id => Optional::Some(transmute::<
+                    TypeWrapper<
+                        unsafe extern "C-unwind" fn(Option<NonNull<CBase>>, $($ty),*) -> $ret
+                    >,
+                    CBaseFn,
+                >(vtable.$field)),
+            )*
+            #[cfg(feature = "unwind_internal")]
+            FnId::ExtGetUnwindInternalInterface => Optional::None,
+        }
+    }};
+}
+
+api_functions!(recorded_functions! {});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collections::SpanMut;
+    use std::convert::TryFrom;
+
+    fn recording() -> Recording {
+        Recording::from(vec![
+            Call {
+                id: FnId::VersionNewShort,
+                args: vec![Value::Int(1), Value::Int(-2), Value::Int(300)],
+                result: Value::List(vec![Value::Int(1), Value::Int(-2), Value::Int(300)]),
+            },
+            Call {
+                id: FnId::LibraryGetDataSymbol,
+                args: vec![Value::Int(5), Value::Bytes(b"symbol".to_vec())],
+                result: Value::Ptr(0xdead_beef),
+            },
+            Call {
+                id: FnId::ModuleLoad,
+                args: vec![Value::Int(i64::MIN)],
+                result: Value::Err("error message".to_string()),
+            },
+            Call {
+                id: FnId::ModuleGetInterface,
+                args: vec![Value::Int(i64::MAX), Value::List(vec![])],
+                result: Value::Unit,
+            },
+        ])
+    }
+
+    #[test]
+    fn round_trip() {
+        let recording = recording();
+        let mut buffer = Vec::new();
+        recording.write_to(&mut buffer).unwrap();
+        assert_eq!(Recording::read_from(buffer.as_slice()).unwrap(), recording);
+
+        // Truncated data must be rejected.
+        buffer.pop();
+        assert!(Recording::read_from(buffer.as_slice()).is_err());
+        assert!(Recording::read_from(&b"EMFREC\x02"[..]).is_err());
+    }
+
+    #[test]
+    fn replay() {
+        use crate::module::api::ModuleBinding;
+        use crate::module::ModuleName;
+        use crate::version::api::VersionBinding;
+
+        let version = Version {
+            major: 1,
+            minor: 2,
+            patch: 3,
+            build: 0,
+            release_number: 0,
+            release_type: ReleaseType::Stable,
+        };
+        let info = ModuleInfo {
+            name: ModuleName::try_from("module").unwrap(),
+            version: Default::default(),
+        };
+        let recording = Recording::from(vec![
+            Call {
+                id: FnId::VersionNewShort,
+                args: vec![Value::Int(1), Value::Int(2), Value::Int(3)],
+                result: version.encode(),
+            },
+            Call {
+                id: FnId::ModuleGetModules,
+                args: vec![Value::Int(4)],
+                result: Value::List(vec![Value::Int(1), ModuleInfo::encode_slice(&[info])]),
+            },
+            Call {
+                id: FnId::ModuleLoad,
+                args: vec![Value::Int(5)],
+                result: Value::Err("error message".to_string()),
+            },
+        ]);
+
+        let replay = ReplayInterface::new(recording.clone());
+        let recorder = unsafe { RecordingInterface::new(replay.interface()) };
+        let mut interface = recorder.interface();
+        let mut buffer = [ModuleInfo {
+            name: Default::default(),
+            version: Default::default(),
+        }; 4];
+        unsafe {
+            assert_eq!(interface.new_short(1, 2, 3), version);
+            assert_eq!(
+                interface.get_modules(SpanMut::from(&mut buffer)),
+                Result::Ok(1)
+            );
+            assert_eq!(buffer[0], info);
+
+            let error = interface
+                .load(ModuleHandle { id: 6 })
+                .into_rust()
+                .unwrap_err();
+            assert_eq!(error.display_info().as_ref(), "error message");
+        }
+
+        // The recorder reproduces the arguments of the calls.
+        let mut expected = recording.calls().to_vec();
+        expected[2].args = vec![Value::Int(6)];
+        assert_eq!(recorder.recording().calls(), expected.as_slice());
+
+        let divergences = replay.finish().unwrap_err();
+        assert_eq!(
+            divergences,
+            vec![Divergence::Arguments {
+                position: 2,
+                expected: recording.calls()[2].clone(),
+                actual: vec![Value::Int(6)],
+            }]
+        );
+
+        // Calls after the end of the recording can not be replayed.
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| unsafe {
+            interface.get_num_modules()
+        }));
+        assert!(result.is_err());
+        assert_eq!(
+            replay.divergences().last(),
+            Some(&Divergence::Exhausted {
+                position: 3,
+                actual: FnId::ModuleGetNumModules,
+            })
+        );
+    }
+
+    #[test]
+    fn matches() {
+        assert!(Value::Ptr(1).matches(&Value::Ptr(2)));
+        assert!(Value::List(vec![Value::Ptr(1), Value::Int(3)])
+            .matches(&Value::List(vec![Value::Ptr(2), Value::Int(3)])));
+        assert!(!Value::List(vec![Value::Ptr(1)]).matches(&Value::List(vec![])));
+        assert!(!Value::Int(1).matches(&Value::Ptr(1)));
+    }
+}
@@ -0,0 +1,527 @@
+//! Conversion of arguments and results to [Value]s.
+use crate::collections::{ConstSpan, MutSpan, NonNullConst, Result, SpanRef, StaticVec};
+use crate::errors::Error;
+use crate::library::library_loader::LibraryLoaderInterface;
+use crate::library::{
+    InternalHandle as InternalLibraryHandle, LibraryHandle, LoaderHandle as LibraryLoaderHandle,
+    Symbol,
+};
+use crate::module::module_loader::ModuleLoaderInterface;
+use crate::module::{
+    Interface, InterfaceDescriptor, InternalHandle as InternalModuleHandle,
+    LoaderHandle as ModuleLoaderHandle, ModuleHandle, ModuleInfo, ModuleStatus,
+};
+use crate::record::Value;
+use crate::version::{ReleaseType, Version};
+use crate::{Bool, CBaseFn};
+use std::any::Any;
+use std::convert::TryFrom;
+use std::ffi::c_void;
+use std::fmt::{Display, Formatter};
+use std::ptr::NonNull;
+
+/// Types that can be recorded.
+pub(super) trait Encode {
+    /// Converts the value.
+    ///
+    /// Pointers passed to the interface are required to be valid,
+    /// therefore pointees are encoded instead of the addresses.
+    fn encode(&self) -> Value;
+
+    /// Converts a slice of values.
+    #[inline]
+    fn encode_slice(slice: &[Self]) -> Value
+    where
+        Self: Sized,
+    {
+        Value::List(slice.iter().map(Encode::encode).collect())
+    }
+}
+
+/// Types that can be replayed.
+pub(super) trait Decode: Sized {
+    /// Converts a value, allocating the pointees in `arena`.
+    fn decode(value: &Value, arena: &mut Arena) -> Option<Self>;
+
+    /// Converts a sequence of values.
+    #[inline]
+    fn decode_vec(value: &Value, arena: &mut Arena) -> Option<Vec<Self>> {
+        match value {
+            Value::List(values) => values.iter().map(|v| Self::decode(v, arena)).collect(),
+            _ => None,
+        }
+    }
+}
+
+/// Storage of the replayed pointees.
+#[derive(Debug, Default)]
+pub(super) struct Arena {
+    allocations: Vec<Box<dyn Any>>,
+}
+
+impl Arena {
+    /// Moves a value into the arena.
+    fn alloc<T: 'static>(&mut self, value: T) -> NonNullConst<T> {
+        let value = Box::new(value);
+        let ptr = NonNullConst::from(&*value);
+        self.allocations.push(value);
+        ptr
+    }
+
+    /// Moves a sequence of values into the arena.
+    fn alloc_slice<T: Copy + 'static>(&mut self, values: Vec<T>) -> ConstSpan<T> {
+        let values = values.into_boxed_slice();
+        let span = ConstSpan::from(&*values);
+        self.allocations.push(Box::new(values));
+        span
+    }
+}
+
+/// An error replayed from its message.
+#[derive(Debug)]
+struct RecordedError(String);
+
+impl Display for RecordedError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for RecordedError {}
+
+macro_rules! int_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl Encode for $ty {
+                #[inline]
+                fn encode(&self) -> Value {
+                    Value::Int(*self as i64)
+                }
+            }
+
+            impl Decode for $ty {
+                #[inline]
+                fn decode(value: &Value, _arena: &mut Arena) -> Option<Self> {
+                    match value {
+                        Value::Int(value) => <$ty>::try_from(*value).ok(),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+int_impls!(i8, u16, i32, i64, isize, usize);
+
+impl Encode for u8 {
+    #[inline]
+    fn encode(&self) -> Value {
+        Value::Int(*self as i64)
+    }
+
+    #[inline]
+    fn encode_slice(slice: &[Self]) -> Value {
+        Value::Bytes(slice.to_vec())
+    }
+}
+
+impl Decode for u8 {
+    #[inline]
+    fn decode(value: &Value, _arena: &mut Arena) -> Option<Self> {
+        match value {
+            Value::Int(value) => u8::try_from(*value).ok(),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    fn decode_vec(value: &Value, _arena: &mut Arena) -> Option<Vec<Self>> {
+        match value {
+            Value::Bytes(bytes) => Some(bytes.clone()),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! handle_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl Encode for $ty {
+                #[inline]
+                fn encode(&self) -> Value {
+                    self.id.encode()
+                }
+            }
+
+            impl Decode for $ty {
+                #[inline]
+                fn decode(value: &Value, arena: &mut Arena) -> Option<Self> {
+                    Decode::decode(value, arena).map(|id| Self { id })
+                }
+            }
+        )*
+    };
+}
+
+handle_impls!(
+    LibraryHandle,
+    LibraryLoaderHandle,
+    InternalLibraryHandle,
+    ModuleHandle,
+    ModuleLoaderHandle,
+    InternalModuleHandle
+);
+
+impl Encode for Bool {
+    #[inline]
+    fn encode(&self) -> Value {
+        Value::Int(*self as i64)
+    }
+}
+
+impl Decode for Bool {
+    #[inline]
+    fn decode(value: &Value, _arena: &mut Arena) -> Option<Self> {
+        match value {
+            Value::Int(0) => Some(Bool::False),
+            Value::Int(1) => Some(Bool::True),
+            _ => None,
+        }
+    }
+}
+
+impl Encode for ReleaseType {
+    #[inline]
+    fn encode(&self) -> Value {
+        Value::Int(*self as i64)
+    }
+}
+
+impl Decode for ReleaseType {
+    #[inline]
+    fn decode(value: &Value, _arena: &mut Arena) -> Option<Self> {
+        match value {
+            Value::Int(0) => Some(ReleaseType::Stable),
+            Value::Int(1) => Some(ReleaseType::Unstable),
+            Value::Int(2) => Some(ReleaseType::Beta),
+            _ => None,
+        }
+    }
+}
+
+impl Encode for ModuleStatus {
+    #[inline]
+    fn encode(&self) -> Value {
+        Value::Int(*self as i64)
+    }
+}
+
+impl Decode for ModuleStatus {
+    #[inline]
+    fn decode(value: &Value, _arena: &mut Arena) -> Option<Self> {
+        match value {
+            Value::Int(0) => Some(ModuleStatus::Unloaded),
+            Value::Int(1) => Some(ModuleStatus::Terminated),
+            Value::Int(2) => Some(ModuleStatus::Ready),
+            _ => None,
+        }
+    }
+}
+
+impl Encode for Version {
+    fn encode(&self) -> Value {
+        Value::List(vec![
+            self.major.encode(),
+            self.minor.encode(),
+            self.patch.encode(),
+            self.build.encode(),
+            self.release_number.encode(),
+            self.release_type.encode(),
+        ])
+    }
+}
+
+impl Decode for Version {
+    fn decode(value: &Value, arena: &mut Arena) -> Option<Self> {
+        match value {
+            Value::List(values) => match values.as_slice() {
+                [major, minor, patch, build, release_number, release_type] => Some(Version {
+                    major: Decode::decode(major, arena)?,
+                    minor: Decode::decode(minor, arena)?,
+                    patch: Decode::decode(patch, arena)?,
+                    build: Decode::decode(build, arena)?,
+                    release_number: Decode::decode(release_number, arena)?,
+                    release_type: Decode::decode(release_type, arena)?,
+                }),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<const N: usize> Encode for StaticVec<u8, N> {
+    #[inline]
+    fn encode(&self) -> Value {
+        u8::encode_slice(self.as_ref())
+    }
+}
+
+impl<const N: usize> Decode for StaticVec<u8, N> {
+    #[inline]
+    fn decode(value: &Value, arena: &mut Arena) -> Option<Self> {
+        let bytes = u8::decode_vec(value, arena)?;
+        if bytes.len() <= N {
+            Some(Self::from(bytes.as_slice()))
+        } else {
+            None
+        }
+    }
+}
+
+impl Encode for ModuleInfo {
+    fn encode(&self) -> Value {
+        Value::List(vec![self.name.encode(), self.version.encode()])
+    }
+}
+
+impl Decode for ModuleInfo {
+    fn decode(value: &Value, arena: &mut Arena) -> Option<Self> {
+        match value {
+            Value::List(values) => match values.as_slice() {
+                [name, version] => Some(ModuleInfo {
+                    name: Decode::decode(name, arena)?,
+                    version: Decode::decode(version, arena)?,
+                }),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Encode for InterfaceDescriptor<'_> {
+    fn encode(&self) -> Value {
+        Value::List(vec![
+            self.name.encode(),
+            self.version.encode(),
+            self.extensions.encode(),
+        ])
+    }
+}
+
+impl Decode for InterfaceDescriptor<'static> {
+    fn decode(value: &Value, arena: &mut Arena) -> Option<Self> {
+        match value {
+            Value::List(values) => match values.as_slice() {
+                [name, version, extensions] => Some(InterfaceDescriptor {
+                    name: Decode::decode(name, arena)?,
+                    version: Decode::decode(version, arena)?,
+                    extensions: Decode::decode(extensions, arena)?,
+                }),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<T: Encode + Copy> Encode for ConstSpan<T> {
+    #[inline]
+    fn encode(&self) -> Value {
+        T::encode_slice(self.as_ref())
+    }
+}
+
+impl<T: Decode + Copy + 'static> Decode for ConstSpan<T> {
+    #[inline]
+    fn decode(value: &Value, arena: &mut Arena) -> Option<Self> {
+        T::decode_vec(value, arena).map(|values| arena.alloc_slice(values))
+    }
+}
+
+impl<T: Encode + Copy> Encode for SpanRef<'_, T> {
+    #[inline]
+    fn encode(&self) -> Value {
+        T::encode_slice(self.as_slice())
+    }
+}
+
+/// Replayed spans borrow from the arena, which outlives every replayed call.
+impl<T: Decode + Copy + 'static> Decode for SpanRef<'static, T> {
+    #[inline]
+    fn decode(value: &Value, arena: &mut Arena) -> Option<Self> {
+        ConstSpan::decode(value, arena).map(|span| unsafe { SpanRef::from_unbound(span) })
+    }
+}
+
+/// Only the length of output buffers is recorded.
+impl<T: Copy> Encode for MutSpan<T> {
+    #[inline]
+    fn encode(&self) -> Value {
+        self.len().encode()
+    }
+}
+
+impl<T: Encode> Encode for NonNullConst<T> {
+    #[inline]
+    fn encode(&self) -> Value {
+        unsafe { self.as_ref() }.encode()
+    }
+}
+
+impl<T: Decode + 'static> Decode for NonNullConst<T> {
+    #[inline]
+    fn decode(value: &Value, arena: &mut Arena) -> Option<Self> {
+        T::decode(value, arena).map(|value| arena.alloc(value))
+    }
+}
+
+impl<T: Encode> Encode for Result<T, Error> {
+    #[inline]
+    fn encode(&self) -> Value {
+        match self {
+            Result::Ok(value) => value.encode(),
+            Result::Err(e) => Value::Err(e.display_info().to_string()),
+        }
+    }
+}
+
+impl<T: Decode> Decode for Result<T, Error> {
+    #[inline]
+    fn decode(value: &Value, arena: &mut Arena) -> Option<Self> {
+        match value {
+            Value::Err(message) => Some(Result::Err(Error::from(RecordedError(message.clone())))),
+            value => T::decode(value, arena).map(Result::Ok),
+        }
+    }
+}
+
+/// Decodes a recorded address.
+fn decode_address<T>(value: &Value) -> Option<NonNull<T>> {
+    match value {
+        Value::Ptr(address) => NonNull::new(*address as usize as *mut T),
+        _ => None,
+    }
+}
+
+/// Encodes an address.
+fn encode_address<T: ?Sized>(ptr: *const T) -> Value {
+    Value::Ptr(ptr as *const () as usize as u64)
+}
+
+impl Encode for Interface {
+    #[inline]
+    fn encode(&self) -> Value {
+        encode_address(self.interface.as_ptr())
+    }
+}
+
+impl Decode for Interface {
+    #[inline]
+    fn decode(value: &Value, _arena: &mut Arena) -> Option<Self> {
+        decode_address(value).map(|interface| Interface { interface })
+    }
+}
+
+impl Encode for Symbol<NonNullConst<c_void>> {
+    #[inline]
+    fn encode(&self) -> Value {
+        encode_address(self.symbol.as_ptr())
+    }
+}
+
+impl Decode for Symbol<NonNullConst<c_void>> {
+    #[inline]
+    fn decode(value: &Value, _arena: &mut Arena) -> Option<Self> {
+        decode_address::<c_void>(value).map(|symbol| Symbol {
+            symbol: NonNullConst::from(symbol),
+        })
+    }
+}
+
+impl Encode for Symbol<CBaseFn> {
+    #[inline]
+    fn encode(&self) -> Value {
+        encode_address(self.symbol as *const ())
+    }
+}
+
+impl Decode for Symbol<CBaseFn> {
+    #[inline]
+    fn decode(value: &Value, _arena: &mut Arena) -> Option<Self> {
+        decode_address::<()>(value).map(|symbol| Symbol {
+            symbol: unsafe { std::mem::transmute::<*mut (), CBaseFn>(symbol.as_ptr()) },
+        })
+    }
+}
+
+macro_rules! loader_impls {
+    ($($ty:ident),*) => {
+        $(
+            impl Encode for $ty {
+                fn encode(&self) -> Value {
+                    let loader = self.loader.map_or(std::ptr::null(), |l| l.as_ptr());
+                    Value::List(vec![encode_address(loader), encode_address(self.vtable.as_ptr())])
+                }
+            }
+
+            impl Decode for $ty {
+                fn decode(value: &Value, _arena: &mut Arena) -> Option<Self> {
+                    match value {
+                        Value::List(values) => match values.as_slice() {
+                            [Value::Ptr(loader), vtable] => Some($ty {
+                                loader: NonNull::new(*loader as usize as *mut _),
+                                vtable: NonNullConst::from(decode_address(vtable)?),
+                            }),
+                            _ => None,
+                        },
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+loader_impls!(LibraryLoaderInterface, ModuleLoaderInterface);
+
+/// Encodes the result of a function writing into `buffer`, together with the written values.
+pub(super) fn encode_buffered<T: Encode + Copy>(
+    result: &Result<usize, Error>,
+    buffer: MutSpan<T>,
+) -> Value {
+    let written = match result {
+        Result::Ok(len) => T::encode_slice(&buffer.as_ref()[..*len]),
+        Result::Err(_) => Value::Unit,
+    };
+    Value::List(vec![result.encode(), written])
+}
+
+/// Decodes the result of a function writing into `buffer`, writing the recorded values.
+pub(super) fn decode_buffered<T: Decode + Copy>(
+    value: &Value,
+    arena: &mut Arena,
+    mut buffer: MutSpan<T>,
+) -> Option<Result<usize, Error>> {
+    match value {
+        Value::List(values) => match values.as_slice() {
+            [result, written] => {
+                let result = Result::<usize, Error>::decode(result, arena)?;
+                if let Result::Ok(len) = result {
+                    let written = T::decode_vec(written, arena)?;
+                    if written.len() != len || len > buffer.len() {
+                        return None;
+                    }
+                    for (dst, src) in buffer.as_mut().iter_mut().zip(written) {
+                        *dst = src;
+                    }
+                }
+                Some(result)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,216 @@
+use crate::collections::{NonNullConst, Optional, Result};
+use crate::errors::Error;
+use crate::guard::abort_on_unwind;
+use crate::module::{Interface, InterfaceDescriptor, ModuleHandle};
+use crate::record::{Call, Encode, Recording, Value};
+use crate::sys::api::{GetFunctionFn, SysBinding};
+use crate::sys::sync_handler::SyncHandlerInterface;
+use crate::{
+    Bool, CBase, CBaseBinding, CBaseFn, CBaseInterface, CBaseInterfaceVTable, FnId,
+    CBASE_INTERFACE_NAME,
+};
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::sync::{Mutex, PoisonError};
+
+/// An interface decorator recording the calls to the version, library and module apis.
+///
+/// Like the [InterceptedInterface](crate::intercept::InterceptedInterface), the decorator
+/// exposes its own interface, which can be passed to the `initialize` functions through
+/// [CBaseBinding::base_module] and [RecordingInterface::get_function_fn].
+///
+/// # Note
+///
+/// The functions of the sys api are forwarded without being recorded.
+/// A panic while recording a call aborts the process.
+/// Extension functions are not exposed by the decorator.
+#[derive(Debug)]
+pub struct RecordingInterface {
+    interface: CBaseInterface,
+    pub(super) vtable: CBaseInterfaceVTable,
+    pub(super) inner: CBaseInterface,
+    recording: Mutex<Recording>,
+    _pinned: PhantomPinned,
+}
+
+unsafe impl Send for RecordingInterface {}
+unsafe impl Sync for RecordingInterface {}
+
+impl RecordingInterface {
+    /// Wraps an interface.
+    ///
+    /// # Safety
+    ///
+    /// The wrapped interface must outlive the decorator.
+    pub unsafe fn new(inner: CBaseInterface) -> Pin<Box<Self>> {
+        let mut recorder = Box::new(Self {
+            interface: inner,
+            vtable: Self::build_vtable(inner.interface_version()),
+            inner,
+            recording: Mutex::new(Recording::new()),
+            _pinned: PhantomPinned,
+        });
+        recorder.interface = CBaseInterface {
+            base_module: Some(NonNull::from(&*recorder).cast()),
+            vtable: NonNullConst::from(&recorder.vtable),
+        };
+        Box::into_pin(recorder)
+    }
+
+    /// Fetches the interface exposed by the decorator.
+    #[inline]
+    pub fn interface(&self) -> CBaseInterface {
+        self.interface
+    }
+
+    /// Fetches the wrapped interface.
+    #[inline]
+    pub fn inner(&self) -> CBaseInterface {
+        self.inner
+    }
+
+    /// Fetches the `get_function` function of the decorator.
+    ///
+    /// Can be passed together with [CBaseBinding::base_module]
+    /// to the `initialize` functions.
+    #[inline]
+    pub fn get_function_fn(&self) -> GetFunctionFn {
+        self.vtable.sys_get_function_fn
+    }
+
+    /// Fetches a copy of the calls recorded until now.
+    #[inline]
+    pub fn recording(&self) -> Recording {
+        self.recording
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Takes the calls recorded until now, leaving an empty recording.
+    #[inline]
+    pub fn take_recording(&self) -> Recording {
+        std::mem::take(
+            &mut *self
+                .recording
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        )
+    }
+
+    /// Casts the `base_module` of a trampoline back to the decorator.
+    #[inline]
+    pub(super) unsafe fn from_base_module<'a>(base_module: Option<NonNull<CBase>>) -> &'a Self {
+        &*base_module
+            .expect("Invalid base module")
+            .cast::<Self>()
+            .as_ptr()
+    }
+
+    /// Appends a call to the recording.
+    #[inline]
+    pub(super) fn record(&self, id: FnId, args: Vec<Value>, result: Value) {
+        self.recording
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Call { id, args, result })
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_shutdown_fn(
+        base_module: Option<NonNull<CBase>>,
+    ) -> ! {
+        let this = Self::from_base_module(base_module);
+        (this.inner.vtable.as_ref().sys_shutdown_fn)(this.inner.base_module)
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_panic_fn(
+        base_module: Option<NonNull<CBase>>,
+        error: Optional<Error>,
+    ) -> ! {
+        let this = Self::from_base_module(base_module);
+        (this.inner.vtable.as_ref().sys_panic_fn)(this.inner.base_module, error)
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_has_function_fn(
+        base_module: Option<NonNull<CBase>>,
+        id: FnId,
+    ) -> Bool {
+        let this = Self::from_base_module(base_module);
+        match this.trampoline(id) {
+            Optional::Some(_) => this.inner.has_function(id),
+            Optional::None => Bool::False,
+        }
+    }
+
+    // `CBaseFn` only serves as a type-erased function pointer.
+    #[allow(improper_ctypes_definitions)]
+    pub(super) unsafe extern "C-unwind" fn sys_get_function_fn(
+        base_module: Option<NonNull<CBase>>,
+        id: FnId,
+    ) -> Optional<CBaseFn> {
+        let this = Self::from_base_module(base_module);
+        match this.inner.get_function(id) {
+            Optional::Some(_) => this.trampoline(id),
+            Optional::None => Optional::None,
+        }
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_lock_fn(base_module: Option<NonNull<CBase>>) {
+        let this = Self::from_base_module(base_module);
+        this.inner.lock()
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_try_lock_fn(
+        base_module: Option<NonNull<CBase>>,
+    ) -> Bool {
+        let this = Self::from_base_module(base_module);
+        this.inner.try_lock()
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_unlock_fn(base_module: Option<NonNull<CBase>>) {
+        let this = Self::from_base_module(base_module);
+        this.inner.unlock()
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_get_sync_handler_fn(
+        base_module: Option<NonNull<CBase>>,
+    ) -> SyncHandlerInterface {
+        let this = Self::from_base_module(base_module);
+        this.inner.get_sync_handler()
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_set_sync_handler_fn(
+        base_module: Option<NonNull<CBase>>,
+        handler: Optional<SyncHandlerInterface>,
+    ) {
+        let this = Self::from_base_module(base_module);
+        let mut inner = this.inner;
+        inner.set_sync_handler(handler)
+    }
+
+    pub(super) unsafe extern "C-unwind" fn module_get_interface_fn(
+        base_module: Option<NonNull<CBase>>,
+        handle: ModuleHandle,
+        interface: NonNullConst<InterfaceDescriptor<'static>>,
+    ) -> Result<Interface, Error> {
+        let this = Self::from_base_module(base_module);
+        let args = abort_on_unwind(|| vec![handle.encode(), interface.encode()]);
+        let result = (this.inner.vtable.as_ref().module_get_interface_fn)(
+            this.inner.base_module,
+            handle,
+            interface,
+        );
+        abort_on_unwind(|| this.record(FnId::ModuleGetInterface, args, result.encode()));
+
+        // Hand out the decorator instead of the wrapped `emf-core-base` interface.
+        match result {
+            Result::Ok(_) if interface.as_ref().name.as_str() == Ok(CBASE_INTERFACE_NAME) => {
+                Result::Ok(Interface {
+                    interface: NonNull::from(&this.interface).cast(),
+                })
+            }
+            result => result,
+        }
+    }
+}
@@ -0,0 +1,382 @@
+use crate::collections::{NonNullConst, Optional, Result};
+use crate::errors::Error;
+use crate::module::{Interface, InterfaceDescriptor, ModuleHandle};
+use crate::record::encode::Arena;
+use crate::record::{Call, Decode, Encode, Recording, Value};
+use crate::sys::api::GetFunctionFn;
+use crate::sys::sync_handler::{SyncHandler, SyncHandlerInterface, SyncHandlerVTable};
+use crate::version::VERSION;
+use crate::{
+    Bool, CBase, CBaseFn, CBaseInterface, CBaseInterfaceVTable, FnId, TypeWrapper,
+    CBASE_INTERFACE_NAME,
+};
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// A divergence between a replay and its recording.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Divergence {
+    /// A different function was called.
+    ///
+    /// The replay can not continue after this divergence.
+    Function {
+        /// Index of the call in the recording.
+        position: usize,
+        /// The recorded call.
+        expected: Call,
+        /// Id of the called function.
+        actual: FnId,
+    },
+    /// The function was called with different arguments.
+    ///
+    /// The replay continues with the recorded result.
+    Arguments {
+        /// Index of the call in the recording.
+        position: usize,
+        /// The recorded call.
+        expected: Call,
+        /// Arguments of the call.
+        actual: Vec<Value>,
+    },
+    /// A function was called after the end of the recording.
+    ///
+    /// The replay can not continue after this divergence.
+    Exhausted {
+        /// Index of the call.
+        position: usize,
+        /// Id of the called function.
+        actual: FnId,
+    },
+    /// The replay ended before the end of the recording.
+    Incomplete {
+        /// Index of the first call, which was not replayed.
+        position: usize,
+        /// Number of calls, which were not replayed.
+        remaining: usize,
+    },
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Divergence::Function {
+                position,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "call {}: expected `{}`, found a call to `{:?}`",
+                position, expected, actual
+            ),
+            Divergence::Arguments {
+                position,
+                expected,
+                actual,
+            } => {
+                write!(
+                    f,
+                    "call {}: expected `{}`, found the arguments `",
+                    position, expected
+                )?;
+                super::write_list(f, actual)?;
+                write!(f, "`")
+            }
+            Divergence::Exhausted { position, actual } => write!(
+                f,
+                "call {}: found a call to `{:?}` after the end of the recording",
+                position, actual
+            ),
+            Divergence::Incomplete {
+                position,
+                remaining,
+            } => write!(
+                f,
+                "call {}: the replay ended with {} remaining calls",
+                position, remaining
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Divergence {}
+
+/// A fake interface replaying a [Recording].
+///
+/// Every call to the version, library and module apis is matched against the next
+/// recorded call and returns the recorded result. Calls with different arguments are
+/// flagged as a [Divergence] and the replay continues. Calls to a different function
+/// or past the end of the recording are flagged and panic, as no result is available.
+///
+/// # Note
+///
+/// The functions of the sys api are implemented as no-ops, except for
+/// [FnId::SysShutdown] and [FnId::SysPanic], which panic.
+/// Unlike the decorators, the fake interface is not protected against unwinding,
+/// as its panics are meant to reach the Rust code driving the replay.
+/// Extension functions are not exposed.
+#[derive(Debug)]
+pub struct ReplayInterface {
+    interface: CBaseInterface,
+    pub(super) vtable: CBaseInterfaceVTable,
+    state: Mutex<ReplayState>,
+    _pinned: PhantomPinned,
+}
+
+#[derive(Debug)]
+struct ReplayState {
+    calls: VecDeque<Call>,
+    position: usize,
+    divergences: Vec<Divergence>,
+    arena: Arena,
+}
+
+unsafe impl Send for ReplayInterface {}
+unsafe impl Sync for ReplayInterface {}
+
+impl ReplayInterface {
+    /// Constructs a fake interface replaying `recording`.
+    pub fn new(recording: Recording) -> Pin<Box<Self>> {
+        let mut replay = Box::new(Self {
+            interface: CBaseInterface {
+                base_module: None,
+                vtable: NonNullConst::dangling(),
+            },
+            vtable: Self::build_vtable(VERSION),
+            state: Mutex::new(ReplayState {
+                calls: recording.into_iter().collect(),
+                position: 0,
+                divergences: Vec::new(),
+                arena: Arena::default(),
+            }),
+            _pinned: PhantomPinned,
+        });
+        replay.interface = CBaseInterface {
+            base_module: Some(NonNull::from(&*replay).cast()),
+            vtable: NonNullConst::from(&replay.vtable),
+        };
+        Box::into_pin(replay)
+    }
+
+    /// Fetches the fake interface.
+    #[inline]
+    pub fn interface(&self) -> CBaseInterface {
+        self.interface
+    }
+
+    /// Fetches the `get_function` function of the fake interface.
+    ///
+    /// Can be passed together with [CBaseBinding::base_module](crate::CBaseBinding::base_module)
+    /// to the `initialize` functions.
+    #[inline]
+    pub fn get_function_fn(&self) -> GetFunctionFn {
+        self.vtable.sys_get_function_fn
+    }
+
+    /// Fetches the divergences flagged until now.
+    #[inline]
+    pub fn divergences(&self) -> Vec<Divergence> {
+        self.state().divergences.clone()
+    }
+
+    /// Fetches the number of recorded calls, which were not replayed yet.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.state().calls.len()
+    }
+
+    /// Checks whether the whole recording was replayed without divergences.
+    ///
+    /// # Return
+    ///
+    /// `Ok` if the replay is complete, the divergences otherwise.
+    pub fn finish(&self) -> std::result::Result<(), Vec<Divergence>> {
+        let state = self.state();
+        let mut divergences = state.divergences.clone();
+        if !state.calls.is_empty() {
+            divergences.push(Divergence::Incomplete {
+                position: state.position,
+                remaining: state.calls.len(),
+            });
+        }
+
+        if divergences.is_empty() {
+            Ok(())
+        } else {
+            Err(divergences)
+        }
+    }
+
+    #[inline]
+    fn state(&self) -> MutexGuard<'_, ReplayState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Casts the `base_module` of a function back to the fake interface.
+    #[inline]
+    unsafe fn from_base_module<'a>(base_module: Option<NonNull<CBase>>) -> &'a Self {
+        &*base_module
+            .expect("Invalid base module")
+            .cast::<Self>()
+            .as_ptr()
+    }
+
+    /// Replays the next call.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the call diverges in the function or the result is malformed.
+    pub(super) unsafe fn replay<R>(
+        base_module: Option<NonNull<CBase>>,
+        id: FnId,
+        args: Vec<Value>,
+        decode: impl FnOnce(&Value, &mut Arena) -> Option<R>,
+    ) -> R {
+        let this = Self::from_base_module(base_module);
+        let mut state = this.state();
+        let state = &mut *state;
+
+        let position = state.position;
+        let call = match state.calls.front() {
+            Some(call) if call.id == id => state.calls.pop_front().unwrap(),
+            Some(call) => {
+                let divergence = Divergence::Function {
+                    position,
+                    expected: call.clone(),
+                    actual: id,
+                };
+                state.divergences.push(divergence.clone());
+                panic!("Replay diverged: {}", divergence)
+            }
+            None => {
+                let divergence = Divergence::Exhausted {
+                    position,
+                    actual: id,
+                };
+                state.divergences.push(divergence.clone());
+                panic!("Replay diverged: {}", divergence)
+            }
+        };
+        state.position += 1;
+
+        let matches = call.args.len() == args.len()
+            && call
+                .args
+                .iter()
+                .zip(&args)
+                .all(|(lhs, rhs)| lhs.matches(rhs));
+        if !matches {
+            state.divergences.push(Divergence::Arguments {
+                position,
+                expected: call.clone(),
+                actual: args,
+            });
+        }
+
+        match decode(&call.result, &mut state.arena) {
+            Some(result) => result,
+            None => panic!("Malformed result of the recorded call `{}`", call),
+        }
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_shutdown_fn(
+        _base_module: Option<NonNull<CBase>>,
+    ) -> ! {
+        panic!("The replayed interface was shut down")
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_panic_fn(
+        _base_module: Option<NonNull<CBase>>,
+        error: Optional<Error>,
+    ) -> ! {
+        match error {
+            Optional::Some(error) => panic!("{}", error.display_info()),
+            Optional::None => panic!("The replayed interface panicked"),
+        }
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_has_function_fn(
+        base_module: Option<NonNull<CBase>>,
+        id: FnId,
+    ) -> Bool {
+        let this = Self::from_base_module(base_module);
+        match this.trampoline(id) {
+            Optional::Some(_) => Bool::True,
+            Optional::None => Bool::False,
+        }
+    }
+
+    // `CBaseFn` only serves as a type-erased function pointer.
+    #[allow(improper_ctypes_definitions)]
+    pub(super) unsafe extern "C-unwind" fn sys_get_function_fn(
+        base_module: Option<NonNull<CBase>>,
+        id: FnId,
+    ) -> Optional<CBaseFn> {
+        Self::from_base_module(base_module).trampoline(id)
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_lock_fn(_base_module: Option<NonNull<CBase>>) {}
+
+    pub(super) unsafe extern "C-unwind" fn sys_try_lock_fn(
+        _base_module: Option<NonNull<CBase>>,
+    ) -> Bool {
+        Bool::True
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_unlock_fn(_base_module: Option<NonNull<CBase>>) {}
+
+    pub(super) unsafe extern "C-unwind" fn sys_get_sync_handler_fn(
+        _base_module: Option<NonNull<CBase>>,
+    ) -> SyncHandlerInterface {
+        SyncHandlerInterface {
+            handler: None,
+            vtable: NonNullConst::from(&NOOP_SYNC_HANDLER_VTABLE),
+        }
+    }
+
+    pub(super) unsafe extern "C-unwind" fn sys_set_sync_handler_fn(
+        _base_module: Option<NonNull<CBase>>,
+        _handler: Optional<SyncHandlerInterface>,
+    ) {
+    }
+
+    pub(super) unsafe extern "C-unwind" fn module_get_interface_fn(
+        base_module: Option<NonNull<CBase>>,
+        handle: ModuleHandle,
+        interface: NonNullConst<InterfaceDescriptor<'static>>,
+    ) -> Result<Interface, Error> {
+        let args = vec![handle.encode(), interface.encode()];
+        let result = Self::replay(
+            base_module,
+            FnId::ModuleGetInterface,
+            args,
+            Result::<Interface, Error>::decode,
+        );
+
+        // Hand out the fake interface instead of the recorded `emf-core-base` interface.
+        match result {
+            Result::Ok(_) if interface.as_ref().name.as_str() == Ok(CBASE_INTERFACE_NAME) => {
+                let this = Self::from_base_module(base_module);
+                Result::Ok(Interface {
+                    interface: NonNull::from(&this.interface).cast(),
+                })
+            }
+            result => result,
+        }
+    }
+}
+
+static NOOP_SYNC_HANDLER_VTABLE: SyncHandlerVTable = SyncHandlerVTable {
+    lock_fn: TypeWrapper(noop_lock),
+    try_lock_fn: TypeWrapper(noop_try_lock),
+    unlock_fn: TypeWrapper(noop_lock),
+};
+
+unsafe extern "C-unwind" fn noop_lock(_handler: Option<NonNull<SyncHandler>>) {}
+
+unsafe extern "C-unwind" fn noop_try_lock(_handler: Option<NonNull<SyncHandler>>) -> Bool {
+    Bool::True
+}
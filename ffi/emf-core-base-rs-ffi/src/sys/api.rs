@@ -4,8 +4,8 @@
 use crate::collections::Optional;
 use crate::errors::Error;
 use crate::sys::sync_handler::SyncHandlerInterface;
-use crate::{Bool, CBase, CBaseFn, CBaseInterface, FnId, TypeWrapper};
-use std::ptr::NonNull;
+use crate::{Bool, CBase, CBaseFn, FnId, TypeWrapper};
+use core::ptr::NonNull;
 
 pub type ShutdownFn =
     TypeWrapper<unsafe extern "C-unwind" fn(base_module: Option<NonNull<CBase>>) -> !>;
@@ -147,49 +147,88 @@ pub trait SysBinding {
     unsafe fn set_sync_handler(&mut self, handler: Optional<SyncHandlerInterface>);
 }
 
-impl SysBinding for CBaseInterface {
-    #[inline]
-    unsafe fn shutdown(&mut self) -> ! {
-        (self.vtable.as_ref().sys_shutdown_fn)(self.base_module)
-    }
-
-    #[inline]
-    unsafe fn panic(&self, error: Optional<Error>) -> ! {
-        (self.vtable.as_ref().sys_panic_fn)(self.base_module, error)
-    }
-
-    #[inline]
-    unsafe fn has_function(&self, id: FnId) -> Bool {
-        (self.vtable.as_ref().sys_has_function_fn)(self.base_module, id)
-    }
-
-    #[inline]
-    unsafe fn get_function(&self, id: FnId) -> Optional<CBaseFn> {
-        (self.vtable.as_ref().sys_get_function_fn)(self.base_module, id)
-    }
-
-    #[inline]
-    unsafe fn lock(&self) {
-        (self.vtable.as_ref().sys_lock_fn)(self.base_module)
-    }
-
-    #[inline]
-    unsafe fn try_lock(&self) -> Bool {
-        (self.vtable.as_ref().sys_try_lock_fn)(self.base_module)
-    }
-
-    #[inline]
-    unsafe fn unlock(&self) {
-        (self.vtable.as_ref().sys_unlock_fn)(self.base_module)
-    }
-
-    #[inline]
-    unsafe fn get_sync_handler(&self) -> SyncHandlerInterface {
-        (self.vtable.as_ref().sys_get_sync_handler_fn)(self.base_module)
-    }
-
-    #[inline]
-    unsafe fn set_sync_handler(&mut self, handler: Optional<SyncHandlerInterface>) {
-        (self.vtable.as_ref().sys_set_sync_handler_fn)(self.base_module, handler)
-    }
+/// Implements [SysBinding] by calling the vtable of a [CBaseInterface].
+///
+/// The interface is accessed through the fields following `self`, which allows
+/// implementing the binding for the types wrapping an interface.
+macro_rules! impl_sys_binding {
+    (impl<$($generic:ident: $bound:path),*> for $ty:ty, self$(.$field:ident)*) => {
+        const _: () = {
+            use $crate::collections::Optional;
+            use $crate::errors::Error;
+            use $crate::sys::api::SysBinding;
+            use $crate::sys::sync_handler::SyncHandlerInterface;
+            use $crate::{Bool, CBaseFn, CBaseInterface, FnId};
+
+            #[inline]
+            fn base_interface<$($generic: $bound),*>(this: &$ty) -> &CBaseInterface {
+                &this$(.$field)*
+            }
+
+            impl<$($generic: $bound),*> SysBinding for $ty {
+                #[inline]
+                unsafe fn shutdown(&mut self) -> ! {
+                    (base_interface(self).vtable.as_ref().sys_shutdown_fn)(base_interface(self).base_module)
+                }
+
+                #[inline]
+                unsafe fn panic(&self, error: Optional<Error>) -> ! {
+                    (base_interface(self).vtable.as_ref().sys_panic_fn)(
+                        base_interface(self).base_module,
+                        error,
+                    )
+                }
+
+                #[inline]
+                unsafe fn has_function(&self, id: FnId) -> Bool {
+                    (base_interface(self).vtable.as_ref().sys_has_function_fn)(
+                        base_interface(self).base_module,
+                        id,
+                    )
+                }
+
+                #[inline]
+                unsafe fn get_function(&self, id: FnId) -> Optional<CBaseFn> {
+                    (base_interface(self).vtable.as_ref().sys_get_function_fn)(
+                        base_interface(self).base_module,
+                        id,
+                    )
+                }
+
+                #[inline]
+                unsafe fn lock(&self) {
+                    (base_interface(self).vtable.as_ref().sys_lock_fn)(base_interface(self).base_module)
+                }
+
+                #[inline]
+                unsafe fn try_lock(&self) -> Bool {
+                    (base_interface(self).vtable.as_ref().sys_try_lock_fn)(base_interface(self).base_module)
+                }
+
+                #[inline]
+                unsafe fn unlock(&self) {
+                    (base_interface(self).vtable.as_ref().sys_unlock_fn)(base_interface(self).base_module)
+                }
+
+                #[inline]
+                unsafe fn get_sync_handler(&self) -> SyncHandlerInterface {
+                    (base_interface(self).vtable.as_ref().sys_get_sync_handler_fn)(
+                        base_interface(self).base_module,
+                    )
+                }
+
+                #[inline]
+                unsafe fn set_sync_handler(&mut self, handler: Optional<SyncHandlerInterface>) {
+                    (base_interface(self).vtable.as_ref().sys_set_sync_handler_fn)(
+                        base_interface(self).base_module,
+                        handler,
+                    )
+                }
+            }
+        };
+    };
 }
+
+pub(crate) use impl_sys_binding;
+
+impl_sys_binding!(impl<> for CBaseInterface, self);
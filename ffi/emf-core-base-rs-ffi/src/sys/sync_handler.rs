@@ -3,7 +3,7 @@
 //! Any object that can be wrapped into a [SyncHandlerInterface] can be used as a sync handler.
 use crate::collections::NonNullConst;
 use crate::{Bool, TypeWrapper};
-use std::ptr::NonNull;
+use core::ptr::NonNull;
 
 /// Opaque structure representing a sync handler.
 #[repr(C)]
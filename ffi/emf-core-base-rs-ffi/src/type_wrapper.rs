@@ -27,25 +27,25 @@ macro_rules! fnptr_impls_safety_abi {
 
         impl<Ret, $($Arg),*> PartialOrd for TypeWrapper<$FnTy> {
             #[inline]
-            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
                 (self.0 as usize).partial_cmp(&(other.0 as usize))
             }
         }
 
         impl<Ret, $($Arg),*> Ord for TypeWrapper<$FnTy> {
             #[inline]
-            fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
                 (self.0 as usize).cmp(&(other.0 as usize))
             }
         }
 
-        impl<Ret, $($Arg),*> std::hash::Hash for TypeWrapper<$FnTy> {
-            fn hash<HH: std::hash::Hasher>(&self, state: &mut HH) {
+        impl<Ret, $($Arg),*> core::hash::Hash for TypeWrapper<$FnTy> {
+            fn hash<HH: core::hash::Hasher>(&self, state: &mut HH) {
                 state.write_usize(self.0 as usize)
             }
         }
 
-        impl<Ret, $($Arg),*> std::ops::Deref for TypeWrapper<$FnTy> {
+        impl<Ret, $($Arg),*> core::ops::Deref for TypeWrapper<$FnTy> {
             type Target = $FnTy;
 
             #[inline]
@@ -54,32 +54,32 @@ macro_rules! fnptr_impls_safety_abi {
             }
         }
 
-        impl<Ret, $($Arg),*> std::ops::DerefMut for TypeWrapper<$FnTy> {
+        impl<Ret, $($Arg),*> core::ops::DerefMut for TypeWrapper<$FnTy> {
             #[inline]
             fn deref_mut(&mut self) -> &mut Self::Target {
                 &mut self.0
             }
         }
 
-        impl<Ret, $($Arg),*> std::fmt::Pointer for TypeWrapper<$FnTy> {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        impl<Ret, $($Arg),*> core::fmt::Pointer for TypeWrapper<$FnTy> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 // HACK: The intermediate cast as usize is required for AVR
                 // so that the address space of the source function pointer
                 // is preserved in the final function pointer.
                 //
                 // https://github.com/avr-rust/rust/issues/143
-                std::fmt::Pointer::fmt(&(self.0 as usize as *const ()), f)
+                core::fmt::Pointer::fmt(&(self.0 as usize as *const ()), f)
             }
         }
 
-        impl<Ret, $($Arg),*> std::fmt::Debug for TypeWrapper<$FnTy> {
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        impl<Ret, $($Arg),*> core::fmt::Debug for TypeWrapper<$FnTy> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 // HACK: The intermediate cast as usize is required for AVR
                 // so that the address space of the source function pointer
                 // is preserved in the final function pointer.
                 //
                 // https://github.com/avr-rust/rust/issues/143
-                std::fmt::Pointer::fmt(&(self.0 as usize as *const ()), f)
+                core::fmt::Pointer::fmt(&(self.0 as usize as *const ()), f)
             }
         }
     }
@@ -7,13 +7,13 @@
 //! # let base_interface: &mut dyn CBaseBinding = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
 //! use emf_core_base_rs_ffi::sys::api::SysBinding;
 //! use emf_core_base_rs_ffi::version::api::VersionBinding;
-//! use emf_core_base_rs_ffi::collections::{NonNullConst, ConstSpan, Optional};
+//! use emf_core_base_rs_ffi::collections::{NonNullConst, SpanRef, Optional};
 //!
 //! unsafe {
 //!     // `base_interface` has the type `&mut dyn CBaseBinding`.
 //!     let v1 = VersionBinding::new_short(base_interface, 1, 2, 3);
 //!
-//!     let v2_string = ConstSpan::from("1.2.3-beta.5+54845652");
+//!     let v2_string = SpanRef::from("1.2.3-beta.5+54845652");
 //!     let v2 = match VersionBinding::from_string(
 //!                     base_interface,
 //!                     v2_string
@@ -42,7 +42,7 @@
 //!     }
 //! }
 //! ```
-use std::fmt::{Display, Formatter};
+use core::fmt::{Display, Formatter};
 
 pub mod api;
 
@@ -88,7 +88,7 @@ pub enum ReleaseType {
 }
 
 impl Display for ReleaseType {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self {
             ReleaseType::Stable => write!(f, "Stable"),
             ReleaseType::Unstable => write!(f, "Unstable"),
@@ -110,7 +110,7 @@ pub struct Version {
 }
 
 impl Display for Version {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         match self.release_type {
             ReleaseType::Stable => {
                 write!(
@@ -1,11 +1,11 @@
 //! Version api.
 //!
 //! The version api is exposed by the [VersionBinding] trait.
-use crate::collections::{ConstSpan, MutSpan, NonNullConst, Result};
+use crate::collections::{ConstSpan, MutSpan, NonNullConst, Result, SpanMut, SpanRef};
 use crate::errors::Error;
 use crate::version::{ReleaseType, Version};
-use crate::{Bool, CBase, CBaseInterface, TypeWrapper};
-use std::ptr::NonNull;
+use crate::{Bool, CBase, TypeWrapper};
+use core::ptr::NonNull;
 
 pub type NewShortFn = TypeWrapper<
     unsafe extern "C-unwind" fn(
@@ -201,7 +201,7 @@ pub trait VersionBinding {
     /// # Safety
     ///
     /// The function crosses the ffi boundary.
-    unsafe fn from_string(&self, buffer: ConstSpan<u8>) -> Result<Version, Error>;
+    unsafe fn from_string(&self, buffer: SpanRef<'_, u8>) -> Result<Version, Error>;
 
     /// Computes the length of the short version string.
     ///
@@ -252,7 +252,7 @@ pub trait VersionBinding {
     unsafe fn as_string_short(
         &self,
         version: NonNullConst<Version>,
-        buffer: MutSpan<u8>,
+        buffer: SpanMut<'_, u8>,
     ) -> Result<usize, Error>;
 
     /// Represents the version as a long string.
@@ -271,7 +271,7 @@ pub trait VersionBinding {
     unsafe fn as_string_long(
         &self,
         version: NonNullConst<Version>,
-        buffer: MutSpan<u8>,
+        buffer: SpanMut<'_, u8>,
     ) -> Result<usize, Error>;
 
     /// Represents the version as a full string.
@@ -290,7 +290,7 @@ pub trait VersionBinding {
     unsafe fn as_string_full(
         &self,
         version: NonNullConst<Version>,
-        buffer: MutSpan<u8>,
+        buffer: SpanMut<'_, u8>,
     ) -> Result<usize, Error>;
 
     /// Checks whether the version string is valid.
@@ -302,7 +302,7 @@ pub trait VersionBinding {
     /// # Safety
     ///
     /// The function crosses the ffi boundary.
-    unsafe fn string_is_valid(&self, version_string: ConstSpan<u8>) -> Bool;
+    unsafe fn string_is_valid(&self, version_string: SpanRef<'_, u8>) -> Bool;
 
     /// Compares two versions.
     ///
@@ -365,121 +365,210 @@ pub trait VersionBinding {
     unsafe fn is_compatible(&self, lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> Bool;
 }
 
-impl VersionBinding for CBaseInterface {
-    #[inline]
-    unsafe fn new_short(&self, major: i32, minor: i32, patch: i32) -> Version {
-        (self.vtable.as_ref().version_new_short_fn)(self.base_module, major, minor, patch)
-    }
-
-    #[inline]
-    unsafe fn new_long(
-        &self,
-        major: i32,
-        minor: i32,
-        patch: i32,
-        release_type: ReleaseType,
-        release_number: i8,
-    ) -> Version {
-        (self.vtable.as_ref().version_new_long_fn)(
-            self.base_module,
-            major,
-            minor,
-            patch,
-            release_type,
-            release_number,
-        )
-    }
-
-    #[inline]
-    unsafe fn new_full(
-        &self,
-        major: i32,
-        minor: i32,
-        patch: i32,
-        release_type: ReleaseType,
-        release_number: i8,
-        build: i64,
-    ) -> Version {
-        (self.vtable.as_ref().version_new_full_fn)(
-            self.base_module,
-            major,
-            minor,
-            patch,
-            release_type,
-            release_number,
-            build,
-        )
-    }
-
-    #[inline]
-    unsafe fn from_string(&self, buffer: ConstSpan<u8>) -> Result<Version, Error> {
-        (self.vtable.as_ref().version_from_string_fn)(self.base_module, buffer)
-    }
-
-    #[inline]
-    unsafe fn string_length_short(&self, version: NonNullConst<Version>) -> usize {
-        (self.vtable.as_ref().version_string_length_short_fn)(self.base_module, version)
-    }
-
-    #[inline]
-    unsafe fn string_length_long(&self, version: NonNullConst<Version>) -> usize {
-        (self.vtable.as_ref().version_string_length_long_fn)(self.base_module, version)
-    }
-
-    #[inline]
-    unsafe fn string_length_full(&self, version: NonNullConst<Version>) -> usize {
-        (self.vtable.as_ref().version_string_length_full_fn)(self.base_module, version)
-    }
-
-    #[inline]
-    unsafe fn as_string_short(
-        &self,
-        version: NonNullConst<Version>,
-        buffer: MutSpan<u8>,
-    ) -> Result<usize, Error> {
-        (self.vtable.as_ref().version_as_string_short_fn)(self.base_module, version, buffer)
-    }
+/// Implements [VersionBinding] by calling the vtable of a [CBaseInterface].
+///
+/// The interface is accessed through the fields following `self`, which allows
+/// implementing the binding for the types wrapping an interface.
+macro_rules! impl_version_binding {
+    (impl<$($generic:ident: $bound:path),*> for $ty:ty, self$(.$field:ident)*) => {
+        const _: () = {
+            use $crate::collections::{NonNullConst, Result, SpanMut, SpanRef};
+            use $crate::errors::Error;
+            use $crate::version::api::VersionBinding;
+            use $crate::version::{ReleaseType, Version};
+            use $crate::{Bool, CBaseInterface};
+
+            #[inline]
+            fn base_interface<$($generic: $bound),*>(this: &$ty) -> &CBaseInterface {
+                &this$(.$field)*
+            }
+
+            impl<$($generic: $bound),*> VersionBinding for $ty {
+                #[inline]
+                unsafe fn new_short(&self, major: i32, minor: i32, patch: i32) -> Version {
+                    (base_interface(self).vtable.as_ref().version_new_short_fn)(
+                        base_interface(self).base_module,
+                        major,
+                        minor,
+                        patch,
+                    )
+                }
+
+                #[inline]
+                unsafe fn new_long(
+                    &self,
+                    major: i32,
+                    minor: i32,
+                    patch: i32,
+                    release_type: ReleaseType,
+                    release_number: i8,
+                ) -> Version {
+                    (base_interface(self).vtable.as_ref().version_new_long_fn)(
+                        base_interface(self).base_module,
+                        major,
+                        minor,
+                        patch,
+                        release_type,
+                        release_number,
+                    )
+                }
+
+                #[inline]
+                unsafe fn new_full(
+                    &self,
+                    major: i32,
+                    minor: i32,
+                    patch: i32,
+                    release_type: ReleaseType,
+                    release_number: i8,
+                    build: i64,
+                ) -> Version {
+                    (base_interface(self).vtable.as_ref().version_new_full_fn)(
+                        base_interface(self).base_module,
+                        major,
+                        minor,
+                        patch,
+                        release_type,
+                        release_number,
+                        build,
+                    )
+                }
+
+                #[inline]
+                unsafe fn from_string(&self, buffer: SpanRef<'_, u8>) -> Result<Version, Error> {
+                    (base_interface(self).vtable.as_ref().version_from_string_fn)(
+                        base_interface(self).base_module,
+                        buffer.into(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn string_length_short(&self, version: NonNullConst<Version>) -> usize {
+                    (base_interface(self).vtable.as_ref().version_string_length_short_fn)(
+                        base_interface(self).base_module,
+                        version,
+                    )
+                }
+
+                #[inline]
+                unsafe fn string_length_long(&self, version: NonNullConst<Version>) -> usize {
+                    (base_interface(self).vtable.as_ref().version_string_length_long_fn)(
+                        base_interface(self).base_module,
+                        version,
+                    )
+                }
+
+                #[inline]
+                unsafe fn string_length_full(&self, version: NonNullConst<Version>) -> usize {
+                    (base_interface(self).vtable.as_ref().version_string_length_full_fn)(
+                        base_interface(self).base_module,
+                        version,
+                    )
+                }
+
+                #[inline]
+                unsafe fn as_string_short(
+                    &self,
+                    version: NonNullConst<Version>,
+                    buffer: SpanMut<'_, u8>,
+                ) -> Result<usize, Error> {
+                    (base_interface(self).vtable.as_ref().version_as_string_short_fn)(
+                        base_interface(self).base_module,
+                        version,
+                        buffer.into(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn as_string_long(
+                    &self,
+                    version: NonNullConst<Version>,
+                    buffer: SpanMut<'_, u8>,
+                ) -> Result<usize, Error> {
+                    (base_interface(self).vtable.as_ref().version_as_string_long_fn)(
+                        base_interface(self).base_module,
+                        version,
+                        buffer.into(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn as_string_full(
+                    &self,
+                    version: NonNullConst<Version>,
+                    buffer: SpanMut<'_, u8>,
+                ) -> Result<usize, Error> {
+                    (base_interface(self).vtable.as_ref().version_as_string_full_fn)(
+                        base_interface(self).base_module,
+                        version,
+                        buffer.into(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn string_is_valid(&self, version_string: SpanRef<'_, u8>) -> Bool {
+                    (base_interface(self).vtable.as_ref().version_string_is_valid_fn)(
+                        base_interface(self).base_module,
+                        version_string.into(),
+                    )
+                }
+
+                #[inline]
+                unsafe fn compare(
+                    &self,
+                    lhs: NonNullConst<Version>,
+                    rhs: NonNullConst<Version>,
+                ) -> i32 {
+                    (base_interface(self).vtable.as_ref().version_compare_fn)(
+                        base_interface(self).base_module,
+                        lhs,
+                        rhs,
+                    )
+                }
+
+                #[inline]
+                unsafe fn compare_weak(
+                    &self,
+                    lhs: NonNullConst<Version>,
+                    rhs: NonNullConst<Version>,
+                ) -> i32 {
+                    (base_interface(self).vtable.as_ref().version_compare_weak_fn)(
+                        base_interface(self).base_module,
+                        lhs,
+                        rhs,
+                    )
+                }
+
+                #[inline]
+                unsafe fn compare_strong(
+                    &self,
+                    lhs: NonNullConst<Version>,
+                    rhs: NonNullConst<Version>,
+                ) -> i32 {
+                    (base_interface(self).vtable.as_ref().version_compare_strong_fn)(
+                        base_interface(self).base_module,
+                        lhs,
+                        rhs,
+                    )
+                }
+
+                #[inline]
+                unsafe fn is_compatible(
+                    &self,
+                    lhs: NonNullConst<Version>,
+                    rhs: NonNullConst<Version>,
+                ) -> Bool {
+                    (base_interface(self).vtable.as_ref().version_is_compatible_fn)(
+                        base_interface(self).base_module,
+                        lhs,
+                        rhs,
+                    )
+                }
+            }
+        };
+    };
+}
 
-    #[inline]
-    unsafe fn as_string_long(
-        &self,
-        version: NonNullConst<Version>,
-        buffer: MutSpan<u8>,
-    ) -> Result<usize, Error> {
-        (self.vtable.as_ref().version_as_string_long_fn)(self.base_module, version, buffer)
-    }
+pub(crate) use impl_version_binding;
 
-    #[inline]
-    unsafe fn as_string_full(
-        &self,
-        version: NonNullConst<Version>,
-        buffer: MutSpan<u8>,
-    ) -> Result<usize, Error> {
-        (self.vtable.as_ref().version_as_string_full_fn)(self.base_module, version, buffer)
-    }
-
-    #[inline]
-    unsafe fn string_is_valid(&self, version_string: ConstSpan<u8>) -> Bool {
-        (self.vtable.as_ref().version_string_is_valid_fn)(self.base_module, version_string)
-    }
-
-    #[inline]
-    unsafe fn compare(&self, lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> i32 {
-        (self.vtable.as_ref().version_compare_fn)(self.base_module, lhs, rhs)
-    }
-
-    #[inline]
-    unsafe fn compare_weak(&self, lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> i32 {
-        (self.vtable.as_ref().version_compare_weak_fn)(self.base_module, lhs, rhs)
-    }
-
-    #[inline]
-    unsafe fn compare_strong(&self, lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> i32 {
-        (self.vtable.as_ref().version_compare_strong_fn)(self.base_module, lhs, rhs)
-    }
-
-    #[inline]
-    unsafe fn is_compatible(&self, lhs: NonNullConst<Version>, rhs: NonNullConst<Version>) -> Bool {
-        (self.vtable.as_ref().version_is_compatible_fn)(self.base_module, lhs, rhs)
-    }
-}
+impl_version_binding!(impl<> for CBaseInterface, self);